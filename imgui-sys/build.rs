@@ -19,7 +19,7 @@ fn get_all_source_files(path: &str) -> impl Iterator<Item = PathBuf> {
                     Ok(ft) => ft,
                     Err(_) => return None,
                 };
-                if file_type.is_file() && ["c", "cpp"].map(|s| OsStr::new(s)).contains(&f.path().extension().unwrap_or_default()) {
+                if file_type.is_file() && ["c", "cpp"].map(OsStr::new).contains(&f.path().extension().unwrap_or_default()) {
                     Some(f.path())
                 } else {
                     None
@@ -264,6 +264,10 @@ fn main() -> std::io::Result<()> {
             build.flag("-fno-exceptions").flag("-fno-rtti");
         }
 
+        // cc emits rerun-if-env-changed, which stops cargo from watching the package files
+        println!("cargo:rerun-if-changed={}", imgui_cpp);
+        println!("cargo:rerun-if-changed=imgui_rs_extras.cpp");
+
         // Build imgui lib, suppressing warnings.
         build.warnings(false).file(imgui_cpp).compile("libcimgui.a");
     }
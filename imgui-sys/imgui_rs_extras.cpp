@@ -0,0 +1,185 @@
+// Hand-written additions to the cimgui API, for functionality which Dear ImGui only exposes
+// through imgui_internal.h. These are included at the end of each `include_imgui_*.cpp` file,
+// after cimgui.cpp, and use an `imgui_rs_` prefix so they never clash with generated functions.
+
+CIMGUI_API ImGuiTableFlags imgui_rs_TableGetFlags()
+{
+    ImGuiTable* table = ImGui::GetCurrentTable();
+    return table ? table->Flags : ImGuiTableFlags_None;
+}
+
+// Whether the columns of the current table can no longer be resized or reordered this frame.
+CIMGUI_API bool imgui_rs_TableIsLayoutLocked()
+{
+    ImGuiTable* table = ImGui::GetCurrentTable();
+    IM_ASSERT(table != NULL);
+    return table->IsLayoutLocked;
+}
+
+// Unlike ImGui::TableSetColumnWidth(), this can be called before the first layout of a table, in
+// which case the width is applied the same way TableLoadSettings() does.
+CIMGUI_API void imgui_rs_TableSetColumnWidth(int column_n, float width)
+{
+    ImGuiTable* table = ImGui::GetCurrentTable();
+    IM_ASSERT(table != NULL && table->IsLayoutLocked == false);
+    IM_ASSERT(column_n >= 0 && column_n < table->ColumnsCount);
+    if (table->MinColumnWidth > 0.0f)
+    {
+        ImGui::TableSetColumnWidth(column_n, width);
+        return;
+    }
+    ImGuiTableColumn* column = &table->Columns[column_n];
+    if (column->Flags & ImGuiTableColumnFlags_WidthStretch)
+        column->StretchWeight = width; // Weights are relative, so widths keep their proportions
+    else
+        column->WidthRequest = width;
+    column->AutoFitQueue = 0x00;
+}
+
+// Reads back the state of a column in the current table which the user can change through the
+// headers: its width, display order, visibility and sort.
+CIMGUI_API void imgui_rs_TableGetColumnState(int column_n, ImGuiID* out_user_id, float* out_width, int* out_display_order, bool* out_enabled, int* out_sort_order, int* out_sort_direction)
+{
+    ImGuiTable* table = ImGui::GetCurrentTable();
+    IM_ASSERT(table != NULL);
+    IM_ASSERT(column_n >= 0 && column_n < table->ColumnsCount);
+    const ImGuiTableColumn* column = &table->Columns[column_n];
+    *out_user_id = column->UserID;
+    *out_width = column->IsEnabled ? column->WidthGiven : column->WidthRequest; // Hidden columns have no given width
+    *out_display_order = column->DisplayOrder;
+    *out_enabled = column->IsUserEnabled;
+    *out_sort_order = column->SortOrder;
+    *out_sort_direction = column->SortDirection;
+}
+
+// Moves a column to the given display order, shifting the columns in between. This mirrors the
+// reordering done by TableBeginApplyRequests() when the user drags a header.
+CIMGUI_API void imgui_rs_TableSetColumnDisplayOrder(int column_n, int order_n)
+{
+    ImGuiTable* table = ImGui::GetCurrentTable();
+    IM_ASSERT(table != NULL && table->IsLayoutLocked == false);
+    IM_ASSERT(column_n >= 0 && column_n < table->ColumnsCount);
+    IM_ASSERT(order_n >= 0 && order_n < table->ColumnsCount);
+    ImGuiTableColumn* column = &table->Columns[column_n];
+    const int src_order = column->DisplayOrder;
+    if (src_order == order_n)
+        return;
+    const int reorder_dir = (order_n > src_order) ? +1 : -1;
+    for (int other_order_n = src_order + reorder_dir; other_order_n != order_n + reorder_dir; other_order_n += reorder_dir)
+        table->Columns[table->DisplayOrderToIndex[other_order_n]].DisplayOrder -= (ImGuiTableColumnIdx)reorder_dir;
+    column->DisplayOrder = (ImGuiTableColumnIdx)order_n;
+    for (int other_column_n = 0; other_column_n < table->ColumnsCount; other_column_n++)
+        table->DisplayOrderToIndex[table->Columns[other_column_n].DisplayOrder] = (ImGuiTableColumnIdx)other_column_n;
+    table->IsSettingsDirty = true;
+}
+
+CIMGUI_API void imgui_rs_TableSetColumnSortDirection(int column_n, int sort_direction, bool append_to_sort_specs)
+{
+    ImGui::TableSetColumnSortDirection(column_n, (ImGuiSortDirection)sort_direction, append_to_sort_specs);
+}
+
+CIMGUI_API void imgui_rs_TableOpenContextMenu(int column_n)
+{
+    ImGui::TableOpenContextMenu(column_n);
+}
+
+// Lets widgets which don't submit items, such as gizmos drawn over a viewport, claim the mouse the
+// way an item would: a hovered id stops clicks from moving the window, and an active id keeps
+// other items from reacting to the mouse while dragging.
+CIMGUI_API void imgui_rs_SetHoveredID(ImGuiID id)
+{
+    ImGui::SetHoveredID(id);
+}
+
+CIMGUI_API void imgui_rs_SetActiveID(ImGuiID id)
+{
+    ImGuiContext& g = *GImGui;
+    if (g.ActiveId != id)
+        ImGui::SetActiveID(id, ImGui::GetCurrentWindow());
+    ImGui::KeepAliveID(id);
+}
+
+CIMGUI_API void imgui_rs_ClearActiveID(ImGuiID id)
+{
+    ImGuiContext& g = *GImGui;
+    if (g.ActiveId == id)
+        ImGui::ClearActiveID();
+}
+
+// Makes any rectangle of the current window a drop target rather than the last item, e.g. the gap
+// between two rows or a region of a canvas.
+CIMGUI_API bool imgui_rs_BeginDragDropTargetCustom(ImVec2 min, ImVec2 max, ImGuiID id)
+{
+    return ImGui::BeginDragDropTargetCustom(ImRect(min, max), id);
+}
+
+// Closes every popup above `remaining`, e.g. 0 to close all popups at once from outside of them.
+// ImGui asserts that at least one popup gets closed, so asking for the current level is a no-op.
+CIMGUI_API void imgui_rs_ClosePopupToLevel(int remaining, bool restore_focus_to_window_under_popup)
+{
+    ImGuiContext& g = *GImGui;
+    if (remaining < g.OpenPopupStack.Size)
+        ImGui::ClosePopupToLevel(remaining, restore_focus_to_window_under_popup);
+}
+
+// Closes the popup with the given id and the popups above it, if it is open, e.g. a popup which is
+// no longer submitted.
+CIMGUI_API void imgui_rs_ClosePopupById(ImGuiID id, bool restore_focus_to_window_under_popup)
+{
+    ImGuiContext& g = *GImGui;
+    for (int n = 0; n < g.OpenPopupStack.Size; n++)
+        if (g.OpenPopupStack[n].PopupId == id)
+        {
+            ImGui::ClosePopupToLevel(n, restore_focus_to_window_under_popup);
+            return;
+        }
+}
+
+CIMGUI_API int imgui_rs_GetOpenPopupCount()
+{
+    ImGuiContext& g = *GImGui;
+    return g.OpenPopupStack.Size;
+}
+
+CIMGUI_API bool imgui_rs_IsAnyModalPopupOpen()
+{
+    return ImGui::GetTopMostAndVisiblePopupModal() != NULL;
+}
+
+// Returns e.g. "Ctrl+Shift+S", in a buffer of the current context which is overwritten by the next
+// call.
+CIMGUI_API const char* imgui_rs_GetKeyChordName(ImGuiKeyChord key_chord)
+{
+    return ImGui::GetKeyChordName(key_chord);
+}
+
+// Returns the style flags which ImGuiHoveredFlags_ForTooltip stands for, which depend on whether the
+// last input was from the mouse or from keyboard/gamepad navigation, like IsItemHovered() does.
+CIMGUI_API ImGuiHoveredFlags imgui_rs_GetHoverFlagsForTooltip()
+{
+    ImGuiContext& g = *GImGui;
+    if (g.NavDisableMouseHover && !g.NavDisableHighlight)
+        return g.Style.HoverFlagsForTooltipNav;
+    return g.Style.HoverFlagsForTooltipMouse;
+}
+
+// Returns the DPI scale of the viewport the focused window is on, or of the main viewport if no
+// window is focused. Viewports have no DPI scale without docking, so that always returns 1.
+CIMGUI_API float imgui_rs_GetFocusedViewportDpiScale()
+{
+#ifdef IMGUI_HAS_DOCK
+    ImGuiContext& g = *GImGui;
+    if (g.NavWindow && g.NavWindow->Viewport)
+        return g.NavWindow->Viewport->DpiScale;
+    return ImGui::GetMainViewport()->DpiScale;
+#else
+    return 1.0f;
+#endif
+}
+
+// Draws the current window above all other windows, without focusing it.
+CIMGUI_API void imgui_rs_BringCurrentWindowToDisplayFront()
+{
+    ImGuiContext& g = *GImGui;
+    ImGui::BringWindowToDisplayFront(g.CurrentWindow);
+}
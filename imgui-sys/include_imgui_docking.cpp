@@ -8,3 +8,4 @@
 #include "./third-party/imgui-docking/imgui/imgui_widgets.cpp"
 #include "./third-party/imgui-docking/imgui/imgui_tables.cpp"
 #include "./third-party/imgui-docking/cimgui.cpp"
+#include "./imgui_rs_extras.cpp"
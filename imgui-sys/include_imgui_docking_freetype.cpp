@@ -8,5 +8,6 @@
 #include "./third-party/imgui-docking-freetype/imgui/imgui_widgets.cpp"
 #include "./third-party/imgui-docking-freetype/imgui/imgui_tables.cpp"
 #include "./third-party/imgui-docking-freetype/cimgui.cpp"
+#include "./imgui_rs_extras.cpp"
 
 #include "./third-party/imgui-docking-freetype/imgui/misc/freetype/imgui_freetype.cpp"
@@ -0,0 +1,50 @@
+//! Declarations of the hand-written functions in `imgui_rs_extras.cpp`, which wrap internal
+//! Dear ImGui functionality that cimgui doesn't expose.
+
+#![allow(nonstandard_style)]
+
+use core::ffi::c_char;
+
+use crate::{ImGuiHoveredFlags, ImGuiID, ImGuiKeyChord, ImGuiTableFlags, ImVec2};
+
+#[cfg_attr(feature = "wasm", link(wasm_import_module = "imgui-sys-v0"))]
+extern "C" {
+    pub fn imgui_rs_TableGetFlags() -> ImGuiTableFlags;
+    pub fn imgui_rs_TableIsLayoutLocked() -> bool;
+    pub fn imgui_rs_TableSetColumnWidth(column_n: i32, width: f32);
+    pub fn imgui_rs_TableGetColumnState(
+        column_n: i32,
+        out_user_id: *mut ImGuiID,
+        out_width: *mut f32,
+        out_display_order: *mut i32,
+        out_enabled: *mut bool,
+        out_sort_order: *mut i32,
+        out_sort_direction: *mut i32,
+    );
+    pub fn imgui_rs_TableSetColumnDisplayOrder(column_n: i32, order_n: i32);
+    pub fn imgui_rs_TableSetColumnSortDirection(
+        column_n: i32,
+        sort_direction: i32,
+        append_to_sort_specs: bool,
+    );
+    pub fn imgui_rs_TableOpenContextMenu(column_n: i32);
+
+    pub fn imgui_rs_SetHoveredID(id: ImGuiID);
+    pub fn imgui_rs_SetActiveID(id: ImGuiID);
+    pub fn imgui_rs_ClearActiveID(id: ImGuiID);
+
+    pub fn imgui_rs_BeginDragDropTargetCustom(min: ImVec2, max: ImVec2, id: ImGuiID) -> bool;
+
+    pub fn imgui_rs_ClosePopupToLevel(remaining: i32, restore_focus_to_window_under_popup: bool);
+    pub fn imgui_rs_ClosePopupById(id: ImGuiID, restore_focus_to_window_under_popup: bool);
+    pub fn imgui_rs_GetOpenPopupCount() -> i32;
+    pub fn imgui_rs_IsAnyModalPopupOpen() -> bool;
+
+    pub fn imgui_rs_GetKeyChordName(key_chord: ImGuiKeyChord) -> *const c_char;
+
+    pub fn imgui_rs_GetHoverFlagsForTooltip() -> ImGuiHoveredFlags;
+
+    pub fn imgui_rs_GetFocusedViewportDpiScale() -> f32;
+
+    pub fn imgui_rs_BringCurrentWindowToDisplayFront();
+}
@@ -1,4 +1,10 @@
 #![no_std]
+// Lints newer rustc versions raise on the generated bindings.
+#![allow(
+    unknown_lints,
+    unpredictable_function_pointer_comparisons,
+    unnecessary_transmutes
+)]
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm")] {
@@ -52,6 +58,9 @@ cfg_if::cfg_if! {
     }
 }
 
+mod extras;
+pub use crate::extras::*;
+
 impl ImVec2 {
     #[inline]
     pub const fn new(x: f32, y: f32) -> ImVec2 {
@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use std::ptr;
 
 use crate::clipboard::{ClipboardBackend, ClipboardContext};
+use crate::dpi_scaling::DpiScaling;
 use crate::fonts::atlas::{FontAtlas, FontId, SharedFontAtlas};
 use crate::io::Io;
 use crate::style::Style;
@@ -62,6 +63,7 @@ pub struct Context {
     // we also put it in an unsafecell since we're going to give
     // imgui a mutable pointer to it.
     clipboard_ctx: Box<UnsafeCell<ClipboardContext>>,
+    pub(crate) dpi_scaling: Option<Box<DpiScaling>>,
 
     ui: Ui,
 }
@@ -246,8 +248,10 @@ impl Context {
             platform_name: None,
             renderer_name: None,
             clipboard_ctx: Box::new(ClipboardContext::dummy().into()),
+            dpi_scaling: None,
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
+                owned_payloads: Default::default(),
             },
         }
     }
@@ -313,6 +317,8 @@ impl SuspendedContext {
     /// If there is already an active context, nothing happens and `Err` is returned, containing
     /// the original suspended context.
     #[doc(alias = "SetCurrentContext")]
+    // the context is handed back as-is on failure, boxing it would only add an allocation
+    #[allow(clippy::result_large_err)]
     pub fn activate(self) -> Result<Context, SuspendedContext> {
         let _guard = CTX_MUTEX.lock();
         if no_current_context() {
@@ -335,8 +341,10 @@ impl SuspendedContext {
             platform_name: None,
             renderer_name: None,
             clipboard_ctx: Box::new(ClipboardContext::dummy().into()),
+            dpi_scaling: None,
             ui: Ui {
                 buffer: UnsafeCell::new(crate::string::UiBuffer::new(1024)),
+                owned_payloads: Default::default(),
             },
         };
         if ctx.is_current_context() {
@@ -504,6 +512,9 @@ impl Context {
         }
     }
     /// Returns a mutable reference to the user interface style
+    ///
+    /// While managed DPI scaling is enabled, edits are overwritten whenever the style is
+    /// rescaled. Use [`Context::base_style_mut`] instead.
     #[doc(alias = "GetStyle")]
     pub fn style_mut(&mut self) -> &mut Style {
         unsafe {
@@ -532,8 +543,12 @@ impl Context {
     }
 
     /// Starts a new frame and returns an `Ui` instance for constructing a user interface.
+    ///
+    /// If managed DPI scaling is enabled, this also rescales the style if needed, but never
+    /// rebuilds fonts. See [`Context::update_dpi_scaling`].
     #[doc(alias = "NewFrame")]
     pub fn new_frame(&mut self) -> &mut Ui {
+        self.update_dpi_style();
         // Clear default font if it no longer exists. This could be an error in the future
         let default_font = self.io().font_default;
         if !default_font.is_null() && self.fonts().get_font(FontId(default_font)).is_none() {
@@ -543,6 +558,8 @@ impl Context {
         unsafe {
             sys::igNewFrame();
         }
+        // Drop the owned drag and drop payload once its drag was delivered or cancelled
+        self.ui.owned_payloads.get_mut().retain_current();
 
         &mut self.ui
     }
@@ -0,0 +1,514 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use crate::{
+    Id, ListClipper, MouseButton, SelectableFlags, TableColumnFlags, TableColumnSetup, TableFlags,
+    TableRowFlags, TableSortDirection, TextFilter, Ui,
+};
+
+type CellRenderer<T> = Box<dyn FnMut(&Ui, &T)>;
+type Comparator<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+type FilterText<T> = Box<dyn Fn(&T) -> String>;
+
+/// How the rows of a [DataGrid] can be selected by the user.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum DataGridSelection {
+    /// Rows can't be selected.
+    #[default]
+    None,
+    /// A single row can be selected by clicking it.
+    Single,
+    /// Many rows can be selected, holding ctrl to toggle rows and shift to select ranges.
+    Multiple,
+}
+
+/// A column of a [DataGrid], built with [DataGridColumn::new].
+#[must_use]
+pub struct DataGridColumn<T> {
+    name: String,
+    flags: TableColumnFlags,
+    init_width_or_weight: f32,
+    render: CellRenderer<T>,
+    compare: Option<Comparator<T>>,
+    filter_text: Option<FilterText<T>>,
+}
+
+impl<T> DataGridColumn<T> {
+    /// Creates a column with the given header, which draws its cells with `render`.
+    ///
+    /// `render` is called with the cursor at the start of the cell, and can draw any widgets.
+    pub fn new(name: impl Into<String>, render: impl FnMut(&Ui, &T) + 'static) -> Self {
+        DataGridColumn {
+            name: name.into(),
+            flags: TableColumnFlags::empty(),
+            init_width_or_weight: 0.0,
+            render: Box::new(render),
+            compare: None,
+            filter_text: None,
+        }
+    }
+
+    /// Replaces all current column flags with the given flags.
+    pub fn flags(mut self, flags: TableColumnFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the initial width, or weight for stretch columns, of the column.
+    pub fn width(mut self, init_width_or_weight: f32) -> Self {
+        self.init_width_or_weight = init_width_or_weight;
+        self
+    }
+
+    /// Makes the column sortable, ordering rows with the given comparator.
+    ///
+    /// Columns without a comparator can't be sorted on.
+    pub fn comparator(mut self, compare: impl Fn(&T, &T) -> Ordering + 'static) -> Self {
+        self.compare = Some(Box::new(compare));
+        self
+    }
+
+    /// Makes the column sortable, ordering rows by the given key.
+    pub fn sort_by_key<K: Ord>(self, key: impl Fn(&T) -> K + 'static) -> Self {
+        self.comparator(move |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Makes the column searchable by the filter of the grid, matching it against the text
+    /// returned by `text`.
+    ///
+    /// A row passes the filter if the text of any searchable column passes it.
+    pub fn filter_text(mut self, text: impl Fn(&T) -> String + 'static) -> Self {
+        self.filter_text = Some(Box::new(text));
+        self
+    }
+}
+
+/// What happened in a [DataGrid] this frame, as returned by [DataGrid::build].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct DataGridResponse {
+    /// The index of the row which was clicked, if any.
+    pub clicked: Option<usize>,
+    /// The index of the row which was double-clicked, if any.
+    pub double_clicked: Option<usize>,
+    /// Whether the selection changed.
+    pub selection_changed: bool,
+}
+
+/// A sortable, filterable table over a slice of items, which only draws the visible rows.
+///
+/// A grid is declared once with its columns, and kept around between frames, as it keeps the
+/// order of the rows, the sort and the selection. Every frame, [build](Self::build) draws it over
+/// the current items. Rows are identified by their index in the items slice.
+///
+/// The order of the rows is only recomputed when the sort, the filter, or the number of items
+/// changes. If items change in place, call [refresh](Self::refresh).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// struct Entry {
+///     name: String,
+///     size: u64,
+/// }
+///
+/// let mut grid = DataGrid::new("files")
+///     .column(
+///         DataGridColumn::new("Name", |ui, entry: &Entry| ui.text(&entry.name))
+///             .sort_by_key(|entry| entry.name.clone())
+///             .filter_text(|entry| entry.name.clone()),
+///     )
+///     .column(
+///         DataGridColumn::new("Size", |ui, entry: &Entry| ui.text(entry.size.to_string()))
+///             .sort_by_key(|entry| entry.size),
+///     )
+///     .filter(TextFilter::new("Search".to_string()))
+///     .selection(DataGridSelection::Multiple);
+///
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let entries: Vec<Entry> = Vec::new();
+/// let response = grid.build(ui, &entries);
+/// if let Some(row) = response.double_clicked {
+///     println!("opening {}", entries[row].name);
+/// }
+/// ```
+#[must_use]
+pub struct DataGrid<T> {
+    id: String,
+    columns: Vec<DataGridColumn<T>>,
+    flags: TableFlags,
+    outer_size: [f32; 2],
+    row_height: f32,
+    selection: DataGridSelection,
+    filter: Option<TextFilter>,
+
+    /// Indices of the items which pass the filter, in sorted order.
+    view: Vec<usize>,
+    view_dirty: bool,
+    item_count: usize,
+    sort: Vec<(usize, TableSortDirection)>,
+    selected: BTreeSet<usize>,
+    /// The row shift-clicks select a range from.
+    anchor: Option<usize>,
+}
+
+impl<T> DataGrid<T> {
+    /// Creates a grid without columns, which are added with [column](Self::column).
+    ///
+    /// The grid is resizable, reorderable, hideable and scrolls vertically, with the header
+    /// row frozen at the top.
+    pub fn new(id: impl Into<String>) -> Self {
+        DataGrid {
+            id: id.into(),
+            columns: Vec::new(),
+            flags: TableFlags::RESIZABLE
+                | TableFlags::REORDERABLE
+                | TableFlags::HIDEABLE
+                | TableFlags::ROW_BG
+                | TableFlags::BORDERS_OUTER
+                | TableFlags::BORDERS_INNER_V
+                | TableFlags::SCROLL_Y,
+            outer_size: [0.0, 0.0],
+            row_height: 0.0,
+            selection: DataGridSelection::None,
+            filter: None,
+            view: Vec::new(),
+            view_dirty: true,
+            item_count: 0,
+            sort: Vec::new(),
+            selected: BTreeSet::new(),
+            anchor: None,
+        }
+    }
+
+    /// Adds a column to the grid.
+    pub fn column(mut self, column: DataGridColumn<T>) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Replaces all current table flags with the given flags.
+    ///
+    /// [TableFlags::SORTABLE] is added automatically when any column has a comparator. Add
+    /// [TableFlags::SORT_MULTI] to sort on several columns with shift-click.
+    pub fn flags(mut self, flags: TableFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the size of the table. With the default [TableFlags::SCROLL_Y], a height of `0.0`
+    /// fills the remaining height of the window.
+    pub fn outer_size(mut self, outer_size: [f32; 2]) -> Self {
+        self.outer_size = outer_size;
+        self
+    }
+
+    /// Sets the minimum height of the rows, for cells taller than a line of text.
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// Sets how rows can be selected.
+    pub fn selection(mut self, selection: DataGridSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets a filter, which is drawn above the table and matched against the columns with
+    /// [filter_text](DataGridColumn::filter_text).
+    pub fn filter(mut self, filter: TextFilter) -> Self {
+        self.filter = Some(filter);
+        self.view_dirty = true;
+        self
+    }
+
+    /// Draws the grid over `items`.
+    pub fn build(&mut self, ui: &Ui, items: &[T]) -> DataGridResponse {
+        let mut response = DataGridResponse::default();
+
+        if let Some(filter) = &self.filter {
+            if filter.draw() {
+                self.view_dirty = true;
+            }
+        }
+        if items.len() != self.item_count {
+            self.item_count = items.len();
+            self.selected.retain(|&row| row < items.len());
+            self.view_dirty = true;
+        }
+
+        let mut flags = self.flags;
+        if self.columns.iter().any(|column| column.compare.is_some()) {
+            flags |= TableFlags::SORTABLE;
+        }
+        let Some(_table) =
+            ui.begin_table_with_sizing(&self.id, self.columns.len(), flags, self.outer_size, 0.0)
+        else {
+            return response;
+        };
+
+        for (idx, column) in self.columns.iter().enumerate() {
+            let mut flags = column.flags;
+            if column.compare.is_none() {
+                flags |= TableColumnFlags::NO_SORT;
+            }
+            ui.table_setup_column_with(TableColumnSetup {
+                name: column.name.as_str(),
+                flags,
+                init_width_or_weight: column.init_width_or_weight,
+                user_id: Id(idx as u32),
+            });
+        }
+        ui.table_setup_scroll_freeze(0, 1);
+
+        if let Some(mut specs) = ui.table_sort_specs_mut() {
+            // also read the specs when rebuilding anyway, as the table may be older than the grid
+            if specs.should_sort() || self.view_dirty {
+                self.sort = specs
+                    .specs()
+                    .iter()
+                    .filter_map(|spec| Some((spec.column_idx(), spec.sort_direction()?)))
+                    .collect();
+                specs.set_sorted();
+                self.view_dirty = true;
+            }
+        }
+        if self.view_dirty {
+            self.rebuild_view(items);
+        }
+
+        ui.table_headers_row();
+
+        let mut clicked = None;
+        let clipper = ListClipper::new(self.view.len() as i32).begin(ui);
+        for pos in clipper.iter() {
+            let pos = pos as usize;
+            let row = self.view[pos];
+            ui.table_next_row_with_height(TableRowFlags::empty(), self.row_height);
+            let _id = ui.push_id_usize(row);
+
+            let mut selectable_drawn = self.selection == DataGridSelection::None;
+            for (idx, column) in self.columns.iter_mut().enumerate() {
+                if !ui.table_set_column_index(idx) {
+                    continue;
+                }
+                if !selectable_drawn {
+                    // the selectable spans the row, and the cell is drawn over it
+                    let cursor = ui.cursor_pos();
+                    let is_selected = self.selected.contains(&row);
+                    if ui
+                        .selectable_config("##row")
+                        .selected(is_selected)
+                        .flags(
+                            SelectableFlags::SPAN_ALL_COLUMNS
+                                | SelectableFlags::ALLOW_OVERLAP
+                                | SelectableFlags::ALLOW_DOUBLE_CLICK,
+                        )
+                        .size([0.0, self.row_height])
+                        .build()
+                    {
+                        clicked = Some((pos, row));
+                        if ui.is_mouse_double_clicked(MouseButton::Left) {
+                            response.double_clicked = Some(row);
+                        }
+                    }
+                    ui.set_cursor_pos(cursor);
+                    selectable_drawn = true;
+                }
+                (column.render)(ui, &items[row]);
+            }
+        }
+
+        if let Some((pos, row)) = clicked {
+            let io = ui.io();
+            response.clicked = Some(row);
+            response.selection_changed = self.click(pos, row, io.key_ctrl, io.key_shift);
+        }
+        response
+    }
+
+    fn rebuild_view(&mut self, items: &[T]) {
+        let filter = self.filter.as_ref().filter(|filter| filter.is_active());
+        let texts: Vec<_> = self
+            .columns
+            .iter()
+            .filter_map(|column| column.filter_text.as_ref())
+            .collect();
+
+        self.view.clear();
+        self.view.extend((0..items.len()).filter(|&row| {
+            match filter {
+                Some(filter) if !texts.is_empty() => texts
+                    .iter()
+                    .any(|text| filter.pass_filter(&text(&items[row]))),
+                _ => true,
+            }
+        }));
+
+        let columns = &self.columns;
+        let sort = &self.sort;
+        if !sort.is_empty() {
+            // a stable sort, so rows which compare equal keep their order
+            self.view.sort_by(|&a, &b| {
+                for &(idx, direction) in sort {
+                    let Some(compare) = columns.get(idx).and_then(|c| c.compare.as_ref()) else {
+                        continue;
+                    };
+                    let ordering = match direction {
+                        TableSortDirection::Ascending => compare(&items[a], &items[b]),
+                        TableSortDirection::Descending => compare(&items[b], &items[a]),
+                    };
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                Ordering::Equal
+            });
+        }
+        self.view_dirty = false;
+    }
+
+    /// Applies a click on the row at `pos` in the view, returning whether the selection changed.
+    fn click(&mut self, pos: usize, row: usize, ctrl: bool, shift: bool) -> bool {
+        let before = self.selected.clone();
+        match self.selection {
+            DataGridSelection::None => return false,
+            DataGridSelection::Single => {
+                self.selected.clear();
+                self.selected.insert(row);
+            }
+            DataGridSelection::Multiple => {
+                let anchor_pos = self
+                    .anchor
+                    .and_then(|anchor| self.view.iter().position(|&r| r == anchor));
+                match anchor_pos {
+                    Some(anchor_pos) if shift => {
+                        if !ctrl {
+                            self.selected.clear();
+                        }
+                        let range = anchor_pos.min(pos)..=anchor_pos.max(pos);
+                        self.selected.extend(&self.view[range]);
+                        // keep the anchor, so the range can be adjusted
+                        return self.selected != before;
+                    }
+                    _ if ctrl => {
+                        if !self.selected.remove(&row) {
+                            self.selected.insert(row);
+                        }
+                    }
+                    _ => {
+                        self.selected.clear();
+                        self.selected.insert(row);
+                    }
+                }
+            }
+        }
+        self.anchor = Some(row);
+        self.selected != before
+    }
+
+    /// Marks the order of the rows as outdated, so it's sorted and filtered again on the next
+    /// [build](Self::build). Call this when items changed without their number changing.
+    pub fn refresh(&mut self) {
+        self.view_dirty = true;
+    }
+
+    /// Returns the indices of the rows which pass the filter, in the order they are displayed.
+    ///
+    /// This is updated by [build](Self::build).
+    pub fn visible_rows(&self) -> &[usize] {
+        &self.view
+    }
+
+    /// Returns the indices of the selected rows, in ascending order.
+    pub fn selected_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected.iter().copied()
+    }
+
+    /// Returns `true` if the given row is selected.
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selected.contains(&row)
+    }
+
+    /// Selects or deselects the given row.
+    pub fn set_selected(&mut self, row: usize, selected: bool) {
+        if selected {
+            self.selected.insert(row);
+        } else {
+            self.selected.remove(&row);
+        }
+    }
+
+    /// Deselects all rows.
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+    }
+
+    /// Returns the filter of the grid, if it has one.
+    pub fn text_filter(&self) -> Option<&TextFilter> {
+        self.filter.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_grid() -> DataGrid<u32> {
+        DataGrid::new("grid")
+            .column(
+                DataGridColumn::new("Value", |ui, value: &u32| ui.text(value.to_string()))
+                    .flags(
+                        TableColumnFlags::DEFAULT_SORT | TableColumnFlags::PREFER_SORT_DESCENDING,
+                    )
+                    .sort_by_key(|value| *value)
+                    .filter_text(|value| value.to_string()),
+            )
+            .selection(DataGridSelection::Multiple)
+    }
+
+    #[test]
+    fn test_data_grid_sorts_and_filters() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let items = [3, 10, 1, 12, 2];
+
+        let mut grid = test_grid();
+        let ui = ctx.frame();
+        ui.window("grid").build(|| {
+            grid.build(ui, &items);
+        });
+        ctx.render();
+        assert_eq!(grid.visible_rows(), &[3, 1, 0, 4, 2]);
+
+        let mut grid = test_grid().filter(TextFilter::new_with_filter(
+            "filter".to_string(),
+            "1".to_string(),
+        ));
+        let ui = ctx.frame();
+        ui.window("grid").build(|| {
+            grid.build(ui, &items);
+        });
+        assert_eq!(grid.visible_rows(), &[3, 1, 2]);
+    }
+
+    #[test]
+    fn test_data_grid_selection() {
+        let mut grid = test_grid();
+        grid.view = vec![4, 3, 2, 1, 0];
+
+        assert!(grid.click(1, 3, false, false));
+        assert!(grid.click(3, 1, false, true));
+        assert_eq!(grid.selected_rows().collect::<Vec<_>>(), [1, 2, 3]);
+
+        assert!(grid.click(4, 0, true, false));
+        assert!(grid.click(2, 2, true, false));
+        assert_eq!(grid.selected_rows().collect::<Vec<_>>(), [0, 1, 3]);
+
+        grid.clear_selection();
+        assert!(grid.click(0, 4, false, true));
+        assert_eq!(grid.selected_rows().collect::<Vec<_>>(), [4]);
+    }
+}
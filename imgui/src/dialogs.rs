@@ -0,0 +1,600 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::math::MintVec4;
+use crate::{sys, Condition, Key, MouseButton, Ui};
+
+/// The error returned by a [DialogHandle] when its dialog was cancelled, with the cancel button,
+/// Escape, or [Dialogs::cancel_all].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DialogCancelled;
+
+impl fmt::Display for DialogCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the dialog was cancelled")
+    }
+}
+
+impl std::error::Error for DialogCancelled {}
+
+type ResultSlot<T> = Rc<RefCell<Option<Result<T, DialogCancelled>>>>;
+
+/// The answer to a dialog queued on [Dialogs], which arrives on a later frame.
+///
+/// Dropping the handle withdraws the dialog: it is skipped if it's still queued, and closed if
+/// it's shown.
+#[derive(Debug)]
+#[must_use]
+pub struct DialogHandle<T> {
+    slot: ResultSlot<T>,
+}
+
+impl<T> DialogHandle<T> {
+    /// Returns the answer once the dialog is closed, `Ok` if it was accepted and
+    /// `Err(DialogCancelled)` otherwise.
+    ///
+    /// The answer is moved out, so this returns `None` again afterwards.
+    pub fn poll(&mut self) -> Option<Result<T, DialogCancelled>> {
+        self.slot.borrow_mut().take()
+    }
+
+    /// Returns `true` if the dialog hasn't been answered yet.
+    pub fn is_pending(&self) -> bool {
+        self.slot.borrow().is_none() && Rc::strong_count(&self.slot) > 1
+    }
+}
+
+/// The state of the widgets of a queued dialog.
+#[derive(Clone, Debug)]
+enum DialogBody {
+    Confirm,
+    Prompt {
+        text: String,
+    },
+    Choose {
+        options: Vec<String>,
+        selected: usize,
+    },
+}
+
+/// Turns the state of an accepted dialog into the value its [DialogHandle] is waiting for.
+trait Responder {
+    /// Sends the answer to the handle, or returns why the input can't be accepted.
+    fn respond(&mut self, answer: Option<&DialogBody>) -> Result<(), String>;
+
+    /// Returns `true` if the handle was dropped.
+    fn is_abandoned(&self) -> bool;
+}
+
+struct Slot<T, F> {
+    slot: ResultSlot<T>,
+    parse: F,
+}
+
+impl<T, F> Responder for Slot<T, F>
+where
+    F: FnMut(&DialogBody) -> Result<T, String>,
+{
+    fn respond(&mut self, answer: Option<&DialogBody>) -> Result<(), String> {
+        let result = match answer {
+            Some(body) => Ok((self.parse)(body)?),
+            None => Err(DialogCancelled),
+        };
+        *self.slot.borrow_mut() = Some(result);
+        Ok(())
+    }
+
+    fn is_abandoned(&self) -> bool {
+        Rc::strong_count(&self.slot) == 1
+    }
+}
+
+struct QueuedDialog {
+    id: u64,
+    message: String,
+    body: DialogBody,
+    error: Option<String>,
+    opened: bool,
+    focus_input: bool,
+    responder: Box<dyn Responder>,
+}
+
+/// How a dialog was closed this frame.
+enum Outcome {
+    Accepted,
+    Cancelled,
+}
+
+/// A queue of modal dialogs whose answers are delivered to [DialogHandle]s, so asking the user
+/// something doesn't need a state machine around a [PopupModal](crate::PopupModal).
+///
+/// Dialogs are queued with [confirm](Self::confirm), [prompt](Self::prompt) and
+/// [choose](Self::choose), and shown one at a time, centered on the current viewport, by calling
+/// [build](Self::build) once per frame. Enter accepts the dialog and Escape cancels it.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut dialogs = Dialogs::new();
+/// let mut delete = None;
+/// let mut rename = None;
+/// if ui.button("Delete") {
+///     delete = Some(dialogs.confirm("Delete the selected files?"));
+/// }
+/// if ui.button("Rename") {
+///     rename = Some(dialogs.prompt_with_default("New name", String::from("untitled")));
+/// }
+/// // Every frame:
+/// dialogs.build(ui);
+/// if let Some(Ok(())) = delete.as_mut().and_then(DialogHandle::poll) {
+///     // Delete the files
+/// }
+/// if let Some(Ok(name)) = rename.as_mut().and_then(DialogHandle::poll) {
+///     // Rename to `name`
+/// }
+/// ```
+pub struct Dialogs {
+    width: f32,
+    ok_label: String,
+    cancel_label: String,
+    error_color: [f32; 4],
+    next_id: u64,
+    queue: VecDeque<QueuedDialog>,
+}
+
+impl Default for Dialogs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Dialogs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dialogs")
+            .field("width", &self.width)
+            .field("ok_label", &self.ok_label)
+            .field("cancel_label", &self.cancel_label)
+            .field("error_color", &self.error_color)
+            .field("pending", &self.queue.len())
+            .finish()
+    }
+}
+
+impl Dialogs {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        Dialogs {
+            width: 320.0,
+            ok_label: String::from("OK"),
+            cancel_label: String::from("Cancel"),
+            error_color: [1.0, 0.4, 0.4, 1.0],
+            next_id: 0,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Sets the width of the dialogs, in pixels. Messages are wrapped to fit.
+    ///
+    /// Defaults to 320.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the label of the button which accepts a dialog.
+    ///
+    /// Defaults to "OK".
+    pub fn ok_label(mut self, label: impl Into<String>) -> Self {
+        self.ok_label = label.into();
+        self
+    }
+
+    /// Sets the label of the button which cancels a dialog.
+    ///
+    /// Defaults to "Cancel".
+    pub fn cancel_label(mut self, label: impl Into<String>) -> Self {
+        self.cancel_label = label.into();
+        self
+    }
+
+    /// Sets the color of the error shown when a prompt's text doesn't parse.
+    ///
+    /// Defaults to a light red.
+    pub fn error_color(mut self, color: impl Into<MintVec4>) -> Self {
+        self.error_color = color.into().into();
+        self
+    }
+
+    /// Queues a dialog asking the user to confirm something, answered with `Ok(())`.
+    pub fn confirm(&mut self, message: impl Into<String>) -> DialogHandle<()> {
+        self.push(message.into(), DialogBody::Confirm, |_| Ok(()))
+    }
+
+    /// Queues a dialog asking the user to type a value, parsed with [FromStr].
+    ///
+    /// The dialog stays open, showing the parse error, until the text parses.
+    pub fn prompt<T>(&mut self, message: impl Into<String>) -> DialogHandle<T>
+    where
+        T: FromStr + 'static,
+        T::Err: fmt::Display,
+    {
+        self.push_prompt(message.into(), String::new())
+    }
+
+    /// Queues a dialog asking the user to type a value, like [prompt](Self::prompt), starting
+    /// from `default`.
+    pub fn prompt_with_default<T>(
+        &mut self,
+        message: impl Into<String>,
+        default: T,
+    ) -> DialogHandle<T>
+    where
+        T: FromStr + ToString + 'static,
+        T::Err: fmt::Display,
+    {
+        self.push_prompt(message.into(), default.to_string())
+    }
+
+    /// Queues a dialog asking the user to pick one of `options`, answered with its index.
+    ///
+    /// Options can be picked with a double click, or highlighted with a click or the arrow keys
+    /// and accepted.
+    pub fn choose<S: AsRef<str>>(&mut self, options: &[S]) -> DialogHandle<usize> {
+        let options = options.iter().map(|o| o.as_ref().to_owned()).collect();
+        let body = DialogBody::Choose {
+            options,
+            selected: 0,
+        };
+        self.push(String::new(), body, |body| match body {
+            DialogBody::Choose { options, selected } if *selected < options.len() => Ok(*selected),
+            _ => Err(String::from("There is nothing to choose")),
+        })
+    }
+
+    /// Returns the number of dialogs shown or waiting to be shown.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if no dialog is shown or waiting to be shown.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Cancels every queued dialog, including the one shown.
+    pub fn cancel_all(&mut self) {
+        for mut dialog in self.queue.drain(..) {
+            let _ = dialog.responder.respond(None);
+        }
+    }
+
+    fn push_prompt<T>(&mut self, message: String, text: String) -> DialogHandle<T>
+    where
+        T: FromStr + 'static,
+        T::Err: fmt::Display,
+    {
+        self.push(message, DialogBody::Prompt { text }, |body| match body {
+            DialogBody::Prompt { text } => text.parse().map_err(|e: T::Err| e.to_string()),
+            _ => unreachable!(),
+        })
+    }
+
+    fn push<T, F>(&mut self, message: String, body: DialogBody, parse: F) -> DialogHandle<T>
+    where
+        T: 'static,
+        F: FnMut(&DialogBody) -> Result<T, String> + 'static,
+    {
+        let slot = ResultSlot::default();
+        self.queue.push_back(QueuedDialog {
+            id: self.next_id,
+            message,
+            body,
+            error: None,
+            opened: false,
+            focus_input: true,
+            responder: Box::new(Slot {
+                slot: slot.clone(),
+                parse,
+            }),
+        });
+        self.next_id += 1;
+        DialogHandle { slot }
+    }
+
+    /// Shows the first queued dialog, which must be done every frame, whether dialogs are
+    /// queued or not.
+    ///
+    /// This should be called outside of any popup. Answers are sent to the handles, and the
+    /// next dialog is shown on the following frame.
+    #[doc(alias = "BeginPopupModal")]
+    pub fn build(&mut self, ui: &Ui) {
+        while self
+            .queue
+            .front()
+            .is_some_and(|dialog| dialog.responder.is_abandoned())
+        {
+            let dialog = self.queue.pop_front().unwrap();
+            if dialog.opened {
+                // Nothing submits the popup anymore, so it has to be closed explicitly
+                let id = ui.new_id_str(format!("##Dialog{}", dialog.id));
+                unsafe { sys::imgui_rs_ClosePopupById(id.0, true) };
+            }
+        }
+        let Some(dialog) = self.queue.front_mut() else {
+            return;
+        };
+
+        let popup_id = format!("##Dialog{}", dialog.id);
+        if !dialog.opened {
+            dialog.opened = true;
+            ui.open_popup(&popup_id);
+        }
+
+        let (work_pos, work_size) = unsafe {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "docking")] {
+                    let viewport = &*sys::igGetWindowViewport();
+                } else {
+                    let viewport = &*sys::igGetMainViewport();
+                }
+            }
+            (viewport.WorkPos, viewport.WorkSize)
+        };
+        let center = sys::ImVec2::new(
+            work_pos.x + work_size.x * 0.5,
+            work_pos.y + work_size.y * 0.5,
+        );
+        unsafe {
+            sys::igSetNextWindowPos(center, Condition::Always as i32, sys::ImVec2::new(0.5, 0.5));
+            // A zero height fits the window to its contents
+            sys::igSetNextWindowSize(sys::ImVec2::new(self.width, 0.0), Condition::Always as i32);
+        }
+        let style = DialogStyle {
+            ok_label: &self.ok_label,
+            cancel_label: &self.cancel_label,
+            error_color: self.error_color,
+        };
+        let answered = ui
+            .modal_popup_config(&popup_id)
+            .title_bar(false)
+            .resizable(false)
+            .movable(false)
+            .save_settings(false)
+            .build(|| {
+                let answer = match build_contents(ui, dialog, &style) {
+                    None => return false,
+                    Some(Outcome::Accepted) => Some(&dialog.body),
+                    Some(Outcome::Cancelled) => None,
+                };
+                match dialog.responder.respond(answer) {
+                    Ok(()) => {
+                        ui.close_current_popup();
+                        true
+                    }
+                    Err(error) => {
+                        // Keep the dialog open until the input is valid
+                        dialog.error = Some(error);
+                        dialog.focus_input = true;
+                        false
+                    }
+                }
+            });
+
+        let answered = answered.unwrap_or_else(|| {
+            // The popup is gone if it was closed from elsewhere
+            let _ = dialog.responder.respond(None);
+            true
+        });
+        if answered {
+            self.queue.pop_front();
+        }
+    }
+}
+
+/// The settings of [Dialogs] used to draw a dialog.
+struct DialogStyle<'a> {
+    ok_label: &'a str,
+    cancel_label: &'a str,
+    error_color: [f32; 4],
+}
+
+/// Draws the widgets of a dialog, and returns `Some` once it should close.
+fn build_contents(ui: &Ui, dialog: &mut QueuedDialog, style: &DialogStyle<'_>) -> Option<Outcome> {
+    let appearing = ui.is_window_appearing();
+    // Keys pressed in the frame the dialog appears were meant for something else
+    let pressed = |key| !appearing && ui.is_key_pressed(key);
+    if !dialog.message.is_empty() {
+        ui.text_wrapped(&dialog.message);
+    }
+
+    let mut accepted = false;
+    match &mut dialog.body {
+        DialogBody::Confirm => {}
+        DialogBody::Prompt { text } => {
+            if std::mem::take(&mut dialog.focus_input) {
+                ui.set_keyboard_focus_here();
+            }
+            ui.set_next_item_width(-f32::MIN_POSITIVE);
+            accepted |= ui
+                .input_text("##prompt", text)
+                .enter_returns_true(true)
+                .auto_select_all(true)
+                .build();
+        }
+        DialogBody::Choose { options, selected } => {
+            if !options.is_empty() {
+                let last = options.len() - 1;
+                if pressed(Key::UpArrow) {
+                    *selected = selected.saturating_sub(1);
+                } else if pressed(Key::DownArrow) {
+                    *selected = (*selected + 1).min(last);
+                }
+            }
+            for (index, option) in options.iter().enumerate() {
+                let _id = ui.push_id_usize(index);
+                if ui
+                    .selectable_config(option)
+                    .selected(index == *selected)
+                    .allow_double_click(true)
+                    .build()
+                {
+                    *selected = index;
+                    accepted |= ui.is_mouse_double_clicked(MouseButton::Left);
+                }
+            }
+        }
+    }
+    if let Some(error) = &dialog.error {
+        ui.text_colored(style.error_color, error);
+    }
+
+    ui.spacing();
+    let (ok_label, cancel_label) = (style.ok_label, style.cancel_label);
+    let ui_style = ui.clone_style();
+    let text_width = ui.calc_text_size(ok_label)[0].max(ui.calc_text_size(cancel_label)[0]);
+    let button_width = text_width + ui_style.frame_padding[0] * 2.0;
+    let button_width = button_width.max(80.0);
+    let spacing = ui_style.item_spacing[0];
+    let start = ui.content_region_avail()[0] - button_width * 2.0 - spacing;
+    ui.set_cursor_pos([ui.cursor_pos()[0] + start.max(0.0), ui.cursor_pos()[1]]);
+    accepted |= ui.button_with_size(ok_label, [button_width, 0.0]);
+    if appearing && matches!(dialog.body, DialogBody::Confirm) {
+        ui.set_item_default_focus();
+    }
+    ui.same_line();
+    let cancelled = ui.button_with_size(cancel_label, [button_width, 0.0]);
+
+    if cancelled || pressed(Key::Escape) {
+        Some(Outcome::Cancelled)
+    } else if accepted || pressed(Key::Enter) || pressed(Key::KeypadEnter) {
+        Some(Outcome::Accepted)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queued_dialogs_answer_in_order() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut dialogs = Dialogs::new();
+
+        // Presses and releases the key in separate frames, like a real key press
+        let mut frame = |dialogs: &mut Dialogs, key: Option<Key>| {
+            for down in [true, false] {
+                if let Some(key) = key {
+                    ctx.io_mut().add_key_event(key, down);
+                }
+                let ui = ctx.frame();
+                dialogs.build(ui);
+                ctx.render();
+            }
+        };
+
+        let mut confirm = dialogs.confirm("Delete?");
+        let mut count = dialogs.prompt_with_default("Count", 3u32);
+        let mut choice = dialogs.choose(&["Red", "Green", "Blue"]);
+        let dropped = dialogs.confirm("Never shown");
+        drop(dropped);
+        assert_eq!(dialogs.len(), 4);
+
+        frame(&mut dialogs, None);
+        assert!(confirm.is_pending());
+        assert_eq!(confirm.poll(), None);
+        frame(&mut dialogs, Some(Key::Enter));
+        assert_eq!(confirm.poll(), Some(Ok(())));
+        assert!(!confirm.is_pending());
+        assert_eq!(confirm.poll(), None);
+
+        frame(&mut dialogs, None);
+        frame(&mut dialogs, Some(Key::Enter));
+        assert_eq!(count.poll(), Some(Ok(3)));
+
+        frame(&mut dialogs, None);
+        frame(&mut dialogs, Some(Key::DownArrow));
+        frame(&mut dialogs, Some(Key::DownArrow));
+        frame(&mut dialogs, Some(Key::Enter));
+        assert_eq!(choice.poll(), Some(Ok(2)));
+
+        // The dialog whose handle was dropped is skipped
+        frame(&mut dialogs, None);
+        assert!(dialogs.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_prompt_stays_open_and_escape_cancels() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut dialogs = Dialogs::new();
+        let mut frame = |dialogs: &mut Dialogs, key: Option<Key>| {
+            for down in [true, false] {
+                if let Some(key) = key {
+                    ctx.io_mut().add_key_event(key, down);
+                }
+                let ui = ctx.frame();
+                dialogs.build(ui);
+                ctx.render();
+            }
+        };
+
+        let mut number = dialogs.prompt::<i32>("Number");
+        frame(&mut dialogs, None);
+        frame(&mut dialogs, Some(Key::Enter));
+        assert_eq!(number.poll(), None);
+        assert!(number.is_pending());
+        assert!(dialogs.queue[0].error.is_some());
+
+        frame(&mut dialogs, Some(Key::Escape));
+        assert_eq!(number.poll(), Some(Err(DialogCancelled)));
+        assert!(dialogs.is_empty());
+
+        let mut later = dialogs.confirm("Later");
+        dialogs.cancel_all();
+        assert_eq!(later.poll(), Some(Err(DialogCancelled)));
+    }
+
+    #[test]
+    fn test_key_of_the_opening_frame_is_ignored() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut dialogs = Dialogs::new();
+
+        // The Enter which e.g. submitted a form queues the dialog and shows it in the same frame
+        ctx.io_mut().add_key_event(Key::Enter, true);
+        let ui = ctx.frame();
+        let mut confirm = dialogs.confirm("Submit?");
+        dialogs.build(ui);
+        ctx.render();
+        ctx.io_mut().add_key_event(Key::Enter, false);
+        let ui = ctx.frame();
+        dialogs.build(ui);
+        ctx.render();
+        assert_eq!(confirm.poll(), None);
+        assert!(confirm.is_pending());
+    }
+
+    #[test]
+    fn test_dropped_handle_closes_popup() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut dialogs = Dialogs::new();
+        let confirm = dialogs.confirm("Close?");
+        let mut frame = |dialogs: &mut Dialogs| {
+            let ui = ctx.frame();
+            dialogs.build(ui);
+            let open = ui.open_popup_count();
+            ctx.render();
+            open
+        };
+        assert_eq!(frame(&mut dialogs), 1);
+        drop(confirm);
+        assert_eq!(frame(&mut dialogs), 0);
+        assert!(dialogs.is_empty());
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt;
+
+use crate::fonts::atlas::{FontAtlas, FontId};
+use crate::style::Style;
+use crate::Context;
+
+type FontBuilder = Box<dyn FnMut(&mut FontAtlas, f32) -> Vec<FontId>>;
+
+/// State of the managed DPI scaling mode of a [`Context`].
+pub(crate) struct DpiScaling {
+    /// The style as the user authored it, at a scale of `1.0`.
+    base_style: Style,
+    /// `Io::font_global_scale` at the time scaling was enabled.
+    base_font_global_scale: f32,
+    font_builder: Option<FontBuilder>,
+    fonts: Vec<FontId>,
+    /// The scale we want to be at.
+    scale: f32,
+    /// The scale the style was last derived for, or `None` if it needs deriving.
+    style_scale: Option<f32>,
+    /// The scale the fonts were last built for, or `None` if they need rebuilding.
+    font_scale: Option<f32>,
+    /// Whether the base style was possibly edited since the style was last derived.
+    style_dirty: bool,
+    /// The last `dpi_scale` we saw on the viewport of the focused window.
+    #[cfg(feature = "docking")]
+    viewport_scale: Option<f32>,
+    /// Whether the scale follows the viewport, rather than the one given to `set_dpi_scale`.
+    #[cfg(feature = "docking")]
+    follow_viewport: bool,
+}
+
+impl fmt::Debug for DpiScaling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DpiScaling")
+            .field("base_style", &self.base_style)
+            .field("base_font_global_scale", &self.base_font_global_scale)
+            .field("has_font_builder", &self.font_builder.is_some())
+            .field("fonts", &self.fonts)
+            .field("scale", &self.scale)
+            .field("style_scale", &self.style_scale)
+            .field("font_scale", &self.font_scale)
+            .field("style_dirty", &self.style_dirty)
+            .finish()
+    }
+}
+
+/// # DPI scaling
+///
+/// Dear ImGui has a single style and a single font atlas, so scaling for high DPI monitors is
+/// normally done once with [`Style::scale_all_sizes`], which is destructive: scaling again when
+/// the window moves to a monitor with a different DPI compounds the error.
+///
+/// In the managed scaling mode, the context keeps an unscaled *base* style and derives the active
+/// style from it whenever the DPI scale changes. Fonts can either be stretched with
+/// [`Io::font_global_scale`](crate::Io::font_global_scale), which is cheap but blurry, or rebuilt
+/// at the right pixel size by a user-provided font builder.
+///
+/// The DPI scale is set with [`Context::set_dpi_scale`], typically from the platform backend's
+/// scale factor. With the `docking` feature, the scale instead follows the `Viewport::dpi_scale`
+/// of the viewport the focused window is on, until [`Context::set_dpi_scale`] is called.
+///
+/// While managed DPI scaling is enabled, the active style is overwritten whenever it is rescaled,
+/// so edit the style with [`Context::base_style_mut`] instead of [`Context::style_mut`].
+impl Context {
+    /// Enables managed DPI scaling, using the current style as the unscaled base style.
+    ///
+    /// Fonts are scaled with [`Io::font_global_scale`](crate::Io::font_global_scale). Use
+    /// [`Context::enable_dpi_scaling_with_fonts`] to rebuild the fonts at the scaled size instead.
+    pub fn enable_dpi_scaling(&mut self) {
+        self.enable_dpi_scaling_internal(None);
+    }
+
+    /// Enables managed DPI scaling, using the current style as the unscaled base style, and
+    /// rebuilding the font atlas with `build_fonts` whenever the scale changes.
+    ///
+    /// `build_fonts` is given a cleared font atlas and the DPI scale, and should add its fonts
+    /// with their pixel sizes multiplied by the scale. The ids it returns are available with
+    /// [`Context::dpi_fonts`] in the same order, since previously returned ids are invalidated by
+    /// every rebuild.
+    ///
+    /// The atlas is only rebuilt by [`Context::update_dpi_scaling`], never implicitly by
+    /// [`Context::new_frame`], as the renderer must upload the new font texture.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// ctx.enable_dpi_scaling_with_fonts(|atlas, scale| {
+    ///     vec![atlas.add_font(&[FontSource::DefaultFontData {
+    ///         config: Some(FontConfig {
+    ///             size_pixels: 13.0 * scale,
+    ///             ..FontConfig::default()
+    ///         }),
+    ///     }])]
+    /// });
+    /// ```
+    pub fn enable_dpi_scaling_with_fonts<F>(&mut self, build_fonts: F)
+    where
+        F: FnMut(&mut FontAtlas, f32) -> Vec<FontId> + 'static,
+    {
+        self.enable_dpi_scaling_internal(Some(Box::new(build_fonts)));
+    }
+
+    fn enable_dpi_scaling_internal(&mut self, font_builder: Option<FontBuilder>) {
+        let scale = self.dpi_scaling.as_ref().map_or(1.0, |s| s.scale);
+        #[cfg(feature = "docking")]
+        let follow_viewport = self.dpi_scaling.as_ref().is_none_or(|s| s.follow_viewport);
+        if let Some(dpi_scaling) = self.dpi_scaling.take() {
+            self.restore_unscaled(&dpi_scaling);
+        }
+        self.dpi_scaling = Some(Box::new(DpiScaling {
+            base_style: *self.style(),
+            base_font_global_scale: self.io().font_global_scale,
+            font_builder,
+            fonts: Vec::new(),
+            scale,
+            style_scale: None,
+            font_scale: None,
+            style_dirty: false,
+            #[cfg(feature = "docking")]
+            viewport_scale: None,
+            #[cfg(feature = "docking")]
+            follow_viewport,
+        }));
+    }
+
+    /// Disables managed DPI scaling, restoring the unscaled base style.
+    ///
+    /// Fonts rebuilt by a font builder are left as they are.
+    pub fn disable_dpi_scaling(&mut self) {
+        if let Some(dpi_scaling) = self.dpi_scaling.take() {
+            self.restore_unscaled(&dpi_scaling);
+        }
+    }
+
+    fn restore_unscaled(&mut self, dpi_scaling: &DpiScaling) {
+        *self.style_mut() = dpi_scaling.base_style;
+        self.io_mut().font_global_scale = dpi_scaling.base_font_global_scale;
+    }
+
+    /// Returns `true` if managed DPI scaling is enabled
+    pub fn is_dpi_scaling_enabled(&self) -> bool {
+        self.dpi_scaling.is_some()
+    }
+
+    /// Returns the DPI scale used by managed DPI scaling, or `1.0` if it is disabled.
+    pub fn dpi_scale(&self) -> f32 {
+        self.dpi_scaling.as_ref().map_or(1.0, |s| s.scale)
+    }
+
+    /// Sets the DPI scale used by managed DPI scaling.
+    ///
+    /// The style is rescaled on the next call to [`Context::update_dpi_scaling`] or
+    /// [`Context::new_frame`], and fonts on the next call to [`Context::update_dpi_scaling`].
+    /// Does nothing if managed DPI scaling is disabled.
+    ///
+    /// With the `docking` feature, this overrides the DPI scale of the focused window's viewport
+    /// until [`Context::follow_viewport_dpi_scale`] is called.
+    pub fn set_dpi_scale(&mut self, scale: f32) {
+        if let Some(dpi_scaling) = &mut self.dpi_scaling {
+            dpi_scaling.scale = scale;
+            #[cfg(feature = "docking")]
+            {
+                dpi_scaling.follow_viewport = false;
+            }
+        }
+    }
+
+    /// Makes managed DPI scaling follow the DPI scale of the viewport the focused window is on
+    /// again, after it was overridden with [`Context::set_dpi_scale`].
+    ///
+    /// This is the default. Does nothing if managed DPI scaling is disabled.
+    #[cfg(feature = "docking")]
+    pub fn follow_viewport_dpi_scale(&mut self) {
+        if let Some(dpi_scaling) = &mut self.dpi_scaling {
+            dpi_scaling.follow_viewport = true;
+            dpi_scaling.viewport_scale = None;
+        }
+    }
+
+    /// Returns the unscaled base style, or `None` if managed DPI scaling is disabled.
+    pub fn base_style(&self) -> Option<&Style> {
+        self.dpi_scaling.as_ref().map(|s| &s.base_style)
+    }
+
+    /// Returns a mutable reference to the unscaled base style, or `None` if managed DPI scaling
+    /// is disabled.
+    ///
+    /// While managed DPI scaling is enabled, edit this instead of [`Context::style_mut`], as the
+    /// active style is overwritten whenever it is rescaled. Edits are applied on the next call to
+    /// [`Context::update_dpi_scaling`] or [`Context::new_frame`].
+    pub fn base_style_mut(&mut self) -> Option<&mut Style> {
+        self.dpi_scaling.as_mut().map(|s| {
+            // we can't know what was changed, so derive the style again
+            s.style_dirty = true;
+            &mut s.base_style
+        })
+    }
+
+    /// Returns the fonts built by the font builder given to
+    /// [`Context::enable_dpi_scaling_with_fonts`] for the current scale.
+    pub fn dpi_fonts(&self) -> &[FontId] {
+        self.dpi_scaling.as_ref().map_or(&[], |s| &s.fonts)
+    }
+
+    /// Rescales the style and fonts if the DPI scale changed since they were last built.
+    ///
+    /// [`Context::new_frame`] only rescales the style and
+    /// [`Io::font_global_scale`](crate::Io::font_global_scale). When using
+    /// [`Context::enable_dpi_scaling_with_fonts`], the application must call this before
+    /// starting a frame, and upload the font texture again whenever this returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # fn reload_font_texture(_: &mut FontAtlas) {}
+    /// if ctx.update_dpi_scaling() {
+    ///     reload_font_texture(ctx.fonts());
+    /// }
+    /// let ui = ctx.new_frame();
+    /// ```
+    pub fn update_dpi_scaling(&mut self) -> bool {
+        self.update_dpi_scaling_internal(true)
+    }
+
+    /// Rescales the style, and the fonts if they are scaled with `Io::font_global_scale`.
+    pub(crate) fn update_dpi_style(&mut self) {
+        self.update_dpi_scaling_internal(false);
+    }
+
+    fn update_dpi_scaling_internal(&mut self, rebuild_fonts: bool) -> bool {
+        let Some(mut dpi_scaling) = self.dpi_scaling.take() else {
+            return false;
+        };
+
+        #[cfg(feature = "docking")]
+        if dpi_scaling.follow_viewport {
+            let viewport_scale = unsafe { crate::sys::imgui_rs_GetFocusedViewportDpiScale() };
+            if viewport_scale > 0.0 && dpi_scaling.viewport_scale != Some(viewport_scale) {
+                dpi_scaling.viewport_scale = Some(viewport_scale);
+                dpi_scaling.scale = viewport_scale;
+            }
+        }
+
+        let scale = dpi_scaling.scale;
+        if dpi_scaling.style_scale != Some(scale) || dpi_scaling.style_dirty {
+            let mut style = dpi_scaling.base_style;
+            style.scale_all_sizes(scale);
+            *self.style_mut() = style;
+            dpi_scaling.style_scale = Some(scale);
+            dpi_scaling.style_dirty = false;
+        }
+
+        let mut fonts_rebuilt = false;
+        if dpi_scaling.font_scale != Some(scale) {
+            match &mut dpi_scaling.font_builder {
+                Some(build_fonts) if rebuild_fonts => {
+                    let atlas = self.fonts();
+                    atlas.clear();
+                    dpi_scaling.fonts = build_fonts(atlas, scale);
+                    atlas.build_rgba32_texture();
+                    fonts_rebuilt = true;
+                    self.io_mut().font_global_scale = dpi_scaling.base_font_global_scale;
+                    dpi_scaling.font_scale = Some(scale);
+                }
+                // the renderer doesn't know about new fonts yet, keep the old ones
+                Some(_) => {}
+                None => {
+                    self.io_mut().font_global_scale = dpi_scaling.base_font_global_scale * scale;
+                    dpi_scaling.font_scale = Some(scale);
+                }
+            }
+        }
+
+        self.dpi_scaling = Some(dpi_scaling);
+        fonts_rebuilt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FontConfig, FontSource};
+
+    #[test]
+    fn test_dpi_scaling_keeps_base_style() {
+        let (_guard, mut ctx) = crate::test::test_ctx();
+        ctx.style_mut().window_padding = [4.0, 6.0];
+        ctx.enable_dpi_scaling();
+
+        ctx.set_dpi_scale(2.0);
+        assert!(!ctx.update_dpi_scaling());
+        assert_eq!(ctx.style().window_padding, [8.0, 12.0]);
+        assert_eq!(ctx.io().font_global_scale, 2.0);
+
+        ctx.set_dpi_scale(1.5);
+        ctx.update_dpi_scaling();
+        assert_eq!(ctx.style().window_padding, [6.0, 9.0]);
+
+        ctx.base_style_mut().unwrap().window_padding = [2.0, 2.0];
+        ctx.update_dpi_scaling();
+        assert_eq!(ctx.style().window_padding, [3.0, 3.0]);
+
+        ctx.disable_dpi_scaling();
+        assert_eq!(ctx.style().window_padding, [2.0, 2.0]);
+        assert_eq!(ctx.io().font_global_scale, 1.0);
+    }
+
+    #[test]
+    fn test_dpi_scaling_rebuilds_fonts() {
+        let (_guard, mut ctx) = crate::test::test_ctx();
+        ctx.enable_dpi_scaling_with_fonts(|atlas, scale| {
+            vec![atlas.add_font(&[FontSource::DefaultFontData {
+                config: Some(FontConfig {
+                    size_pixels: 13.0 * scale,
+                    ..FontConfig::default()
+                }),
+            }])]
+        });
+
+        assert!(ctx.update_dpi_scaling());
+        assert!(!ctx.update_dpi_scaling());
+        let font = ctx.dpi_fonts()[0];
+        assert_eq!(ctx.fonts().get_font(font).unwrap().font_size, 13.0);
+
+        ctx.set_dpi_scale(2.0);
+        assert!(ctx.update_dpi_scaling());
+        assert!(ctx.fonts().is_built());
+        let font = ctx.dpi_fonts()[0];
+        assert_eq!(ctx.fonts().get_font(font).unwrap().font_size, 26.0);
+        assert_eq!(ctx.io().font_global_scale, 1.0);
+
+        // editing the base style doesn't need new fonts
+        ctx.base_style_mut().unwrap().alpha = 0.5;
+        assert!(!ctx.update_dpi_scaling());
+        assert_eq!(ctx.style().alpha, 0.5);
+    }
+
+    #[test]
+    fn test_dpi_scaling_new_frame_keeps_fonts() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        ctx.style_mut().window_padding = [4.0, 6.0];
+        ctx.enable_dpi_scaling_with_fonts(|atlas, scale| {
+            vec![atlas.add_font(&[FontSource::DefaultFontData {
+                config: Some(FontConfig {
+                    size_pixels: 13.0 * scale,
+                    ..FontConfig::default()
+                }),
+            }])]
+        });
+        assert!(ctx.update_dpi_scaling());
+
+        ctx.set_dpi_scale(2.0);
+        ctx.new_frame();
+        ctx.render();
+        assert_eq!(ctx.style().window_padding, [8.0, 12.0]);
+        let font = ctx.dpi_fonts()[0];
+        assert_eq!(ctx.fonts().get_font(font).unwrap().font_size, 13.0);
+
+        assert!(ctx.update_dpi_scaling());
+        let font = ctx.dpi_fonts()[0];
+        assert_eq!(ctx.fonts().get_font(font).unwrap().font_size, 26.0);
+    }
+
+    #[cfg(feature = "docking")]
+    #[test]
+    fn test_dpi_scaling_set_scale_overrides_viewport() {
+        let (_guard, mut ctx) = crate::test::test_ctx();
+        ctx.style_mut().window_padding = [4.0, 6.0];
+        ctx.enable_dpi_scaling();
+
+        ctx.main_viewport_mut().dpi_scale = 1.5;
+        ctx.update_dpi_scaling();
+        assert_eq!(ctx.dpi_scale(), 1.5);
+        assert_eq!(ctx.style().window_padding, [6.0, 9.0]);
+
+        ctx.set_dpi_scale(2.0);
+        ctx.main_viewport_mut().dpi_scale = 3.0;
+        ctx.update_dpi_scaling();
+        assert_eq!(ctx.dpi_scale(), 2.0);
+        assert_eq!(ctx.style().window_padding, [8.0, 12.0]);
+
+        ctx.follow_viewport_dpi_scale();
+        ctx.update_dpi_scaling();
+        assert_eq!(ctx.dpi_scale(), 3.0);
+        assert_eq!(ctx.style().window_padding, [12.0, 18.0]);
+    }
+}
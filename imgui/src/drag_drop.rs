@@ -10,25 +10,30 @@
 //! in the target).
 //!
 //! The complexity of this implementation is primarily in managing this payload. Users
-//! can provide three different kinds of payloads:
+//! can provide four different kinds of payloads:
 //!
 //! 1.  Users can give an [empty payload](DragDropPayloadEmpty) with [begin](DragDropSource::begin).
 //!     This payload type is essentially just a notification system, but using some shared state,
-//!     this can be reasonably powerful, and is the safest way to transfer non-Copy data offered
-//!     right now.
+//!     this can be reasonably powerful.
 //! 2.  Users can give a [simple Copy payload](DragDropPayloadPod) with [begin](DragDropSource::begin_payload).
 //!     This allows users to copy data to Dear ImGui, which will take ownership over it, and then be given
 //!     it back to the Target. Please note: users are of course free to not drop any drag (cancel a drag),
 //!     so this data could easily be lost forever. Our `'static + Copy` bound is intended to keep users
 //!     to simplistic types.
-//! 3.  An unsafe implementation is provided which allows for any data to be unsafely copied. Note that once
-//!     you use this method, the safe implementations in #1 and #2 can create memory unsafety problems; notably,
-//!     they both assume that a payload has certain header information within it.
+//! 3.  Users can give an [owned payload](DragDropPayloadOwned) of any `'static` type with
+//!     [begin_owned_payload](DragDropSource::begin_owned_payload), e.g. a `Vec` of selected items.
+//!     The data stays on the Rust side and Dear ImGui only copies a handle to it. The data is moved
+//!     to the Target when it's delivered, or dropped when the drag is cancelled. This is the safest
+//!     way to transfer non-Copy data.
+//! 4.  An unsafe implementation is provided which allows for any data to be unsafely copied. Note that once
+//!     you use this method, the safe implementations in #1, #2 and #3 can create memory unsafety problems;
+//!     notably, they all assume that a payload has certain header information within it.
 //!
 //! For examples of each payload type, see [DragDropSource].
-use std::{any, ffi, marker::PhantomData};
+use std::{any, any::Any, ffi, marker::PhantomData, mem, ptr, rc::Rc};
 
-use crate::{sys, Condition, Ui};
+use crate::math::MintVec2;
+use crate::{sys, Condition, Id, Ui};
 use bitflags::bitflags;
 
 bitflags!(
@@ -248,6 +253,80 @@ impl<'ui, T: AsRef<str>> DragDropSource<'ui, T> {
         }
     }
 
+    /// Creates the source of a drag and returns a handle on the tooltip.
+    /// This handle can be immediately dropped without binding it, in which case a default empty
+    /// circle will be used for the "blank" tooltip as this item is being dragged around.
+    ///
+    /// Otherwise, use this tooltip to add data which will display as this item is dragged.
+    /// If `SOURCE_NO_PREVIEW_TOOLTIP` is enabled, however, no preview will be displayed
+    /// and this returned token does nothing. Additionally, a given target may use the flag
+    /// `ACCEPT_NO_PREVIEW_TOOLTIP`, which will also prevent this tooltip from being shown.
+    ///
+    /// This function also takes a function creating a payload of any `T: 'static`, which is
+    /// called once when the drag starts rather than every frame. The payload is kept by imgui-rs
+    /// while Dear ImGui only holds a handle to it, and is moved to the [DragDropTarget] which
+    /// accepts it with [accept_owned_payload](DragDropTarget::accept_owned_payload), or dropped
+    /// if the drag is cancelled.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// fn show_ui(ui: &Ui, selected: &[u32], dropped: &mut Vec<u32>) {
+    ///     ui.button("Drag the selection!");
+    ///
+    ///     // drag drop SOURCE
+    ///     if let Some(tooltip) = ui
+    ///         .drag_drop_source_config("ITEMS")
+    ///         .begin_owned_payload(|| selected.to_vec())
+    ///     {
+    ///         ui.text(format!("{} items", selected.len()));
+    ///         tooltip.end();
+    ///     }
+    ///
+    ///     ui.button("Target me!");
+    ///
+    ///     // drag drop TARGET
+    ///     if let Some(target) = ui.drag_drop_target() {
+    ///         if let Some(Ok(payload)) =
+    ///             target.accept_owned_payload::<Vec<u32>, _>("ITEMS", DragDropFlags::empty())
+    ///         {
+    ///             dropped.extend(payload.data.unwrap_or_default());
+    ///         }
+    ///
+    ///         target.pop();
+    ///     }
+    /// }
+    /// ```
+    pub fn begin_owned_payload<P: 'static>(
+        self,
+        payload: impl FnOnce() -> P,
+    ) -> Option<DragDropSourceToolTip<'ui>> {
+        let should_begin = unsafe { sys::igBeginDragDropSource(self.flags.bits() as i32) };
+        if !should_begin {
+            return None;
+        }
+
+        let payloads = &self.ui.owned_payloads;
+        let session = match current_owned_handle() {
+            Some(handle) if payloads.borrow().contains(handle.session) => handle.session,
+            _ => {
+                let data = Rc::new(payload());
+                payloads
+                    .borrow_mut()
+                    .insert(TypedPayloadHeader::new::<P>(), data)
+            }
+        };
+        let handle = TypedPayload::new(OwnedPayloadHandle { session });
+        unsafe {
+            sys::igSetDragDropPayload(
+                self.ui.scratch_txt(&self.name),
+                &handle as *const _ as *const ffi::c_void,
+                mem::size_of::<TypedPayload<OwnedPayloadHandle>>(),
+                self.cond as i32,
+            );
+        }
+        Some(DragDropSourceToolTip::push())
+    }
+
     /// Creates the source of a drag and returns a handle on the tooltip.
     /// This handle can be immediately dropped without binding it, in which case a default empty
     /// circle will be used for the "blank" tooltip as this item is being dragged around.
@@ -273,7 +352,8 @@ impl<'ui, T: AsRef<str>> DragDropSource<'ui, T> {
     /// handing the allocation to ImGui, would result in a significant amount of data created.
     ///
     /// Overall, users should be very sure that this function is needed before they reach for it, and instead
-    /// should consider either [begin](Self::begin) or [begin_payload](Self::begin_payload).
+    /// should consider [begin](Self::begin), [begin_payload](Self::begin_payload) or
+    /// [begin_owned_payload](Self::begin_owned_payload).
     #[inline]
     pub unsafe fn begin_payload_unchecked(
         &self,
@@ -328,6 +408,122 @@ impl Ui {
             None
         }
     }
+
+    /// Creates a new DragDropTarget over a rectangle of the current window (in screen
+    /// coordinates) rather than the last item, e.g. the gap between two rows to insert between
+    /// them, or a region of a canvas. `id` identifies the target.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is zero, which Dear ImGui doesn't accept as a target.
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// fn show_ui(ui: &Ui, rows: &mut Vec<u32>) {
+    ///     for (index, row) in rows.clone().iter().enumerate() {
+    ///         ui.text(row.to_string());
+    ///         // The gap below each row is a target inserting after it
+    ///         let min = [ui.item_rect_min()[0], ui.item_rect_max()[1]];
+    ///         let max = [ui.item_rect_max()[0], min[1] + ui.clone_style().item_spacing[1]];
+    ///         let id = ui.new_id(index);
+    ///         if let Some(target) = ui.drag_drop_target_custom(min, max, id) {
+    ///             if let Some(Ok(payload)) =
+    ///                 target.accept_payload::<u32, _>("ROW", DragDropFlags::empty())
+    ///             {
+    ///                 rows.insert(index + 1, payload.data);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[doc(alias = "BeginDragDropTargetCustom")]
+    pub fn drag_drop_target_custom(
+        &self,
+        min: impl Into<MintVec2>,
+        max: impl Into<MintVec2>,
+        id: Id,
+    ) -> Option<DragDropTarget<'_>> {
+        assert!(id != Id(0), "drag and drop targets need a non-zero id");
+        let should_begin = unsafe {
+            sys::imgui_rs_BeginDragDropTargetCustom(min.into().into(), max.into().into(), id.0)
+        };
+        if should_begin {
+            Some(DragDropTarget(self))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the payload currently being dragged, e.g. to highlight the targets which would
+    /// accept it, or `None` if nothing is being dragged.
+    #[doc(alias = "GetDragDropPayload")]
+    pub fn drag_drop_payload(&self) -> Option<DragDropPayloadRef<'_>> {
+        let raw = unsafe { sys::igGetDragDropPayload() };
+        if raw.is_null() {
+            None
+        } else {
+            Some(DragDropPayloadRef { raw, ui: self })
+        }
+    }
+}
+
+/// The payload currently being dragged, see [Ui::drag_drop_payload]. It can be inspected
+/// before any target accepts it.
+#[derive(Debug)]
+pub struct DragDropPayloadRef<'ui> {
+    raw: *const sys::ImGuiPayload,
+    ui: &'ui Ui,
+}
+
+impl DragDropPayloadRef<'_> {
+    fn raw(&self) -> &sys::ImGuiPayload {
+        unsafe { &*self.raw }
+    }
+
+    /// Returns the name the [DragDropSource] was created with.
+    pub fn data_type(&self) -> &str {
+        let data_type = unsafe { ffi::CStr::from_ptr(self.raw().DataType.as_ptr()) };
+        data_type.to_str().unwrap_or_default()
+    }
+
+    /// Returns `true` if the [DragDropSource] was created with the given name.
+    #[doc(alias = "IsDataType")]
+    pub fn is_data_type(&self, name: impl AsRef<str>) -> bool {
+        self.data_type() == name.as_ref()
+    }
+
+    /// Returns `true` if a target accepting the payload is hovered.
+    #[doc(alias = "IsPreview")]
+    pub fn is_preview(&self) -> bool {
+        self.raw().Preview
+    }
+
+    /// Returns `true` if the payload is being dropped on a target this frame.
+    #[doc(alias = "IsDelivery")]
+    pub fn is_delivery(&self) -> bool {
+        self.raw().Delivery
+    }
+
+    /// Returns the id of the item the drag started from.
+    pub fn source_id(&self) -> Id {
+        Id(self.raw().SourceId)
+    }
+
+    /// Returns a copy of the data of a payload started with
+    /// [begin_payload](DragDropSource::begin_payload), or `None` if it isn't a `T`.
+    pub fn data<T: 'static + Copy>(&self) -> Option<T> {
+        read_typed(self.raw)
+    }
+
+    /// Calls `f` with the data of a payload started with
+    /// [begin_owned_payload](DragDropSource::begin_owned_payload), or returns `None` if it isn't
+    /// a `T` or was already delivered.
+    pub fn with_owned<T: 'static, R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let handle = read_typed::<OwnedPayloadHandle>(self.raw)?;
+        // Release the registry before calling `f`, which may start or accept drags itself
+        let data = Rc::clone(&self.ui.owned_payloads.borrow().get(handle.session)?.data);
+        Some(f(data.downcast_ref::<T>()?))
+    }
 }
 
 /// Creates a target for drag drop data out of the last ID created.
@@ -430,6 +626,64 @@ impl<'ui> DragDropTarget<'ui> {
         })
     }
 
+    /// Accepts a payload started with [begin_owned_payload](DragDropSource::begin_owned_payload).
+    /// This returns a Result, since you can specify any type. The sent type must match the return
+    /// type (via TypeId) to receive an `Ok`.
+    ///
+    /// The data is moved out of the drag when it's delivered. With
+    /// [ACCEPT_BEFORE_DELIVERY](DragDropFlags::ACCEPT_BEFORE_DELIVERY), this also returns the
+    /// payload while it's only hovering the target, without the data.
+    ///
+    /// The data can't be moved out while [with_owned](DragDropPayloadRef::with_owned) is reading
+    /// it, so a delivery from within its closure returns [OwnedPayloadError::InUse] and leaves
+    /// the data with the drag, to be accepted again after the closure returns.
+    ///
+    /// Note: If you began this operation with `begin_payload_unchecked` it always incorrect
+    /// to use this function. Use `accept_payload_unchecked` instead
+    pub fn accept_owned_payload<T: 'static, Name: AsRef<str>>(
+        &self,
+        name: Name,
+        flags: DragDropFlags,
+    ) -> Option<Result<DragDropPayloadOwned<T>, OwnedPayloadError>> {
+        let handle = match self.accept_payload::<OwnedPayloadHandle, _>(name, flags)? {
+            Ok(handle) => handle,
+            Err(error) => {
+                return Some(Err(OwnedPayloadError::WrongType(PayloadIsWrongType {
+                    expected: TypedPayloadHeader::new::<T>(),
+                    ..error
+                })))
+            }
+        };
+
+        let mut payloads = self.0.owned_payloads.borrow_mut();
+        // Gone if it was already delivered to another target
+        let owned = payloads.get(handle.data.session)?;
+        let expected = TypedPayloadHeader::new::<T>();
+        if owned.header.type_id != expected.type_id {
+            return Some(Err(OwnedPayloadError::WrongType(PayloadIsWrongType {
+                expected,
+                received: owned.header,
+            })));
+        }
+        let data = if handle.delivery {
+            // Still shared if it's delivered from within `with_owned`
+            if Rc::strong_count(&owned.data) > 1 {
+                return Some(Err(OwnedPayloadError::InUse));
+            }
+            payloads
+                .take(handle.data.session)
+                .and_then(|owned| owned.data.downcast::<T>().ok())
+                .and_then(|data| Rc::try_unwrap(data).ok())
+        } else {
+            None
+        };
+        Some(Ok(DragDropPayloadOwned {
+            data,
+            preview: handle.preview,
+            delivery: handle.delivery,
+        }))
+    }
+
     /// Accepts a drag and drop payload  which contains a raw pointer to [c_void](std::ffi::c_void)
     /// and a size in bytes. Users should generally avoid using this function
     /// if one of the safer variants is acceptable.
@@ -518,6 +772,24 @@ pub struct DragDropPayloadPod<T: 'static + Copy> {
     pub delivery: bool,
 }
 
+/// A DragDropPayload with status information and data of any `'static` type, see
+/// [DragDropSource::begin_owned_payload].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DragDropPayloadOwned<T: 'static> {
+    /// The data, which is only moved out of the drag on delivery, so this is `None` before
+    /// that when using `ACCEPT_BEFORE_DELIVERY`.
+    pub data: Option<T>,
+
+    /// Set when [`accept_owned_payload`](DragDropTarget::accept_owned_payload) was called
+    /// and mouse has been hovering the target item.
+    pub preview: bool,
+
+    /// Set when [`accept_owned_payload`](DragDropTarget::accept_owned_payload) was
+    /// called and mouse button is released over the target item.
+    pub delivery: bool,
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct DragDropPayload {
@@ -557,6 +829,87 @@ impl<T: Copy + 'static> TypedPayload<T> {
     }
 }
 
+/// What Dear ImGui holds of an owned payload, which is stored in [OwnedPayloads].
+#[derive(Debug, Clone, Copy)]
+struct OwnedPayloadHandle {
+    session: u64,
+}
+
+/// Returns the handle of the owned payload being dragged, if there is one.
+fn current_owned_handle() -> Option<OwnedPayloadHandle> {
+    read_typed(unsafe { sys::igGetDragDropPayload() })
+}
+
+/// Reads the data of a payload started with [DragDropSource::begin_payload], if it's a `T`.
+fn read_typed<T: 'static + Copy>(payload: *const sys::ImGuiPayload) -> Option<T> {
+    unsafe {
+        if payload.is_null()
+            || (*payload).Data.is_null()
+            || (*payload).DataSize as usize != mem::size_of::<TypedPayload<T>>()
+        {
+            return None;
+        }
+        // Only the type id is read, since any other payload could have the same size
+        let typed = (*payload).Data as *const TypedPayload<T>;
+        let type_id = ptr::addr_of!((*typed).header.type_id).read_unaligned();
+        if type_id != any::TypeId::of::<T>() {
+            return None;
+        }
+        Some(ptr::addr_of!((*typed).data).read_unaligned())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OwnedPayload {
+    header: TypedPayloadHeader,
+    data: Rc<dyn Any>,
+}
+
+/// The payloads of drags started with [DragDropSource::begin_owned_payload], keyed by drag
+/// session. There's only one drag at a time, so this holds at most one payload.
+#[derive(Debug, Default)]
+pub(crate) struct OwnedPayloads {
+    last_session: u64,
+    current: Option<(u64, OwnedPayload)>,
+}
+
+impl OwnedPayloads {
+    fn insert(&mut self, header: TypedPayloadHeader, data: Rc<dyn Any>) -> u64 {
+        self.last_session += 1;
+        self.current = Some((self.last_session, OwnedPayload { header, data }));
+        self.last_session
+    }
+
+    fn contains(&self, session: u64) -> bool {
+        self.get(session).is_some()
+    }
+
+    fn get(&self, session: u64) -> Option<&OwnedPayload> {
+        self.current
+            .as_ref()
+            .filter(|(current, _)| *current == session)
+            .map(|(_, owned)| owned)
+    }
+
+    fn take(&mut self, session: u64) -> Option<OwnedPayload> {
+        if self.contains(session) {
+            self.current.take().map(|(_, owned)| owned)
+        } else {
+            None
+        }
+    }
+
+    /// Drops the payload unless it's still being dragged. Called after `igNewFrame`, which
+    /// clears delivered and cancelled drags.
+    pub(crate) fn retain_current(&mut self) {
+        if let Some((session, _)) = &self.current {
+            if current_owned_handle().is_none_or(|handle| handle.session != *session) {
+                self.current = None;
+            }
+        }
+    }
+}
+
 /// A header for a typed payload.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
 #[repr(C)]
@@ -611,3 +964,214 @@ impl std::fmt::Display for PayloadIsWrongType {
 }
 
 impl std::error::Error for PayloadIsWrongType {}
+
+/// Why [accept_owned_payload](DragDropTarget::accept_owned_payload) couldn't return a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnedPayloadError {
+    /// The payload isn't of the requested type.
+    WrongType(PayloadIsWrongType),
+    /// The payload was delivered while [with_owned](DragDropPayloadRef::with_owned) was reading
+    /// it, so it couldn't be moved out of the drag yet.
+    InUse,
+}
+
+impl From<PayloadIsWrongType> for OwnedPayloadError {
+    fn from(error: PayloadIsWrongType) -> Self {
+        OwnedPayloadError::WrongType(error)
+    }
+}
+
+impl std::fmt::Display for OwnedPayloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OwnedPayloadError::WrongType(error) => error.fmt(f),
+            OwnedPayloadError::InUse => f.pad("Payload is in use by with_owned"),
+        }
+    }
+}
+
+impl std::error::Error for OwnedPayloadError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    /// The mouse positions and button states of a drag from `from` to `to`, without the release.
+    fn drag_steps(from: [f32; 2], to: [f32; 2]) -> Vec<([f32; 2], bool)> {
+        let mut steps = vec![(from, false), (from, true)];
+        for step in 1..=4 {
+            let t = step as f32 / 4.0;
+            let pos = [
+                from[0] + (to[0] - from[0]) * t,
+                from[1] + (to[1] - from[1]) * t,
+            ];
+            steps.push((pos, true));
+        }
+        steps
+    }
+
+    /// Drags from the first button to the second, or to empty space if `deliver` is `false`,
+    /// returning what the target received, and how often accepting it from within `with_owned`
+    /// failed because it was in use.
+    fn drag(payload: &Rc<String>, deliver: bool) -> (Vec<Option<Vec<Rc<String>>>>, usize) {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut received = Vec::new();
+        let mut in_use = 0;
+        // Returns the positions of the buttons
+        let mut frame = |ctx: &mut crate::Context, mouse: Option<([f32; 2], bool)>| {
+            if let Some((pos, down)) = mouse {
+                ctx.io_mut().add_mouse_pos_event(pos);
+                ctx.io_mut()
+                    .add_mouse_button_event(crate::MouseButton::Left, down);
+            }
+            let mut rects = [[0.0; 2]; 2];
+            let ui = ctx.frame();
+            ui.window("drag")
+                .position([0.0, 0.0], Condition::Always)
+                .size([200.0, 200.0], Condition::Always)
+                .build(|| {
+                    ui.button("source");
+                    rects[0] = ui.item_rect_min();
+                    if let Some(tooltip) = ui
+                        .drag_drop_source_config("ITEMS")
+                        .begin_owned_payload(|| vec![payload.clone(), payload.clone()])
+                    {
+                        tooltip.end();
+                    }
+                    ui.button("target");
+                    rects[1] = ui.item_rect_min();
+                    if let Some(target) = ui.drag_drop_target() {
+                        // The wrong type leaves the payload in place, and accepting doesn't
+                        // conflict with peeking at it, but can't move the payload out yet
+                        let peeked = ui.drag_drop_payload().and_then(|peek| {
+                            peek.with_owned(|items: &Vec<Rc<String>>| {
+                                assert!(matches!(
+                                    target.accept_owned_payload::<u32, _>(
+                                        "ITEMS",
+                                        DragDropFlags::empty()
+                                    ),
+                                    None | Some(Err(OwnedPayloadError::WrongType(_)))
+                                ));
+                                match target.accept_owned_payload::<Vec<Rc<String>>, _>(
+                                    "ITEMS",
+                                    DragDropFlags::empty(),
+                                ) {
+                                    None => {}
+                                    Some(Err(OwnedPayloadError::InUse)) => in_use += 1,
+                                    Some(other) => panic!("accepted while in use: {:?}", other),
+                                }
+                                items.len()
+                            })
+                        });
+                        assert_eq!(peeked, Some(2));
+                        if let Some(payload) = target.accept_owned_payload::<Vec<Rc<String>>, _>(
+                            "ITEMS",
+                            DragDropFlags::empty(),
+                        ) {
+                            received.push(payload.unwrap().data);
+                        }
+                    }
+                });
+            ctx.render();
+            rects
+        };
+
+        let rects = frame(&mut ctx, None);
+        let [source, target] = rects.map(|[x, y]| [x + 4.0, y + 4.0]);
+        let end = if deliver { target } else { [150.0, 150.0] };
+        for step in drag_steps(source, end) {
+            frame(&mut ctx, Some(step));
+        }
+        // Only one handle should be around while dragging
+        assert_eq!(Rc::strong_count(payload), 3);
+        frame(&mut ctx, Some((end, false)));
+        for _ in 0..3 {
+            frame(&mut ctx, None);
+        }
+        (received, in_use)
+    }
+
+    #[test]
+    fn test_owned_payload_is_delivered() {
+        let payload = Rc::new(String::from("asset"));
+        let (received, in_use) = drag(&payload, true);
+        assert_eq!(in_use, 1);
+        assert_eq!(received.len(), 1);
+        let items = received[0].as_ref().unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(Rc::ptr_eq(&items[0], &payload));
+        drop(received);
+        assert_eq!(Rc::strong_count(&payload), 1);
+    }
+
+    #[test]
+    fn test_owned_payload_is_dropped_on_cancel() {
+        let payload = Rc::new(String::from("asset"));
+        let (received, in_use) = drag(&payload, false);
+        assert_eq!(in_use, 0);
+        assert!(received.is_empty());
+        assert_eq!(Rc::strong_count(&payload), 1);
+    }
+
+    #[test]
+    fn test_custom_target_and_peek() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut peeked = Vec::new();
+        let mut received = Vec::new();
+        let gap = ([8.0, 60.0], [150.0, 90.0]);
+        let mut frame = |ctx: &mut crate::Context, mouse: Option<([f32; 2], bool)>| {
+            if let Some((pos, down)) = mouse {
+                ctx.io_mut().add_mouse_pos_event(pos);
+                ctx.io_mut()
+                    .add_mouse_button_event(crate::MouseButton::Left, down);
+            }
+            let mut source = [0.0; 2];
+            let ui = ctx.frame();
+            ui.window("drag")
+                .position([0.0, 0.0], Condition::Always)
+                .size([200.0, 200.0], Condition::Always)
+                .build(|| {
+                    peeked.push(ui.drag_drop_payload().map(|payload| {
+                        assert!(payload.is_data_type("ROW"));
+                        assert_eq!(payload.data::<i32>(), None);
+                        assert_eq!(payload.with_owned(|_: &u32| ()), None);
+                        (payload.data::<u32>(), payload.is_preview())
+                    }));
+                    ui.button("source");
+                    source = ui.item_rect_min();
+                    if let Some(tooltip) = ui.drag_drop_source_config("ROW").begin_payload(7u32) {
+                        tooltip.end();
+                    }
+                    let id = ui.new_id_str("gap");
+                    if let Some(target) = ui.drag_drop_target_custom(gap.0, gap.1, id) {
+                        if let Some(Ok(payload)) =
+                            target.accept_payload::<u32, _>("ROW", DragDropFlags::empty())
+                        {
+                            received.push(payload.data);
+                        }
+                    }
+                });
+            ctx.render();
+            source
+        };
+
+        let source = frame(&mut ctx, None).map(|v| v + 4.0);
+        for step in drag_steps(source, [100.0, 75.0]) {
+            frame(&mut ctx, Some(step));
+        }
+        frame(&mut ctx, None);
+        frame(&mut ctx, Some(([100.0, 75.0], false)));
+        for _ in 0..2 {
+            frame(&mut ctx, None);
+        }
+
+        assert_eq!(received, [7]);
+        let dragged: Vec<_> = peeked.iter().flatten().collect();
+        assert!(dragged.iter().all(|(data, _)| *data == Some(7)));
+        // Previewing once the target accepted it
+        assert_eq!(dragged.first().map(|(_, preview)| *preview), Some(false));
+        assert_eq!(dragged.last().map(|(_, preview)| *preview), Some(true));
+        assert!(peeked.first().unwrap().is_none() && peeked.last().unwrap().is_none());
+    }
+}
@@ -356,6 +356,36 @@ impl<'ui> DrawListMut<'ui> {
         }
     }
 
+    /// Draw a text with the current font at the given size, whose upper-left corner is at point
+    /// `pos`. This is useful to scale text along with zoomable content.
+    #[doc(alias = "AddText")]
+    pub fn add_text_with_font_size(
+        &self,
+        pos: impl Into<MintVec2>,
+        col: impl Into<ImColor32>,
+        font_size: f32,
+        text: impl AsRef<str>,
+    ) {
+        use std::os::raw::c_char;
+
+        let text = text.as_ref();
+        unsafe {
+            let start = text.as_ptr() as *const c_char;
+            let end = (start as usize + text.len()) as *const c_char;
+            sys::ImDrawList_AddText_FontPtr(
+                self.draw_list,
+                sys::igGetFont(),
+                font_size,
+                pos.into().into(),
+                col.into().into(),
+                start,
+                end,
+                0.0,
+                std::ptr::null(),
+            )
+        }
+    }
+
     /// Returns a Bezier curve stretching from `pos0` to `pos1`, whose
     /// curvature is defined by `cp0` and `cp1`.
     #[doc(alias = "AddBezier", alias = "AddBezierCubic")]
@@ -0,0 +1,900 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::math::MintVec4;
+use crate::value_format::utc_date_time;
+use crate::{
+    sys, Condition, Id, ListClipper, MouseButton, SelectableFlags, StyleColor, TableColumnFlags,
+    TableColumnSetup, TableFlags, TableSortDirection, Ui,
+};
+
+/// What a [FileBrowser] picks.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum FileBrowserMode {
+    /// One or more existing files, see [FileBrowser::multiple].
+    #[default]
+    Open,
+    /// A file name to save to, which may not exist yet.
+    Save,
+    /// An existing directory.
+    SelectDirectory,
+}
+
+/// A named set of file extensions a [FileBrowser] can show, e.g. `Images (*.png, *.jpg)`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileFilter {
+    pub name: String,
+    /// Extensions without the leading dot, matched case-insensitively. Empty matches all files.
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Creates a new filter.
+    pub fn new<S: Into<String>>(
+        name: impl Into<String>,
+        extensions: impl IntoIterator<Item = S>,
+    ) -> Self {
+        FileFilter {
+            name: name.into(),
+            extensions: extensions.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns `true` if the filter shows `path`.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.extensions.is_empty()
+            || path.extension().is_some_and(|extension| {
+                self.extensions
+                    .iter()
+                    .any(|e| extension.eq_ignore_ascii_case(e.as_str()))
+            })
+    }
+
+    fn label(&self) -> String {
+        if self.extensions.is_empty() {
+            return self.name.clone();
+        }
+        let patterns: Vec<String> = self.extensions.iter().map(|e| format!("*.{}", e)).collect();
+        format!("{} ({})", self.name, patterns.join(", "))
+    }
+}
+
+/// How a [FileBrowser] was closed, as returned by [FileBrowser::build].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FileBrowserResult {
+    /// The user confirmed these paths. There is exactly one, unless multiple files can be
+    /// opened.
+    Selected(Vec<PathBuf>),
+    /// The user closed the browser without picking anything.
+    Cancelled,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum SortColumn {
+    Name,
+    Size,
+    Modified,
+}
+
+/// A file and directory picker drawn with Dear ImGui, for applications where native dialogs
+/// aren't available, e.g. fullscreen or embedded ones.
+///
+/// The browser is a modal popup with back, forward and up buttons, breadcrumbs for the current
+/// directory, a sortable table of its entries, extension filters and a button to create a
+/// folder. It keeps its directory and history between uses, so it should be stored alongside
+/// the rest of the UI state. Requires the `tables-api` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut browser = FileBrowser::new("Open image")
+///     .filter(FileFilter::new("Images", ["png", "jpg"]))
+///     .filter(FileFilter::new("All files", Vec::<String>::new()));
+/// if ui.button("Open...") {
+///     browser.open();
+/// }
+/// // Every frame:
+/// if let Some(FileBrowserResult::Selected(paths)) = browser.build(ui) {
+///     println!("{}", paths[0].display());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FileBrowser {
+    title: String,
+    mode: FileBrowserMode,
+    multiple: bool,
+    show_hidden: bool,
+    filters: Vec<FileFilter>,
+    active_filter: usize,
+    error_color: [f32; 4],
+
+    directory: PathBuf,
+    back: Vec<PathBuf>,
+    forward: Vec<PathBuf>,
+    entries: Vec<Entry>,
+    error: Option<String>,
+    selected: BTreeSet<usize>,
+    last_clicked: Option<usize>,
+    sort: (SortColumn, TableSortDirection),
+    file_name: String,
+    new_folder_name: String,
+    /// Why the last attempt of the new folder popup failed.
+    new_folder_error: Option<String>,
+    open_requested: bool,
+    close_requested: bool,
+    is_open: bool,
+    needs_refresh: bool,
+}
+
+impl FileBrowser {
+    /// Creates a new browser with the given title, in the current working directory.
+    pub fn new(title: impl Into<String>) -> Self {
+        FileBrowser {
+            title: title.into(),
+            mode: FileBrowserMode::Open,
+            multiple: false,
+            show_hidden: false,
+            filters: Vec::new(),
+            active_filter: 0,
+            error_color: [1.0, 0.4, 0.4, 1.0],
+            directory: std::env::current_dir().unwrap_or_default(),
+            back: Vec::new(),
+            forward: Vec::new(),
+            entries: Vec::new(),
+            error: None,
+            selected: BTreeSet::new(),
+            last_clicked: None,
+            sort: (SortColumn::Name, TableSortDirection::Ascending),
+            file_name: String::new(),
+            new_folder_name: String::new(),
+            new_folder_error: None,
+            open_requested: false,
+            close_requested: false,
+            is_open: false,
+            needs_refresh: true,
+        }
+    }
+
+    /// Sets what the browser picks.
+    pub fn mode(mut self, mode: FileBrowserMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Allows opening several files, holding ctrl to toggle entries and shift to select ranges.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Shows entries whose names start with a dot.
+    pub fn show_hidden(mut self, show_hidden: bool) -> Self {
+        self.show_hidden = show_hidden;
+        self.needs_refresh = true;
+        self
+    }
+
+    /// Adds an extension filter. The first filter is active by default, and the user can pick
+    /// another one below the table.
+    pub fn filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self.needs_refresh = true;
+        self
+    }
+
+    /// Sets the color of errors, such as a directory that can't be read.
+    ///
+    /// Defaults to a light red.
+    pub fn error_color(mut self, color: impl Into<MintVec4>) -> Self {
+        self.error_color = color.into().into();
+        self
+    }
+
+    /// Sets the directory the browser starts in.
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self.needs_refresh = true;
+        self
+    }
+
+    /// Sets the file name initially proposed when saving.
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = file_name.into();
+        self
+    }
+
+    /// Opens the browser. It is shown from the next call to [build](Self::build).
+    pub fn open(&mut self) {
+        self.open_requested = true;
+        self.needs_refresh = true;
+    }
+
+    /// Closes the browser, which returns [FileBrowserResult::Cancelled] from the next call to
+    /// [build](Self::build).
+    pub fn close(&mut self) {
+        self.open_requested = false;
+        self.close_requested = true;
+    }
+
+    /// Returns `true` if the browser is open.
+    pub fn is_open(&self) -> bool {
+        self.is_open || self.open_requested
+    }
+
+    /// Returns the directory shown by the browser.
+    pub fn current_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Navigates to `directory`, which is remembered in the back history.
+    pub fn set_directory(&mut self, directory: impl Into<PathBuf>) {
+        let directory = directory.into();
+        if directory != self.directory {
+            let previous = std::mem::replace(&mut self.directory, directory);
+            self.back.push(previous);
+            self.forward.clear();
+        }
+        self.refresh();
+    }
+
+    /// Creates a directory named `name` in the current directory, and selects it.
+    pub fn create_folder(&mut self, name: &str) -> io::Result<PathBuf> {
+        if name.is_empty() || name.contains(['/', '\\']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid folder name",
+            ));
+        }
+        let path = self.directory.join(name);
+        fs::create_dir(&path)?;
+        self.refresh();
+        if let Some(index) = self.entries.iter().position(|entry| entry.path == path) {
+            self.selected = BTreeSet::from([index]);
+            self.last_clicked = Some(index);
+        }
+        Ok(path)
+    }
+
+    /// Returns the paths of the selected entries.
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        self.selected
+            .iter()
+            .map(|&index| self.entries[index].path.clone())
+            .collect()
+    }
+
+    /// Reads the current directory again.
+    pub fn refresh(&mut self) {
+        self.needs_refresh = false;
+        self.selected.clear();
+        self.last_clicked = None;
+        self.entries.clear();
+        self.error = None;
+        match fs::read_dir(&self.directory) {
+            Ok(read_dir) => {
+                for entry in read_dir.flatten() {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    if name.starts_with('.') && !self.show_hidden {
+                        continue;
+                    }
+                    // Follows symbolic links, so that links to directories can be entered
+                    let Ok(metadata) = fs::metadata(entry.path()) else {
+                        continue;
+                    };
+                    let is_dir = metadata.is_dir();
+                    let path = entry.path();
+                    let shown = if is_dir {
+                        true
+                    } else {
+                        self.mode != FileBrowserMode::SelectDirectory
+                            && self
+                                .filters
+                                .get(self.active_filter)
+                                .is_none_or(|filter| filter.matches(&path))
+                    };
+                    if shown {
+                        self.entries.push(Entry {
+                            name,
+                            path,
+                            is_dir,
+                            size: metadata.len(),
+                            modified: metadata.modified().ok(),
+                        });
+                    }
+                }
+                self.sort_entries();
+            }
+            Err(error) => self.error = Some(error.to_string()),
+        }
+    }
+
+    fn sort_entries(&mut self) {
+        let (column, direction) = self.sort;
+        self.entries.sort_by(|a, b| {
+            // Directories always come first
+            let ordering = match column {
+                SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SortColumn::Size => a.size.cmp(&b.size),
+                SortColumn::Modified => a.modified.cmp(&b.modified),
+            };
+            let ordering = match direction {
+                TableSortDirection::Ascending => ordering,
+                TableSortDirection::Descending => ordering.reverse(),
+            };
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then(ordering)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+
+    /// Creates the folder named in the new folder popup. On failure, the popup stays open and
+    /// shows the error, and the listing is kept.
+    fn submit_new_folder(&mut self) -> bool {
+        if self.new_folder_name.is_empty() {
+            return false;
+        }
+        match self.create_folder(&self.new_folder_name.clone()) {
+            Ok(_) => {
+                self.new_folder_name.clear();
+                self.new_folder_error = None;
+                true
+            }
+            Err(error) => {
+                self.new_folder_error = Some(error.to_string());
+                false
+            }
+        }
+    }
+
+    fn navigate_back(&mut self) {
+        if let Some(directory) = self.back.pop() {
+            let previous = std::mem::replace(&mut self.directory, directory);
+            self.forward.push(previous);
+            self.refresh();
+        }
+    }
+
+    fn navigate_forward(&mut self) {
+        if let Some(directory) = self.forward.pop() {
+            let previous = std::mem::replace(&mut self.directory, directory);
+            self.back.push(previous);
+            self.refresh();
+        }
+    }
+
+    /// Draws the browser while it is open. Returns how it was closed, on the frame it closes.
+    pub fn build(&mut self, ui: &Ui) -> Option<FileBrowserResult> {
+        let _id = ui.push_id(&self.title);
+        if self.open_requested {
+            self.open_requested = false;
+            self.close_requested = false;
+            self.is_open = true;
+            ui.open_popup(&self.title);
+        }
+        if !self.is_open {
+            return None;
+        }
+        if self.needs_refresh {
+            self.refresh();
+        }
+
+        let mut opened = true;
+        let size = [ui.current_font_size() * 45.0, ui.current_font_size() * 30.0];
+        unsafe { sys::igSetNextWindowSize(size.into(), Condition::FirstUseEver as i32) };
+        let title = self.title.clone();
+        let result = ui.modal_popup_config(&title).opened(&mut opened).build(|| {
+            let result = if self.close_requested {
+                Some(FileBrowserResult::Cancelled)
+            } else {
+                self.build_contents(ui)
+            };
+            if result.is_some() {
+                ui.close_current_popup();
+            }
+            result
+        });
+        // The popup is also gone if it was closed from elsewhere
+        let result = match result {
+            Some(result) if opened => result,
+            _ => Some(FileBrowserResult::Cancelled),
+        };
+        if result.is_some() {
+            self.is_open = false;
+        }
+        result
+    }
+
+    fn build_contents(&mut self, ui: &Ui) -> Option<FileBrowserResult> {
+        self.build_navigation(ui);
+
+        let footer_height = ui.frame_height_with_spacing() * 2.0;
+        let mut confirmed = false;
+        if let Some(error) = &self.error {
+            ui.child_window("##error")
+                .size([0.0, -footer_height])
+                .build(|| ui.text_colored(self.error_color, error));
+        } else {
+            confirmed = self.build_table(ui, footer_height);
+        }
+
+        self.build_footer(ui, confirmed)
+    }
+
+    fn build_navigation(&mut self, ui: &Ui) {
+        ui.enabled(!self.back.is_empty(), || {
+            if ui.arrow_button("##back", crate::Direction::Left) {
+                self.navigate_back();
+            }
+        });
+        ui.same_line();
+        ui.enabled(!self.forward.is_empty(), || {
+            if ui.arrow_button("##forward", crate::Direction::Right) {
+                self.navigate_forward();
+            }
+        });
+        ui.same_line();
+        let parent = self.directory.parent().map(Path::to_path_buf);
+        ui.enabled(parent.is_some(), || {
+            if ui.arrow_button("##up", crate::Direction::Up) {
+                if let Some(parent) = &parent {
+                    self.set_directory(parent);
+                }
+            }
+        });
+        ui.same_line();
+        if ui.button("Refresh") {
+            self.refresh();
+        }
+
+        ui.same_line();
+        if ui.button("New folder") {
+            self.new_folder_name.clear();
+            self.new_folder_error = None;
+            ui.open_popup("##new_folder");
+        }
+        ui.popup("##new_folder", || {
+            ui.set_keyboard_focus_here();
+            let entered = ui
+                .input_text("Name", &mut self.new_folder_name)
+                .enter_returns_true(true)
+                .build();
+            if (ui.button("Create") || entered) && self.submit_new_folder() {
+                ui.close_current_popup();
+            }
+            if let Some(error) = &self.new_folder_error {
+                ui.text_colored(self.error_color, error);
+            }
+        });
+
+        // Breadcrumbs, one button per ancestor of the directory
+        let ancestors: Vec<PathBuf> = self.directory.ancestors().map(Path::to_path_buf).collect();
+        let mut target = None;
+        for (index, ancestor) in ancestors.iter().rev().enumerate() {
+            let label = match ancestor.file_name() {
+                Some(name) => name.to_string_lossy().into_owned(),
+                None => ancestor.display().to_string(),
+            };
+            if index > 0 {
+                ui.same_line_with_spacing(0.0, 2.0);
+                ui.text_disabled("/");
+                ui.same_line_with_spacing(0.0, 2.0);
+            }
+            let _id = ui.push_id_usize(index);
+            if ui.small_button(&label) {
+                target = Some(ancestor.clone());
+            }
+        }
+        if let Some(target) = target {
+            self.set_directory(target);
+        }
+    }
+
+    /// Draws the entries, and returns `true` if a file was double-clicked to confirm it.
+    fn build_table(&mut self, ui: &Ui, footer_height: f32) -> bool {
+        let flags = TableFlags::SCROLL_Y
+            | TableFlags::ROW_BG
+            | TableFlags::BORDERS_OUTER
+            | TableFlags::RESIZABLE
+            | TableFlags::SORTABLE;
+        let Some(_table) =
+            ui.begin_table_with_sizing("##entries", 3, flags, [0.0, -footer_height], 0.0)
+        else {
+            return false;
+        };
+        let columns = [
+            (
+                "Name",
+                TableColumnFlags::WIDTH_STRETCH | TableColumnFlags::DEFAULT_SORT,
+            ),
+            ("Size", TableColumnFlags::WIDTH_FIXED),
+            ("Modified (UTC)", TableColumnFlags::WIDTH_FIXED),
+        ];
+        for (index, (name, flags)) in columns.into_iter().enumerate() {
+            ui.table_setup_column_with(TableColumnSetup {
+                name,
+                flags,
+                init_width_or_weight: 0.0,
+                user_id: Id(index as u32),
+            });
+        }
+        ui.table_setup_scroll_freeze(0, 1);
+
+        if let Some(mut specs) = ui.table_sort_specs_mut() {
+            if specs.should_sort() {
+                let sort = specs
+                    .specs()
+                    .iter()
+                    .find_map(|spec| Some((spec.column_idx(), spec.sort_direction()?)));
+                if let Some((column, direction)) = sort {
+                    let column = match column {
+                        1 => SortColumn::Size,
+                        2 => SortColumn::Modified,
+                        _ => SortColumn::Name,
+                    };
+                    self.sort = (column, direction);
+                    let selected = self.selected_paths();
+                    self.sort_entries();
+                    // Keep the same entries selected after sorting
+                    self.selected = (0..self.entries.len())
+                        .filter(|&index| selected.contains(&self.entries[index].path))
+                        .collect();
+                    self.last_clicked = None;
+                }
+                specs.set_sorted();
+            }
+        }
+        ui.table_headers_row();
+
+        let directory_color = ui.style_color(StyleColor::PlotLinesHovered);
+        let mut clicked = None;
+        let mut entered = None;
+        let clipper = ListClipper::new(self.entries.len() as i32).begin(ui);
+        for index in clipper.iter() {
+            let index = index as usize;
+            let entry = &self.entries[index];
+            ui.table_next_row();
+            ui.table_next_column();
+            let _id = ui.push_id_usize(index);
+            if ui
+                .selectable_config("##entry")
+                .selected(self.selected.contains(&index))
+                .flags(
+                    SelectableFlags::SPAN_ALL_COLUMNS
+                        | SelectableFlags::ALLOW_DOUBLE_CLICK
+                        | SelectableFlags::NO_AUTO_CLOSE_POPUPS,
+                )
+                .build()
+            {
+                clicked = Some(index);
+                if ui.is_mouse_double_clicked(MouseButton::Left) {
+                    entered = Some(index);
+                }
+            }
+            ui.same_line();
+            if entry.is_dir {
+                ui.text_colored(directory_color, format!("{}/", entry.name));
+            } else {
+                ui.text(&entry.name);
+            }
+            ui.table_next_column();
+            if !entry.is_dir {
+                ui.text(format_size(entry.size));
+            }
+            ui.table_next_column();
+            if let Some(modified) = entry.modified {
+                ui.text(format_time(modified));
+            }
+        }
+
+        if let Some(index) = clicked {
+            let io = ui.io();
+            self.click(index, io.key_ctrl, io.key_shift);
+        }
+        if let Some(index) = entered {
+            let entry = &self.entries[index];
+            if entry.is_dir {
+                let path = entry.path.clone();
+                self.set_directory(path);
+            } else {
+                return self.mode != FileBrowserMode::SelectDirectory;
+            }
+        }
+        false
+    }
+
+    fn click(&mut self, index: usize, ctrl: bool, shift: bool) {
+        let multiple = self.multiple && self.mode == FileBrowserMode::Open;
+        match self.last_clicked {
+            Some(last) if multiple && shift => {
+                self.selected = (last.min(index)..=last.max(index)).collect();
+            }
+            _ if multiple && ctrl => {
+                if !self.selected.remove(&index) {
+                    self.selected.insert(index);
+                }
+                self.last_clicked = Some(index);
+            }
+            _ => {
+                self.selected = BTreeSet::from([index]);
+                self.last_clicked = Some(index);
+            }
+        }
+        // Show the selected files in the name field
+        let names: Vec<&str> = self
+            .selected
+            .iter()
+            .map(|&index| &self.entries[index])
+            .filter(|entry| entry.is_dir == (self.mode == FileBrowserMode::SelectDirectory))
+            .map(|entry| entry.name.as_str())
+            .collect();
+        if !names.is_empty() {
+            self.file_name = names.join(", ");
+        }
+    }
+
+    fn build_footer(&mut self, ui: &Ui, confirmed: bool) -> Option<FileBrowserResult> {
+        let confirm_label = match self.mode {
+            FileBrowserMode::Open => "Open",
+            FileBrowserMode::Save => "Save",
+            FileBrowserMode::SelectDirectory => "Select",
+        };
+        let style = unsafe { ui.style() };
+        let buttons_width = ui.calc_text_size(confirm_label)[0]
+            + ui.calc_text_size("Cancel")[0]
+            + style.frame_padding[0] * 4.0
+            + style.item_spacing[0];
+
+        ui.set_next_item_width(-1.0);
+        let entered = ui
+            .input_text("##file_name", &mut self.file_name)
+            .hint("File name")
+            .enter_returns_true(true)
+            .build();
+        if ui.is_item_edited() {
+            self.selected.clear();
+        }
+        let entered = entered || confirmed;
+        if !self.filters.is_empty() {
+            let labels: Vec<String> = self.filters.iter().map(FileFilter::label).collect();
+            let width = ui.content_region_avail()[0] - buttons_width - style.item_spacing[0];
+            ui.set_next_item_width(width);
+            if ui.combo_simple_string("##filter", &mut self.active_filter, &labels) {
+                self.refresh();
+            }
+            ui.same_line();
+        } else {
+            ui.dummy([ui.content_region_avail()[0] - buttons_width, 0.0]);
+            ui.same_line();
+        }
+
+        let target = self.confirmed_paths();
+        let mut result = None;
+        ui.enabled(target.is_some(), || {
+            if ui.button(confirm_label) || entered {
+                result = target.clone();
+            }
+        });
+        ui.same_line();
+        if ui.button("Cancel") {
+            return Some(FileBrowserResult::Cancelled);
+        }
+
+        let paths = result?;
+        // Picking a single directory while opening files enters it instead
+        if self.mode == FileBrowserMode::Open && paths.len() == 1 && paths[0].is_dir() {
+            self.set_directory(&paths[0]);
+            return None;
+        }
+        Some(FileBrowserResult::Selected(paths))
+    }
+
+    /// Returns what confirming the browser now would pick, if anything.
+    fn confirmed_paths(&self) -> Option<Vec<PathBuf>> {
+        let selected: Vec<&Entry> = self.selected.iter().map(|&i| &self.entries[i]).collect();
+        match self.mode {
+            FileBrowserMode::Open => {
+                if let [entry] = selected.as_slice() {
+                    return Some(vec![entry.path.clone()]);
+                }
+                if selected.len() > 1 {
+                    let files: Vec<PathBuf> = selected
+                        .iter()
+                        .filter(|entry| !entry.is_dir)
+                        .map(|entry| entry.path.clone())
+                        .collect();
+                    return (!files.is_empty()).then_some(files);
+                }
+                let path = self.directory.join(self.file_name.trim());
+                (!self.file_name.trim().is_empty() && path.exists()).then(|| vec![path])
+            }
+            FileBrowserMode::Save => {
+                let name = self.file_name.trim();
+                if name.is_empty() {
+                    return None;
+                }
+                let mut path = self.directory.join(name);
+                // Adds the extension of the filter if the name has none
+                let extension = self
+                    .filters
+                    .get(self.active_filter)
+                    .and_then(|filter| filter.extensions.first());
+                if let (None, Some(extension)) = (path.extension(), extension) {
+                    path.set_extension(extension);
+                }
+                (!path.is_dir()).then(|| vec![path])
+            }
+            FileBrowserMode::SelectDirectory => match selected.as_slice() {
+                [entry] if entry.is_dir => Some(vec![entry.path.clone()]),
+                _ => Some(vec![self.directory.clone()]),
+            },
+        }
+    }
+}
+
+/// Formats a file size with binary units, e.g. `1.5 KiB`.
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if size < 1024 {
+        return format!("{} B", size);
+    }
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Formats a modification time as a UTC `YYYY-MM-DD HH:MM`.
+fn format_time(time: SystemTime) -> String {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs_f64(),
+        Err(error) => -error.duration().as_secs_f64(),
+    };
+    let (date, time) = utc_date_time(seconds);
+    format!("{} {}", date, &time[..5])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "imgui-rs-file-browser-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn names(browser: &FileBrowser) -> Vec<&str> {
+        browser.entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_listing_filters_and_sorting() {
+        let dir = temp_dir("listing");
+        fs::write(dir.join("b.png"), [0; 10]).unwrap();
+        fs::write(dir.join("A.JPG"), [0; 1000]).unwrap();
+        fs::write(dir.join("notes.txt"), [0; 100]).unwrap();
+        fs::write(dir.join(".hidden"), []).unwrap();
+        fs::create_dir(dir.join("z_dir")).unwrap();
+
+        let mut browser = FileBrowser::new("Open")
+            .directory(&dir)
+            .filter(FileFilter::new("Images", ["png", "jpg"]))
+            .filter(FileFilter::new("All files", Vec::<String>::new()));
+        browser.refresh();
+        assert_eq!(names(&browser), ["z_dir", "A.JPG", "b.png"]);
+
+        browser.active_filter = 1;
+        browser.sort = (SortColumn::Size, TableSortDirection::Descending);
+        browser.refresh();
+        assert_eq!(names(&browser), ["z_dir", "A.JPG", "notes.txt", "b.png"]);
+
+        let mut browser = browser.mode(FileBrowserMode::SelectDirectory);
+        browser.refresh();
+        assert_eq!(names(&browser), ["z_dir"]);
+        assert_eq!(browser.confirmed_paths(), Some(vec![dir.clone()]));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_navigation_selection_and_new_folder() {
+        let dir = temp_dir("navigation");
+        fs::write(dir.join("a.txt"), []).unwrap();
+        fs::write(dir.join("b.txt"), []).unwrap();
+        fs::write(dir.join("c.txt"), []).unwrap();
+
+        let mut browser = FileBrowser::new("Open").directory(&dir).multiple(true);
+        browser.refresh();
+        browser.click(0, false, false);
+        browser.click(2, false, true);
+        assert_eq!(browser.selected.len(), 3);
+        browser.click(1, true, false);
+        assert_eq!(
+            browser.confirmed_paths(),
+            Some(vec![dir.join("a.txt"), dir.join("c.txt")])
+        );
+
+        let created = browser.create_folder("sub").unwrap();
+        assert!(created.is_dir());
+        assert_eq!(browser.selected_paths(), vec![created.clone()]);
+        assert!(browser.create_folder("a/b").is_err());
+
+        // A failed attempt from the popup doesn't replace the listing
+        browser.new_folder_name = String::from("sub");
+        assert!(!browser.submit_new_folder());
+        assert!(browser.new_folder_error.is_some());
+        assert_eq!(browser.error, None);
+        assert_eq!(names(&browser), ["sub", "a.txt", "b.txt", "c.txt"]);
+        browser.new_folder_name = String::from("other");
+        assert!(browser.submit_new_folder());
+        assert_eq!(browser.new_folder_error, None);
+        assert_eq!(browser.selected_paths(), vec![dir.join("other")]);
+
+        browser.set_directory(&created);
+        assert!(browser.entries.is_empty());
+        browser.navigate_back();
+        assert_eq!(browser.current_directory(), dir);
+        browser.navigate_forward();
+        assert_eq!(browser.current_directory(), created);
+
+        let mut browser = FileBrowser::new("Save")
+            .mode(FileBrowserMode::Save)
+            .directory(&dir)
+            .filter(FileFilter::new("Text", ["txt"]))
+            .file_name("report");
+        browser.refresh();
+        assert_eq!(
+            browser.confirmed_paths(),
+            Some(vec![dir.join("report.txt")])
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_and_cancel() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let dir = temp_dir("popup");
+        let mut browser = FileBrowser::new("Browse").directory(&dir);
+        browser.open();
+        for _ in 0..3 {
+            let ui = ctx.frame();
+            assert_eq!(browser.build(ui), None);
+            ctx.render();
+        }
+        assert!(browser.is_open());
+
+        // Closing the browser from outside cancels it
+        browser.close();
+        let ui = ctx.frame();
+        assert_eq!(browser.build(ui), Some(FileBrowserResult::Cancelled));
+        ctx.render();
+        assert!(!browser.is_open());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,1171 @@
+use mint::ColumnMatrix4;
+
+use crate::math::MintVec2;
+use crate::{DrawListMut, MouseButton, StyleColor, Ui};
+
+type Mat4 = [[f32; 4]; 4];
+type Vec3 = [f32; 3];
+
+const AXIS_COLORS: [[f32; 4]; 3] = [
+    [0.9, 0.2, 0.2, 1.0],
+    [0.2, 0.75, 0.2, 1.0],
+    [0.25, 0.4, 0.95, 1.0],
+];
+const HOVERED_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+const CENTER_COLOR: [f32; 4] = [0.9, 0.9, 0.9, 1.0];
+/// Distance from a handle under which the mouse hovers it, in pixels.
+const PICK_DISTANCE: f32 = 6.0;
+const CIRCLE_SEGMENTS: usize = 64;
+
+/// What a [Gizmo] changes in a transform.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GizmoOperation {
+    /// Arrows which move along an axis, and a center handle which moves in the screen plane.
+    #[default]
+    Translate,
+    /// Circles which rotate around an axis.
+    Rotate,
+    /// Handles which scale along a local axis, and a center handle which scales uniformly.
+    Scale,
+}
+
+/// The axes a [Gizmo] translates and rotates along. Scaling always uses the local axes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GizmoSpace {
+    /// The axes of the transform itself.
+    Local,
+    /// The axes of the world.
+    #[default]
+    World,
+}
+
+/// The draw list [Gizmo]s and [ViewCube]s are drawn into.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GizmoDrawList {
+    /// The draw list of the current window.
+    #[default]
+    Window,
+    /// The background draw list, behind all windows.
+    Background,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Handle {
+    Axis(usize),
+    Center,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Drag {
+    handle: Handle,
+    /// The transform when the drag started.
+    start: Mat4,
+    /// The axis the drag happens along or around, in world space.
+    axis: Vec3,
+    /// Where the drag started: the parameter along the axis when translating along an axis,
+    /// the point in the drag plane when translating in the screen plane or rotating.
+    anchor: Vec3,
+    start_mouse: [f32; 2],
+}
+
+/// Maps world coordinates to the screen, and the mouse to rays in the world.
+#[derive(Copy, Clone, Debug)]
+struct Camera {
+    view: Mat4,
+    view_proj: Mat4,
+    inverse_view_proj: Mat4,
+    min: [f32; 2],
+    size: [f32; 2],
+}
+
+impl Camera {
+    fn new(view: Mat4, projection: Mat4, min: [f32; 2], size: [f32; 2]) -> Option<Self> {
+        let view_proj = mul(projection, view);
+        Some(Camera {
+            view,
+            view_proj,
+            inverse_view_proj: inverse(view_proj)?,
+            min,
+            size,
+        })
+    }
+
+    /// Returns the screen position of `p`, or `None` if it is behind the camera.
+    fn project(&self, p: Vec3) -> Option<[f32; 2]> {
+        let clip = transform(self.view_proj, [p[0], p[1], p[2], 1.0]);
+        if clip[3] <= 1e-6 {
+            return None;
+        }
+        Some([
+            self.min[0] + (clip[0] / clip[3] * 0.5 + 0.5) * self.size[0],
+            self.min[1] + (0.5 - clip[1] / clip[3] * 0.5) * self.size[1],
+        ])
+    }
+
+    /// Returns the origin and direction of the ray through the screen position `p`.
+    fn ray(&self, p: [f32; 2]) -> (Vec3, Vec3) {
+        let x = (p[0] - self.min[0]) / self.size[0] * 2.0 - 1.0;
+        let y = 1.0 - (p[1] - self.min[1]) / self.size[1] * 2.0;
+        // Any two depths give points on the ray, whatever the depth range of the projection
+        let near = unproject(self.inverse_view_proj, [x, y, 0.0, 1.0]);
+        let far = unproject(self.inverse_view_proj, [x, y, 0.5, 1.0]);
+        (near, normalize(sub(far, near)))
+    }
+
+    /// Returns the camera position, in world space.
+    fn position(&self) -> Vec3 {
+        let inverse_view = inverse(self.view).unwrap_or(IDENTITY);
+        [inverse_view[3][0], inverse_view[3][1], inverse_view[3][2]]
+    }
+}
+
+/// A 3D transform gizmo, which draws translate, rotate or scale handles over a viewport, and
+/// lets the user drag them to edit a transform.
+///
+/// Matrices are column-major `mint` matrices, which most math libraries convert to and from.
+/// The view matrix maps world to camera space, and the projection maps camera space to clip
+/// space; both OpenGL-style and zero-to-one depth ranges work.
+///
+/// By default the viewport is the current window, and the gizmo is drawn into its draw list,
+/// which suits a window showing the rendered scene as an [Image](crate::Image).
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let (view, projection) = ([[1.0f32; 4]; 4], [[1.0f32; 4]; 4]);
+/// let mut gizmo = Gizmo::new("gizmo")
+///     .operation(GizmoOperation::Rotate)
+///     .rotate_snap(15.0);
+/// let mut object: [[f32; 4]; 4] = [
+///     [1.0, 0.0, 0.0, 0.0],
+///     [0.0, 1.0, 0.0, 0.0],
+///     [0.0, 0.0, 1.0, 0.0],
+///     [0.0, 0.0, 0.0, 1.0],
+/// ];
+/// // Every frame, inside the viewport window:
+/// if let Some(transform) = gizmo.manipulate(ui, view, projection, object) {
+///     object = transform.into();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Gizmo {
+    id: String,
+    operation: GizmoOperation,
+    space: GizmoSpace,
+    size: f32,
+    translate_snap: f32,
+    rotate_snap: f32,
+    scale_snap: f32,
+    draw_list: GizmoDrawList,
+    viewport: Option<([f32; 2], [f32; 2])>,
+    drag: Option<Drag>,
+}
+
+impl Gizmo {
+    /// Creates a new gizmo with the given id.
+    pub fn new(id: impl Into<String>) -> Self {
+        Gizmo {
+            id: id.into(),
+            operation: GizmoOperation::Translate,
+            space: GizmoSpace::World,
+            size: 100.0,
+            translate_snap: 0.0,
+            rotate_snap: 0.0,
+            scale_snap: 0.0,
+            draw_list: GizmoDrawList::Window,
+            viewport: None,
+            drag: None,
+        }
+    }
+
+    /// Sets the operation.
+    pub fn operation(mut self, operation: GizmoOperation) -> Self {
+        self.operation = operation;
+        self
+    }
+
+    /// Sets the space of the axes.
+    pub fn space(mut self, space: GizmoSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Sets the length of the handles, in pixels.
+    ///
+    /// Defaults to 100.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the increment translations snap to, in world units. Zero disables snapping.
+    ///
+    /// Disabled by default.
+    pub fn translate_snap(mut self, snap: f32) -> Self {
+        self.translate_snap = snap.max(0.0);
+        self
+    }
+
+    /// Sets the increment rotations snap to, in degrees. Zero disables snapping.
+    ///
+    /// Disabled by default.
+    pub fn rotate_snap(mut self, snap: f32) -> Self {
+        self.rotate_snap = snap.max(0.0);
+        self
+    }
+
+    /// Sets the increment scale factors snap to. Zero disables snapping.
+    ///
+    /// Disabled by default.
+    pub fn scale_snap(mut self, snap: f32) -> Self {
+        self.scale_snap = snap.max(0.0);
+        self
+    }
+
+    /// Sets the draw list the gizmo is drawn into.
+    pub fn draw_list(mut self, draw_list: GizmoDrawList) -> Self {
+        self.draw_list = draw_list;
+        self
+    }
+
+    /// Returns the operation.
+    pub fn current_operation(&self) -> GizmoOperation {
+        self.operation
+    }
+
+    /// Changes the operation, for example from a keyboard shortcut.
+    pub fn set_operation(&mut self, operation: GizmoOperation) {
+        self.operation = operation;
+        self.drag = None;
+    }
+
+    /// Returns the space of the axes.
+    pub fn current_space(&self) -> GizmoSpace {
+        self.space
+    }
+
+    /// Changes the space of the axes.
+    pub fn set_space(&mut self, space: GizmoSpace) {
+        self.space = space;
+        self.drag = None;
+    }
+
+    /// Sets the screen rectangle the projection maps to. Defaults to the current window.
+    pub fn set_viewport(&mut self, min: impl Into<MintVec2>, size: impl Into<MintVec2>) {
+        self.viewport = Some((min.into().into(), size.into().into()));
+    }
+
+    /// Returns whether the user is dragging a handle.
+    pub fn is_using(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Draws the gizmo for `model`, and handles the mouse. Returns the edited transform while
+    /// the user drags a handle and it changed.
+    pub fn manipulate(
+        &mut self,
+        ui: &Ui,
+        view: impl Into<ColumnMatrix4<f32>>,
+        projection: impl Into<ColumnMatrix4<f32>>,
+        model: impl Into<ColumnMatrix4<f32>>,
+    ) -> Option<ColumnMatrix4<f32>> {
+        let view: Mat4 = view.into().into();
+        let projection: Mat4 = projection.into().into();
+        let model: Mat4 = model.into().into();
+        let (min, size) = self
+            .viewport
+            .unwrap_or_else(|| (ui.window_pos(), ui.window_size()));
+        let camera = Camera::new(view, projection, min, size)?;
+        let id = ui.new_id_str(&self.id).0;
+        let mouse = ui.io().mouse_pos;
+
+        let origin = [model[3][0], model[3][1], model[3][2]];
+        let origin_clip = transform(camera.view_proj, [origin[0], origin[1], origin[2], 1.0]);
+        if origin_clip[3] <= 1e-6 {
+            self.end_drag(id);
+            return None;
+        }
+        // World length of the handles, so that they keep the same size on screen
+        let length = self.size * origin_clip[3] / (projection[1][1] * size[1] * 0.5);
+        let axes = self.axes(&model);
+
+        let hovered = if self.drag.is_none() && ui.is_window_hovered() && !ui.is_any_item_active() {
+            self.pick(&camera, origin, axes, length, mouse)
+        } else {
+            None
+        };
+        if let Some(handle) = hovered {
+            unsafe { sys::imgui_rs_SetHoveredID(id) };
+            if ui.is_mouse_clicked(MouseButton::Left) {
+                self.drag = self.start_drag(&camera, &model, handle, axes, mouse);
+            }
+        }
+
+        let mut result = None;
+        if let Some(drag) = self.drag {
+            if ui.is_mouse_down(MouseButton::Left) {
+                unsafe { sys::imgui_rs_SetActiveID(id) };
+                let edited = self.apply_drag(&camera, &drag, length, mouse);
+                if edited != model {
+                    result = Some(edited.into());
+                }
+            } else {
+                self.end_drag(id);
+            }
+        }
+
+        let active = self.drag.map(|drag| drag.handle).or(hovered);
+        let shown = result.map_or(model, |m: ColumnMatrix4<f32>| m.into());
+        let draw_list = match self.draw_list {
+            GizmoDrawList::Window => ui.get_window_draw_list(),
+            GizmoDrawList::Background => ui.get_background_draw_list(),
+        };
+        draw_list.with_clip_rect_intersect(min, [min[0] + size[0], min[1] + size[1]], || {
+            self.draw(ui, &draw_list, &camera, &shown, length, active);
+        });
+        result
+    }
+
+    fn end_drag(&mut self, id: u32) {
+        if self.drag.take().is_some() {
+            unsafe { sys::imgui_rs_ClearActiveID(id) };
+        }
+    }
+
+    /// Returns the directions of the translation and rotation axes.
+    fn axes(&self, model: &Mat4) -> [Vec3; 3] {
+        match (self.space, self.operation) {
+            (GizmoSpace::World, GizmoOperation::Translate | GizmoOperation::Rotate) => {
+                [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+            }
+            _ => [0, 1, 2].map(|i| normalize([model[i][0], model[i][1], model[i][2]])),
+        }
+    }
+
+    fn pick(
+        &self,
+        camera: &Camera,
+        origin: Vec3,
+        axes: [Vec3; 3],
+        length: f32,
+        mouse: [f32; 2],
+    ) -> Option<Handle> {
+        let center = camera.project(origin)?;
+        let mut best = None;
+        let mut consider = |handle, distance: f32| {
+            if distance < PICK_DISTANCE && best.is_none_or(|(_, d)| distance < d) {
+                best = Some((handle, distance));
+            }
+        };
+        match self.operation {
+            GizmoOperation::Translate | GizmoOperation::Scale => {
+                consider(
+                    Handle::Center,
+                    distance(center, mouse) - PICK_DISTANCE * 0.5,
+                );
+                for (i, axis) in axes.iter().enumerate() {
+                    let Some(end) = camera.project(add(origin, scale(*axis, length))) else {
+                        continue;
+                    };
+                    // Axes pointing at the camera are too short to be picked reliably
+                    if distance(center, end) < self.size * 0.15 {
+                        continue;
+                    }
+                    consider(Handle::Axis(i), segment_distance(center, end, mouse));
+                }
+            }
+            GizmoOperation::Rotate => {
+                for (i, axis) in axes.iter().enumerate() {
+                    let points = circle_points(camera, origin, *axis, length);
+                    let d = points
+                        .windows(2)
+                        .map(|w| segment_distance(w[0], w[1], mouse))
+                        .fold(f32::MAX, f32::min);
+                    consider(Handle::Axis(i), d);
+                }
+            }
+        }
+        best.map(|(handle, _)| handle)
+    }
+
+    fn start_drag(
+        &self,
+        camera: &Camera,
+        model: &Mat4,
+        handle: Handle,
+        axes: [Vec3; 3],
+        mouse: [f32; 2],
+    ) -> Option<Drag> {
+        let origin = [model[3][0], model[3][1], model[3][2]];
+        let (ray_origin, ray_direction) = camera.ray(mouse);
+        let (axis, anchor) = match (self.operation, handle) {
+            (GizmoOperation::Translate, Handle::Axis(i)) => {
+                let t = closest_on_axis(origin, axes[i], ray_origin, ray_direction)?;
+                (axes[i], [t, 0.0, 0.0])
+            }
+            (GizmoOperation::Translate, Handle::Center) => {
+                let normal = normalize(sub(origin, camera.position()));
+                let hit = intersect_plane(origin, normal, ray_origin, ray_direction)?;
+                (normal, hit)
+            }
+            (GizmoOperation::Rotate, Handle::Axis(i)) => {
+                let hit = intersect_plane(origin, axes[i], ray_origin, ray_direction)?;
+                (axes[i], hit)
+            }
+            (GizmoOperation::Scale, Handle::Axis(i)) => (axes[i], [0.0; 3]),
+            _ => ([0.0; 3], [0.0; 3]),
+        };
+        Some(Drag {
+            handle,
+            start: *model,
+            axis,
+            anchor,
+            start_mouse: mouse,
+        })
+    }
+
+    /// Returns the snapped angle a rotation drag rotated by so far, in radians.
+    fn rotation_angle(&self, camera: &Camera, drag: &Drag, mouse: [f32; 2]) -> Option<f32> {
+        let origin = [drag.start[3][0], drag.start[3][1], drag.start[3][2]];
+        let (ray_origin, ray_direction) = camera.ray(mouse);
+        let hit = intersect_plane(origin, drag.axis, ray_origin, ray_direction)?;
+        let from = sub(drag.anchor, origin);
+        let to = sub(hit, origin);
+        let angle = dot(drag.axis, cross(from, to))
+            .atan2(dot(from, to))
+            .to_degrees();
+        Some(snap(angle, self.rotate_snap).to_radians())
+    }
+
+    fn apply_drag(&self, camera: &Camera, drag: &Drag, length: f32, mouse: [f32; 2]) -> Mat4 {
+        let start = drag.start;
+        let origin = [start[3][0], start[3][1], start[3][2]];
+        let (ray_origin, ray_direction) = camera.ray(mouse);
+        match (self.operation, drag.handle) {
+            (GizmoOperation::Translate, handle) => {
+                let delta = match handle {
+                    Handle::Axis(_) => {
+                        closest_on_axis(origin, drag.axis, ray_origin, ray_direction).map(|t| {
+                            scale(drag.axis, snap(t - drag.anchor[0], self.translate_snap))
+                        })
+                    }
+                    Handle::Center => intersect_plane(origin, drag.axis, ray_origin, ray_direction)
+                        .map(|hit| sub(hit, drag.anchor).map(|v| snap(v, self.translate_snap))),
+                };
+                let mut edited = start;
+                if let Some(delta) = delta {
+                    for (i, d) in delta.iter().enumerate() {
+                        edited[3][i] += d;
+                    }
+                }
+                edited
+            }
+            (GizmoOperation::Rotate, _) => {
+                let Some(angle) = self.rotation_angle(camera, drag, mouse) else {
+                    return start;
+                };
+                let rotation = rotation_about(drag.axis, angle);
+                let mut edited = mul(rotation, start);
+                // Rotate around the origin of the transform
+                for (i, &o) in origin.iter().enumerate() {
+                    edited[3][i] = o;
+                }
+                edited
+            }
+            (GizmoOperation::Scale, handle) => {
+                let moved = [
+                    mouse[0] - drag.start_mouse[0],
+                    mouse[1] - drag.start_mouse[1],
+                ];
+                let factors = match handle {
+                    Handle::Axis(i) => {
+                        let center = camera.project(origin).unwrap_or(drag.start_mouse);
+                        let end = camera
+                            .project(add(origin, scale(drag.axis, length)))
+                            .unwrap_or(center);
+                        let screen_axis = [end[0] - center[0], end[1] - center[1]];
+                        let screen_length = screen_axis[0].powi(2) + screen_axis[1].powi(2);
+                        let mut factors = [1.0; 3];
+                        if screen_length > 1.0 {
+                            factors[i] += (moved[0] * screen_axis[0] + moved[1] * screen_axis[1])
+                                / screen_length;
+                        }
+                        factors
+                    }
+                    Handle::Center => [1.0 + (moved[0] - moved[1]) / self.size; 3],
+                };
+                let mut edited = start;
+                for (column, factor) in edited.iter_mut().zip(factors) {
+                    let factor = snap(factor, self.scale_snap).max(1e-3);
+                    for v in column.iter_mut().take(3) {
+                        *v *= factor;
+                    }
+                }
+                edited
+            }
+        }
+    }
+
+    fn draw(
+        &self,
+        ui: &Ui,
+        draw_list: &DrawListMut<'_>,
+        camera: &Camera,
+        model: &Mat4,
+        length: f32,
+        active: Option<Handle>,
+    ) {
+        let origin = [model[3][0], model[3][1], model[3][2]];
+        let Some(center) = camera.project(origin) else {
+            return;
+        };
+        let color = |handle: Handle| {
+            if active == Some(handle) {
+                HOVERED_COLOR
+            } else {
+                match handle {
+                    Handle::Axis(i) => AXIS_COLORS[i],
+                    Handle::Center => CENTER_COLOR,
+                }
+            }
+        };
+        let axes = self.axes(model);
+        match self.operation {
+            GizmoOperation::Translate | GizmoOperation::Scale => {
+                for (i, axis) in axes.iter().enumerate() {
+                    let Some(end) = camera.project(add(origin, scale(*axis, length))) else {
+                        continue;
+                    };
+                    let screen_length = distance(center, end);
+                    if screen_length < self.size * 0.15 {
+                        continue;
+                    }
+                    let color = color(Handle::Axis(i));
+                    draw_list
+                        .add_line(center, end, color)
+                        .thickness(3.0)
+                        .build();
+                    let direction = [
+                        (end[0] - center[0]) / screen_length,
+                        (end[1] - center[1]) / screen_length,
+                    ];
+                    let normal = [-direction[1], direction[0]];
+                    if self.operation == GizmoOperation::Translate {
+                        let (head, width) = (12.0, 6.0);
+                        let tip = [end[0] + direction[0] * head, end[1] + direction[1] * head];
+                        draw_list
+                            .add_triangle(
+                                tip,
+                                [end[0] + normal[0] * width, end[1] + normal[1] * width],
+                                [end[0] - normal[0] * width, end[1] - normal[1] * width],
+                                color,
+                            )
+                            .filled(true)
+                            .build();
+                    } else {
+                        draw_list
+                            .add_rect(
+                                [end[0] - 5.0, end[1] - 5.0],
+                                [end[0] + 5.0, end[1] + 5.0],
+                                color,
+                            )
+                            .filled(true)
+                            .build();
+                    }
+                }
+                let color = color(Handle::Center);
+                if self.operation == GizmoOperation::Translate {
+                    draw_list
+                        .add_circle(center, PICK_DISTANCE, color)
+                        .thickness(2.0)
+                        .build();
+                } else {
+                    let half = PICK_DISTANCE;
+                    draw_list
+                        .add_rect(
+                            [center[0] - half, center[1] - half],
+                            [center[0] + half, center[1] + half],
+                            color,
+                        )
+                        .filled(true)
+                        .build();
+                }
+            }
+            GizmoOperation::Rotate => {
+                for (i, axis) in axes.iter().enumerate() {
+                    let points = circle_points(camera, origin, *axis, length);
+                    draw_list
+                        .add_polyline(points, color(Handle::Axis(i)))
+                        .thickness(2.5)
+                        .build();
+                }
+                // Show the angle rotated so far
+                let angle = self
+                    .drag
+                    .and_then(|drag| self.rotation_angle(camera, &drag, ui.io().mouse_pos));
+                if let Some(angle) = angle {
+                    draw_list.add_text(
+                        [center[0] + 10.0, center[1] - 10.0 - ui.current_font_size()],
+                        ui.style_color(StyleColor::Text),
+                        format!("{:.1}°", angle.to_degrees()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A cube showing the orientation of a camera, which can be dragged to orbit the camera
+/// around the point it looks at, or clicked on a face to look at the scene from that side.
+///
+/// The camera is described by its view matrix, as for [Gizmo], and is assumed to look down
+/// its negative Z axis, as with the usual right-handed `look_at` functions.
+#[derive(Clone, Debug)]
+pub struct ViewCube {
+    id: String,
+    size: f32,
+    distance: f32,
+    draw_list: GizmoDrawList,
+    /// Whether the cube is being dragged, and whether the mouse moved since it was pressed.
+    drag: Option<bool>,
+}
+
+impl ViewCube {
+    /// Creates a new view cube with the given id.
+    pub fn new(id: impl Into<String>) -> Self {
+        ViewCube {
+            id: id.into(),
+            size: 100.0,
+            distance: 10.0,
+            draw_list: GizmoDrawList::Window,
+            drag: None,
+        }
+    }
+
+    /// Sets the size of the cube, in pixels.
+    ///
+    /// Defaults to 100.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the distance from the camera to the point it orbits around.
+    ///
+    /// Defaults to 10.
+    pub fn distance(mut self, distance: f32) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Sets the draw list the cube is drawn into.
+    pub fn draw_list(mut self, draw_list: GizmoDrawList) -> Self {
+        self.draw_list = draw_list;
+        self
+    }
+
+    /// Draws the cube with its top-left corner at `position`, in screen coordinates, and
+    /// handles the mouse. Returns the new view matrix when the user orbited the camera or
+    /// clicked a face.
+    pub fn build(
+        &mut self,
+        ui: &Ui,
+        position: impl Into<MintVec2>,
+        view: impl Into<ColumnMatrix4<f32>>,
+    ) -> Option<ColumnMatrix4<f32>> {
+        let position: [f32; 2] = position.into().into();
+        let view: Mat4 = view.into().into();
+        let id = ui.new_id_str(&self.id).0;
+        let io = ui.io();
+        let mouse = io.mouse_pos;
+        let center = [position[0] + self.size * 0.5, position[1] + self.size * 0.5];
+        let faces = cube_faces(&view, center, self.size * 0.28);
+        let inside = distance(center, mouse) < self.size * 0.5;
+
+        let hovered_face = if self.drag.is_none()
+            && inside
+            && ui.is_window_hovered()
+            && !ui.is_any_item_active()
+        {
+            unsafe { sys::imgui_rs_SetHoveredID(id) };
+            if ui.is_mouse_clicked(MouseButton::Left) {
+                self.drag = Some(false);
+            }
+            faces
+                .iter()
+                .find(|face| point_in_quad(mouse, &face.corners))
+                .map(|face| face.axis)
+        } else {
+            None
+        };
+
+        let mut result = None;
+        match self.drag {
+            Some(moved) if ui.is_mouse_down(MouseButton::Left) => {
+                unsafe { sys::imgui_rs_SetActiveID(id) };
+                let delta = io.mouse_delta;
+                if moved || delta[0].abs() + delta[1].abs() > 0.0 {
+                    self.drag = Some(true);
+                    result = Some(orbit(
+                        &view,
+                        self.distance,
+                        delta[0] * 0.01,
+                        delta[1] * 0.01,
+                    ));
+                }
+            }
+            Some(moved) => {
+                if !moved {
+                    let clicked = faces
+                        .iter()
+                        .find(|face| point_in_quad(mouse, &face.corners))
+                        .map(|face| face.axis);
+                    if let Some(axis) = clicked {
+                        result = Some(face_view(&view, self.distance, axis));
+                    }
+                }
+                self.drag = None;
+                unsafe { sys::imgui_rs_ClearActiveID(id) };
+            }
+            None => {}
+        }
+
+        let draw_list = match self.draw_list {
+            GizmoDrawList::Window => ui.get_window_draw_list(),
+            GizmoDrawList::Background => ui.get_background_draw_list(),
+        };
+        let mut background = ui.style_color(StyleColor::FrameBg);
+        if inside || self.drag.is_some() {
+            background = ui.style_color(StyleColor::FrameBgHovered);
+        }
+        draw_list
+            .add_circle(center, self.size * 0.5, background)
+            .filled(true)
+            .build();
+        let shown = result.map_or(view, |m: ColumnMatrix4<f32>| m.into());
+        let faces = cube_faces(&shown, center, self.size * 0.28);
+        let text_color = ui.style_color(StyleColor::Text);
+        for face in &faces {
+            let (index, sign) = face.axis;
+            let mut color = AXIS_COLORS[index];
+            if hovered_face == Some(face.axis) {
+                color = HOVERED_COLOR;
+            } else if sign < 0.0 {
+                color = [color[0] * 0.6, color[1] * 0.6, color[2] * 0.6, 1.0];
+            }
+            draw_list
+                .add_polyline(face.corners.to_vec(), color)
+                .filled(true)
+                .build();
+            draw_list
+                .add_polyline(face.corners.to_vec(), [0.0, 0.0, 0.0, 0.5])
+                .thickness(1.0)
+                .build();
+            let label = match face.axis {
+                (0, s) if s > 0.0 => "X",
+                (1, s) if s > 0.0 => "Y",
+                (2, s) if s > 0.0 => "Z",
+                (0, _) => "-X",
+                (1, _) => "-Y",
+                _ => "-Z",
+            };
+            let face_center = [
+                face.corners.iter().map(|c| c[0]).sum::<f32>() * 0.25,
+                face.corners.iter().map(|c| c[1]).sum::<f32>() * 0.25,
+            ];
+            let text_size = ui.calc_text_size(label);
+            draw_list.add_text(
+                [
+                    face_center[0] - text_size[0] * 0.5,
+                    face_center[1] - text_size[1] * 0.5,
+                ],
+                text_color,
+                label,
+            );
+        }
+        result
+    }
+}
+
+struct CubeFace {
+    /// The index of the axis the face is normal to, and the sign of the normal.
+    axis: (usize, f32),
+    corners: [[f32; 2]; 4],
+}
+
+/// Returns the faces of a cube centered on `center` which face the camera.
+fn cube_faces(view: &Mat4, center: [f32; 2], half_size: f32) -> Vec<CubeFace> {
+    // Only the rotation of the view matters, and the cube is drawn orthographically
+    let to_view = |v: Vec3| {
+        [
+            view[0][0] * v[0] + view[1][0] * v[1] + view[2][0] * v[2],
+            view[0][1] * v[0] + view[1][1] * v[1] + view[2][1] * v[2],
+            view[0][2] * v[0] + view[1][2] * v[1] + view[2][2] * v[2],
+        ]
+    };
+    let mut faces = Vec::new();
+    for index in 0..3 {
+        for sign in [1.0, -1.0] {
+            let mut normal = [0.0; 3];
+            normal[index] = sign;
+            if to_view(normal)[2] <= 1e-3 {
+                continue;
+            }
+            let (u, v) = ((index + 1) % 3, (index + 2) % 3);
+            let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(a, b)| {
+                let mut p = normal;
+                p[u] = a;
+                p[v] = b;
+                let p = to_view(p);
+                [center[0] + p[0] * half_size, center[1] - p[1] * half_size]
+            });
+            faces.push(CubeFace {
+                axis: (index, sign),
+                corners,
+            });
+        }
+    }
+    faces
+}
+
+/// Returns the view looking at the orbit target from the side of the given face.
+fn face_view(view: &Mat4, distance: f32, (index, sign): (usize, f32)) -> ColumnMatrix4<f32> {
+    let (eye, forward, _) = camera_frame(view);
+    let target = add(eye, scale(forward, distance));
+    let mut normal = [0.0; 3];
+    normal[index] = sign;
+    let up = if index == 1 {
+        [0.0, 0.0, -sign]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    look_at(add(target, scale(normal, distance)), target, up).into()
+}
+
+/// Orbits the camera around the point `distance` in front of it, by `yaw` radians around the
+/// world Y axis and `pitch` radians around the camera's horizontal axis.
+fn orbit(view: &Mat4, distance: f32, yaw: f32, pitch: f32) -> ColumnMatrix4<f32> {
+    let (eye, forward, up) = camera_frame(view);
+    let target = add(eye, scale(forward, distance));
+    let yaw_rotation = rotation_about([0.0, 1.0, 0.0], -yaw);
+    let rotate = |m: &Mat4, v: Vec3| {
+        let r = transform(*m, [v[0], v[1], v[2], 0.0]);
+        [r[0], r[1], r[2]]
+    };
+    let offset = rotate(&yaw_rotation, sub(eye, target));
+    let up = rotate(&yaw_rotation, up);
+    let right = normalize(cross(up, offset));
+    let pitch_rotation = rotation_about(right, pitch);
+    let offset = rotate(&pitch_rotation, offset);
+    let up = rotate(&pitch_rotation, up);
+    look_at(add(target, offset), target, up).into()
+}
+
+/// Returns the position, forward and up directions of the camera of a view matrix.
+fn camera_frame(view: &Mat4) -> (Vec3, Vec3, Vec3) {
+    let inverse_view = inverse(*view).unwrap_or(IDENTITY);
+    let eye = [inverse_view[3][0], inverse_view[3][1], inverse_view[3][2]];
+    let forward = normalize([-view[0][2], -view[1][2], -view[2][2]]);
+    let up = normalize([view[0][1], view[1][1], view[2][1]]);
+    (eye, forward, up)
+}
+
+/// A right-handed view matrix looking from `eye` at `target`.
+fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+    let f = normalize(sub(target, eye));
+    let s = normalize(cross(f, up));
+    let u = cross(s, f);
+    [
+        [s[0], u[0], -f[0], 0.0],
+        [s[1], u[1], -f[1], 0.0],
+        [s[2], u[2], -f[2], 0.0],
+        [-dot(s, eye), -dot(u, eye), dot(f, eye), 1.0],
+    ]
+}
+
+fn circle_points(camera: &Camera, origin: Vec3, axis: Vec3, radius: f32) -> Vec<[f32; 2]> {
+    // Any vector not parallel to the axis gives a basis of the circle's plane
+    let helper = if axis[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+    let u = normalize(cross(axis, helper));
+    let v = cross(axis, u);
+    (0..=CIRCLE_SEGMENTS)
+        .filter_map(|i| {
+            let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            let p = add(
+                origin,
+                add(
+                    scale(u, angle.cos() * radius),
+                    scale(v, angle.sin() * radius),
+                ),
+            );
+            camera.project(p)
+        })
+        .collect()
+}
+
+/// Returns the parameter along the axis through `origin` of the point closest to the ray.
+fn closest_on_axis(origin: Vec3, axis: Vec3, ray_origin: Vec3, ray_direction: Vec3) -> Option<f32> {
+    let w = sub(origin, ray_origin);
+    let b = dot(axis, ray_direction);
+    let denominator = 1.0 - b * b;
+    if denominator.abs() < 1e-6 {
+        return None;
+    }
+    Some((b * dot(ray_direction, w) - dot(axis, w)) / denominator)
+}
+
+fn intersect_plane(
+    point: Vec3,
+    normal: Vec3,
+    ray_origin: Vec3,
+    ray_direction: Vec3,
+) -> Option<Vec3> {
+    let denominator = dot(normal, ray_direction);
+    if denominator.abs() < 1e-6 {
+        return None;
+    }
+    let t = dot(normal, sub(point, ray_origin)) / denominator;
+    Some(add(ray_origin, scale(ray_direction, t)))
+}
+
+fn point_in_quad(p: [f32; 2], quad: &[[f32; 2]; 4]) -> bool {
+    let mut sign = 0.0;
+    for i in 0..4 {
+        let (a, b) = (quad[i], quad[(i + 1) % 4]);
+        let cross = (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]);
+        if cross * sign < 0.0 {
+            return false;
+        }
+        if cross != 0.0 {
+            sign = cross;
+        }
+    }
+    true
+}
+
+/// Rounds `value` to a multiple of `increment`, unless `increment` is zero.
+fn snap(value: f32, increment: f32) -> f32 {
+    if increment > 0.0 {
+        (value / increment).round() * increment
+    } else {
+        value
+    }
+}
+
+fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+fn segment_distance(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    let ab = [b[0] - a[0], b[1] - a[1]];
+    let length_sq = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if length_sq > 0.0 {
+        (((p[0] - a[0]) * ab[0] + (p[1] - a[1]) * ab[1]) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    distance([a[0] + ab[0] * t, a[1] + ab[1] * t], p)
+}
+
+const IDENTITY: Mat4 = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: Vec3, s: f32) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: Vec3) -> Vec3 {
+    let length = dot(a, a).sqrt();
+    if length > 0.0 {
+        scale(a, 1.0 / length)
+    } else {
+        a
+    }
+}
+
+/// Multiplies two column-major matrices.
+fn mul(a: Mat4, b: Mat4) -> Mat4 {
+    let mut out = [[0.0; 4]; 4];
+    for (c, column) in out.iter_mut().enumerate() {
+        for (r, value) in column.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[k][r] * b[c][k]).sum();
+        }
+    }
+    out
+}
+
+fn transform(m: Mat4, v: [f32; 4]) -> [f32; 4] {
+    let mut out = [0.0; 4];
+    for (r, value) in out.iter_mut().enumerate() {
+        *value = (0..4).map(|c| m[c][r] * v[c]).sum();
+    }
+    out
+}
+
+fn unproject(inverse_view_proj: Mat4, ndc: [f32; 4]) -> Vec3 {
+    let p = transform(inverse_view_proj, ndc);
+    [p[0] / p[3], p[1] / p[3], p[2] / p[3]]
+}
+
+/// Inverts a matrix by Gauss-Jordan elimination, or returns `None` if it isn't invertible.
+fn inverse(m: Mat4) -> Option<Mat4> {
+    // Rows of [m | identity], with m transposed to work on rows
+    let mut rows = [[0.0f32; 8]; 4];
+    for (r, row) in rows.iter_mut().enumerate() {
+        for c in 0..4 {
+            row[c] = m[c][r];
+        }
+        row[4 + r] = 1.0;
+    }
+    for col in 0..4 {
+        let pivot = (col..4).max_by(|&a, &b| rows[a][col].abs().total_cmp(&rows[b][col].abs()))?;
+        if rows[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        rows.swap(col, pivot);
+        let inv = 1.0 / rows[col][col];
+        for v in rows[col].iter_mut() {
+            *v *= inv;
+        }
+        for r in 0..4 {
+            if r != col {
+                let factor = rows[r][col];
+                let pivot_row = rows[col];
+                for (v, p) in rows[r].iter_mut().zip(pivot_row) {
+                    *v -= factor * p;
+                }
+            }
+        }
+    }
+    let mut out = [[0.0; 4]; 4];
+    for (r, row) in rows.iter().enumerate() {
+        for c in 0..4 {
+            out[c][r] = row[4 + c];
+        }
+    }
+    Some(out)
+}
+
+/// A rotation of `angle` radians around a unit axis.
+fn rotation_about(axis: Vec3, angle: f32) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    let t = 1.0 - c;
+    let [x, y, z] = axis;
+    [
+        [t * x * x + c, t * x * y + s * z, t * x * z - s * y, 0.0],
+        [t * x * y - s * z, t * y * y + c, t * y * z + s * x, 0.0],
+        [t * x * z + s * y, t * y * z - s * x, t * z * z + c, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Condition;
+
+    fn assert_close(a: Vec3, b: Vec3) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_view_math() {
+        let view = look_at([1.0, 2.0, 3.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        let product = mul(view, inverse(view).unwrap());
+        for (c, column) in product.iter().enumerate() {
+            for (r, value) in column.iter().enumerate() {
+                let expected = if c == r { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-5);
+            }
+        }
+        let (eye, forward, _) = camera_frame(&view);
+        assert_close(eye, [1.0, 2.0, 3.0]);
+        assert_close(forward, normalize([-1.0, -2.0, -3.0]));
+
+        // Clicking the +X face looks at the same target from the +X side
+        let faced: Mat4 = face_view(&IDENTITY, 10.0, (0, 1.0)).into();
+        let (eye, forward, up) = camera_frame(&faced);
+        assert_close(eye, [10.0, 0.0, -10.0]);
+        assert_close(forward, [-1.0, 0.0, 0.0]);
+        assert_close(up, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_translate_along_axis() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        // Only the translation increment applies to translations
+        let mut gizmo = Gizmo::new("gizmo")
+            .translate_snap(0.5)
+            .rotate_snap(15.0)
+            .scale_snap(0.25);
+        gizmo.set_viewport([0.0, 0.0], [600.0, 400.0]);
+        // An orthographic projection where one unit along X is 30 pixels
+        let projection = [
+            [0.1, 0.0, 0.0, 0.0],
+            [0.0, 0.1, 0.0, 0.0],
+            [0.0, 0.0, -0.01, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let mut model = IDENTITY;
+        let mut run = |ctx: &mut crate::Context, model: &mut Mat4, mouse, down| {
+            ctx.io_mut().add_mouse_pos_event(mouse);
+            ctx.io_mut().add_mouse_button_event(MouseButton::Left, down);
+            let ui = ctx.frame();
+            let mut changed = false;
+            ui.window("Viewport")
+                .position([0.0, 0.0], Condition::Always)
+                .size([600.0, 400.0], Condition::Always)
+                .build(|| {
+                    if let Some(edited) = gizmo.manipulate(ui, IDENTITY, projection, *model) {
+                        *model = edited.into();
+                        changed = true;
+                    }
+                });
+            ctx.render();
+            changed
+        };
+
+        // Hover the X arrow, 100 pixels right of the origin, and drag it 70 pixels further
+        run(&mut ctx, &mut model, [400.0, 200.0], false);
+        run(&mut ctx, &mut model, [400.0, 200.0], false);
+        assert!(!run(&mut ctx, &mut model, [400.0, 200.0], true));
+        assert!(run(&mut ctx, &mut model, [470.0, 200.0], true));
+        assert!((model[3][0] - 2.5).abs() < 1e-3);
+        assert_eq!([model[3][1], model[3][2]], [0.0, 0.0]);
+        run(&mut ctx, &mut model, [470.0, 200.0], false);
+        assert!(!gizmo.is_using());
+    }
+}
@@ -1,3 +1,8 @@
+use std::ffi::CStr;
+use std::fmt;
+
+use bitflags::bitflags;
+
 use crate::sys;
 use crate::Ui;
 
@@ -326,6 +331,103 @@ impl Key {
     pub const COUNT: usize = sys::ImGuiKey_NamedKey_COUNT as usize;
 }
 
+bitflags! {
+    /// Modifier keys held as part of a [KeyChord]
+    #[repr(transparent)]
+    pub struct KeyMods: u32 {
+        const CTRL = sys::ImGuiMod_Ctrl;
+        const SHIFT = sys::ImGuiMod_Shift;
+        const ALT = sys::ImGuiMod_Alt;
+        /// Cmd on macOS, the Windows key elsewhere
+        const SUPER = sys::ImGuiMod_Super;
+    }
+}
+
+/// A key pressed while holding modifier keys, e.g. `Ctrl+Shift+S`.
+///
+/// The chord is only pressed if exactly these modifiers are held. It is displayed the way
+/// Dear ImGui's `GetKeyChordName` names it, e.g. `Ctrl+LeftArrow`, or with the same modifier
+/// and key names when there is no current context.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct KeyChord {
+    /// The modifiers held while pressing the key
+    pub mods: KeyMods,
+    /// The key pressed
+    pub key: Key,
+}
+
+impl KeyChord {
+    /// Creates a chord of `key` with the given modifiers.
+    pub const fn new(mods: KeyMods, key: Key) -> Self {
+        KeyChord { mods, key }
+    }
+
+    /// Returns the chord as an `ImGuiKeyChord`.
+    pub fn raw(self) -> sys::ImGuiKeyChord {
+        (self.mods.bits() | self.key as u32) as sys::ImGuiKeyChord
+    }
+}
+
+impl From<Key> for KeyChord {
+    fn from(key: Key) -> Self {
+        KeyChord::new(KeyMods::empty(), key)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `GetKeyChordName` formats into a buffer of the current context
+        if !unsafe { sys::igGetCurrentContext() }.is_null() {
+            let name = unsafe { CStr::from_ptr(sys::imgui_rs_GetKeyChordName(self.raw())) };
+            return f.write_str(&name.to_string_lossy());
+        }
+        for (modifier, name) in [
+            (KeyMods::CTRL, "Ctrl+"),
+            (KeyMods::SHIFT, "Shift+"),
+            (KeyMods::ALT, "Alt+"),
+            (KeyMods::SUPER, "Super+"),
+        ] {
+            if self.mods.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        // The same names as `GetKeyName`, which strips the prefix of the digit keys
+        let name = format!("{:?}", self.key);
+        match name.strip_prefix("Alpha") {
+            Some(digit) => f.write_str(digit),
+            None => f.write_str(&name),
+        }
+    }
+}
+
+bitflags! {
+    /// Options for [`Ui::shortcut`], which decide who receives a shortcut when several places
+    /// in the UI use it.
+    #[repr(transparent)]
+    pub struct ShortcutFlags: u32 {
+        /// Keep returning true while the chord is held, at the key repeat rate
+        const REPEAT = sys::ImGuiInputFlags_Repeat;
+        /// Only the active item receives the shortcut
+        const ROUTE_ACTIVE = sys::ImGuiInputFlags_RouteActive;
+        /// The focused window and its parents receive the shortcut, unless the active item
+        /// uses it. This is the default
+        const ROUTE_FOCUSED = sys::ImGuiInputFlags_RouteFocused;
+        /// Received anywhere, unless the focused window or the active item uses it
+        const ROUTE_GLOBAL = sys::ImGuiInputFlags_RouteGlobal;
+        /// Always received, without registering a route
+        const ROUTE_ALWAYS = sys::ImGuiInputFlags_RouteAlways;
+        /// With `ROUTE_GLOBAL`: take precedence over the focused window
+        const ROUTE_OVER_FOCUSED = sys::ImGuiInputFlags_RouteOverFocused;
+        /// With `ROUTE_GLOBAL`: take precedence over the active item
+        const ROUTE_OVER_ACTIVE = sys::ImGuiInputFlags_RouteOverActive;
+        /// With `ROUTE_GLOBAL`: ignore the shortcut when the background is focused, so it
+        /// goes to the application rather than the UI
+        const ROUTE_UNLESS_BG_FOCUSED = sys::ImGuiInputFlags_RouteUnlessBgFocused;
+        /// With `ROUTE_FOCUSED`: route from the root window rather than the current window
+        const ROUTE_FROM_ROOT_WINDOW = sys::ImGuiInputFlags_RouteFromRootWindow;
+    }
+}
+
 /// Target widget selection for keyboard focus
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum FocusedWidget {
@@ -418,6 +520,42 @@ impl Ui {
         unsafe { sys::igGetKeyPressedAmount(key as u32, repeat_delay, rate) as u32 }
     }
 
+    /// Returns true if the key of the chord was pressed while holding exactly its modifiers.
+    ///
+    /// Unlike [shortcut](Self::shortcut) this doesn't check whether other parts of the UI use
+    /// the chord, e.g. a focused text field receiving `Ctrl+A`.
+    #[inline]
+    #[doc(alias = "IsKeyChordPressed")]
+    pub fn is_key_chord_pressed(&self, chord: impl Into<KeyChord>) -> bool {
+        let chord = chord.into().raw();
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "docking")] {
+                unsafe { sys::igIsKeyChordPressed_Nil(chord) }
+            } else {
+                unsafe { sys::igIsKeyChordPressed(chord) }
+            }
+        }
+    }
+
+    /// Returns true if the chord was pressed and this call site receives it, according to the
+    /// routing in `flags`. Without a route, the current focused window receives it.
+    ///
+    /// Routes are resolved over frames, so this should be called every frame, not only when a
+    /// key is pressed.
+    #[inline]
+    #[doc(alias = "Shortcut")]
+    pub fn shortcut(&self, chord: impl Into<KeyChord>, flags: ShortcutFlags) -> bool {
+        let chord = chord.into().raw();
+        let flags = flags.bits() as sys::ImGuiInputFlags;
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "docking")] {
+                unsafe { sys::igShortcut_Nil(chord, flags) }
+            } else {
+                unsafe { sys::igShortcut(chord, flags) }
+            }
+        }
+    }
+
     /// Focuses keyboard on the next widget.
     ///
     /// This is the equivalent to [set_keyboard_focus_here_with_offset](Self::set_keyboard_focus_here_with_offset)
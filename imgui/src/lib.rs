@@ -93,26 +93,35 @@ use std::os::raw::c_char;
 pub use self::clipboard::*;
 pub use self::color::ImColor32;
 pub use self::context::*;
+#[cfg(feature = "tables-api")]
+pub use self::data_grid::*;
+pub use self::dialogs::*;
 #[cfg(feature = "docking")]
 pub use self::docking_utils::*;
 pub use self::drag_drop::{DragDropFlags, DragDropSource, DragDropTarget};
 pub use self::draw_list::{ChannelsSplit, DrawListMut};
+#[cfg(feature = "tables-api")]
+pub use self::file_browser::*;
 pub use self::fonts::atlas::*;
 pub use self::fonts::font::*;
 pub use self::fonts::glyph::*;
 pub use self::fonts::glyph_ranges::*;
+pub use self::gizmo::*;
 pub use self::input::keyboard::*;
 pub use self::input::mouse::*;
 pub use self::input_widget::*;
 pub use self::io::*;
 pub use self::layout::*;
-pub use self::list_clipper::ListClipper;
+pub use self::list_clipper::{ListClipper, ListClipperToken, VariableListClipper};
+pub use self::node_editor::*;
 pub use self::platform_io::*;
+pub use self::plot::*;
 pub use self::plothistogram::PlotHistogram;
 pub use self::plotlines::PlotLines;
 pub use self::popups::*;
 pub use self::render::draw_data::*;
 pub use self::render::renderer::*;
+pub use self::reorderable_list::*;
 pub use self::stacks::*;
 pub use self::string::*;
 pub use self::style::*;
@@ -120,19 +129,28 @@ pub use self::style::*;
 #[cfg(feature = "tables-api")]
 pub use self::tables::*;
 pub use self::text_filter::*;
+pub use self::timeline::*;
+pub use self::toasts::*;
+pub use self::tooltips::*;
 pub use self::utils::*;
+pub use self::widget::code_editor::*;
 pub use self::widget::color_editors::*;
 pub use self::widget::combo_box::*;
+pub use self::widget::command_palette::*;
 pub use self::widget::drag::*;
 pub use self::widget::image::*;
 pub use self::widget::list_box::*;
+pub use self::widget::memory_editor::*;
 pub use self::widget::menu::*;
+pub use self::widget::menu_model::*;
 pub use self::widget::misc::*;
 pub use self::widget::progress_bar::*;
+pub use self::widget::searchable_combo::*;
 pub use self::widget::selectable::*;
 pub use self::widget::slider::*;
 pub use self::widget::tab::*;
 pub use self::widget::tree::*;
+pub use self::widget::tree_view::*;
 pub use self::window::child_window::*;
 pub use self::window::*;
 use internal::RawCast;
@@ -148,14 +166,21 @@ mod clipboard;
 pub mod color;
 mod columns;
 mod context;
+#[cfg(feature = "tables-api")]
+mod data_grid;
+mod dialogs;
 #[cfg(feature = "docking")]
 mod dock_space;
 #[cfg(feature = "docking")]
 mod docking_utils;
+mod dpi_scaling;
 
 pub mod drag_drop;
 pub mod draw_list;
+#[cfg(feature = "tables-api")]
+mod file_browser;
 mod fonts;
+mod gizmo;
 mod input;
 mod input_widget;
 pub mod internal;
@@ -163,11 +188,14 @@ mod io;
 mod layout;
 mod list_clipper;
 mod math;
+mod node_editor;
 mod platform_io;
+mod plot;
 mod plothistogram;
 mod plotlines;
 mod popups;
 mod render;
+mod reorderable_list;
 mod stacks;
 mod style;
 #[cfg(feature = "tables-api")]
@@ -175,7 +203,12 @@ mod tables;
 #[cfg(test)]
 mod test;
 pub mod text_filter;
+mod text_match;
+mod timeline;
+mod toasts;
+mod tooltips;
 mod utils;
+mod value_format;
 mod widget;
 mod window;
 
@@ -215,6 +248,8 @@ impl Context {
 pub struct Ui {
     /// our scratch sheet
     buffer: cell::UnsafeCell<string::UiBuffer>,
+    /// payloads of drags started with `DragDropSource::begin_owned_payload`
+    owned_payloads: cell::RefCell<drag_drop::OwnedPayloads>,
 }
 
 impl Ui {
@@ -409,6 +444,16 @@ impl Ui {
         Id(value)
     }
 
+    /// Create [`Id`] from a `u64`, hashing all of its bits even where `usize` is 32 bits.
+    pub(crate) fn new_id_u64(&self, input: u64) -> Id {
+        let bytes = input.to_le_bytes();
+        let value = unsafe {
+            let start = bytes.as_ptr() as *const std::os::raw::c_char;
+            sys::igGetID_StrStr(start, start.add(bytes.len()))
+        };
+        Id(value)
+    }
+
     /// Create [`Id`] from i32
     pub fn new_id_int(&self, input: i32) -> Id {
         let p = input as *const std::os::raw::c_void;
@@ -669,7 +714,9 @@ create_token!(
 impl Ui {
     /// Construct a tooltip window that can have any kind of content.
     ///
-    /// Typically used with `Ui::is_item_hovered()` or some other conditional check.
+    /// Typically used with `Ui::is_item_hovered()` or some other conditional check. For the
+    /// tooltip of the last item, [`item_tooltip`](Self::item_tooltip) also waits for the hover
+    /// delays of the style.
     ///
     /// # Examples
     ///
@@ -890,6 +937,30 @@ impl<'ui> Ui {
         PlotHistogram::new(self, label, values)
     }
 
+    /// Plot `values_count` floats, read by calling `values_getter` with their index, as a
+    /// "sparkline" style plot
+    #[doc(alias = "PlotLines")]
+    pub fn plot_lines_from_fn<'p, Label: AsRef<str>, Getter: Fn(usize) -> f32>(
+        &'ui self,
+        label: Label,
+        values_count: usize,
+        values_getter: Getter,
+    ) -> PlotLines<'ui, 'p, Label, &'static str, Getter> {
+        PlotLines::from_fn(self, label, values_count, values_getter)
+    }
+
+    /// Plot `values_count` floats, read by calling `values_getter` with their index, as a
+    /// histogram
+    #[doc(alias = "PlotHistogram")]
+    pub fn plot_histogram_from_fn<'p, Label: AsRef<str>, Getter: Fn(usize) -> f32>(
+        &'ui self,
+        label: Label,
+        values_count: usize,
+        values_getter: Getter,
+    ) -> PlotHistogram<'ui, 'p, Label, &'static str, Getter> {
+        PlotHistogram::from_fn(self, label, values_count, values_getter)
+    }
+
     /// Calculate the size required for a given text string.
     ///
     /// This is the same as [calc_text_size_with_opts](Self::calc_text_size_with_opts)
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::ops::Range;
 
 use crate::sys;
 use crate::Ui;
@@ -13,8 +13,9 @@ use crate::Ui;
 /// call `ui.checkbox(...)` for the currently visible items.
 ///
 /// Note the efficiency of list clipper relies on the height
-/// of each item being cheaply calculated. The current rust
-/// bindings only works with a fixed height for all items.
+/// of each item being cheaply calculated. This works with a fixed
+/// height for all items, see [VariableListClipper] for items of
+/// varying heights.
 pub struct ListClipper {
     items_count: i32,
     items_height: f32,
@@ -52,7 +53,9 @@ impl ListClipper {
 /// clipper will help you only draw the ones which are visible.
 pub struct ListClipperToken<'ui> {
     list_clipper: *mut sys::ImGuiListClipper,
-    _phantom: PhantomData<&'ui Ui>,
+    ui: &'ui Ui,
+    /// Item to scroll to, and its center ratio, once the item height is known.
+    scroll_to: Option<(i32, f32)>,
 
     /// In upstream imgui < 1.87, calling step too many times will
     /// cause a segfault due to null pointer. So we keep track of this
@@ -65,14 +68,67 @@ pub struct ListClipperToken<'ui> {
 }
 
 impl<'ui> ListClipperToken<'ui> {
-    fn new(_: &Ui, list_clipper: *mut sys::ImGuiListClipper) -> Self {
+    fn new(ui: &'ui Ui, list_clipper: *mut sys::ImGuiListClipper) -> Self {
         Self {
             list_clipper,
-            _phantom: PhantomData,
+            ui,
+            scroll_to: None,
             consumed_workaround: false,
         }
     }
 
+    /// Forces the given item to be displayed even if it isn't visible, e.g. because it has
+    /// keyboard focus. Must be called before the first call to `step`.
+    #[doc(alias = "IncludeItemByIndex")]
+    pub fn include_item_by_index(&mut self, item_index: i32) {
+        unsafe { sys::ImGuiListClipper_IncludeItemByIndex(self.list_clipper, item_index) }
+    }
+
+    /// Forces the given range of items to be displayed even if they aren't visible. Must be
+    /// called before the first call to `step`.
+    #[doc(alias = "IncludeItemsByIndex")]
+    pub fn include_items_by_range(&mut self, items: Range<i32>) {
+        unsafe {
+            sys::ImGuiListClipper_IncludeItemsByIndex(self.list_clipper, items.start, items.end)
+        }
+    }
+
+    /// Moves the cursor to the position of the given item, which must have been displayed
+    /// by the current step.
+    #[doc(alias = "SeekCursorForItem")]
+    pub fn seek_cursor_for_item(&mut self, item_index: i32) {
+        unsafe { sys::ImGuiListClipper_SeekCursorForItem(self.list_clipper, item_index) }
+    }
+
+    /// Scrolls the window so the given item is visible, with `center_y_ratio` placing it at
+    /// the top (`0.0`), center (`0.5`) or bottom (`1.0`). Must be called before the first call
+    /// to `step`.
+    ///
+    /// The item is included like with [include_item_by_index](Self::include_item_by_index),
+    /// and the scroll is applied once the clipper knows the height of the items.
+    pub fn scroll_to_item(&mut self, item_index: i32, center_y_ratio: f32) {
+        self.include_item_by_index(item_index);
+        self.scroll_to = Some((item_index, center_y_ratio));
+    }
+
+    fn apply_scroll_to(&mut self) {
+        let Some((item_index, center_y_ratio)) = self.scroll_to else {
+            return;
+        };
+        let clipper = unsafe { &*self.list_clipper };
+        if clipper.ItemsHeight <= 0.0 {
+            return;
+        }
+        // the same position ImGuiListClipper uses to seek to an item
+        let item_y = clipper.StartPosY as f64
+            + clipper.StartSeekOffsetY
+            + (item_index as f64 + center_y_ratio as f64) * clipper.ItemsHeight as f64;
+        let local_y = item_y as f32 - self.ui.window_pos()[1];
+        self.ui
+            .set_scroll_from_pos_y_with_ratio(local_y, center_y_ratio);
+        self.scroll_to = None;
+    }
+
     /// Progress the list clipper.
     ///
     /// If this returns returns `true` then the you can loop between
@@ -86,7 +142,11 @@ impl<'ui> ListClipperToken<'ui> {
     pub fn step(&mut self) -> bool {
         let is_imgui_1_88_or_higher = false;
         if is_imgui_1_88_or_higher {
-            unsafe { sys::ImGuiListClipper_Step(self.list_clipper) }
+            let ret = unsafe { sys::ImGuiListClipper_Step(self.list_clipper) };
+            if ret {
+                self.apply_scroll_to();
+            }
+            ret
         } else {
             if self.consumed_workaround {
                 panic!("ListClipperToken::step called after it has previously returned false");
@@ -94,6 +154,8 @@ impl<'ui> ListClipperToken<'ui> {
             let ret = unsafe { sys::ImGuiListClipper_Step(self.list_clipper) };
             if !ret {
                 self.consumed_workaround = true;
+            } else {
+                self.apply_scroll_to();
             }
             ret
         }
@@ -202,6 +264,173 @@ impl Iterator for ListClipperIterator<'_> {
     }
 }
 
+/// Used to render only the visible items of a long list whose items have different heights,
+/// such as multi-line log entries.
+///
+/// Unlike [ListClipper], this is kept between frames: it measures the height of every item it
+/// displays, and uses an estimated height for the items it hasn't displayed yet. If the heights
+/// of items change, e.g. because the window was resized and text wraps differently, call
+/// [invalidate_heights](Self::invalidate_heights).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let lines = vec!["single line".to_string(), "two\nlines".to_string()];
+/// let mut clipper = VariableListClipper::new();
+///
+/// // e.g. when jumping to a line
+/// clipper.scroll_to_item(1, 0.5);
+///
+/// clipper.build(ui, lines.len(), |index| {
+///     ui.text(&lines[index]);
+/// });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct VariableListClipper {
+    estimated_height: f32,
+    /// Measured heights, including item spacing, or a negative value if not measured yet.
+    heights: Vec<f32>,
+    /// The top of each item relative to the start of the list, and the total height at the end.
+    offsets: Vec<f32>,
+    /// The number of leading `offsets` which are up to date.
+    offsets_valid: usize,
+    included: Vec<Range<usize>>,
+    scroll_to: Option<(usize, f32)>,
+}
+
+impl VariableListClipper {
+    /// Creates a clipper which estimates unmeasured items to be a line of text high.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the height used for items which haven't been measured yet. Defaults to
+    /// [text_line_height_with_spacing](Ui::text_line_height_with_spacing).
+    pub fn estimated_item_height(mut self, estimated_height: f32) -> Self {
+        self.estimated_height = estimated_height;
+        self
+    }
+
+    /// Forces the given item to be displayed on the next [build](Self::build) even if it isn't
+    /// visible, e.g. because it has keyboard focus.
+    pub fn include_item_by_index(&mut self, item_index: usize) {
+        self.include_items_by_range(item_index..item_index + 1);
+    }
+
+    /// Forces the given range of items to be displayed on the next [build](Self::build) even if
+    /// they aren't visible.
+    pub fn include_items_by_range(&mut self, items: Range<usize>) {
+        self.included.push(items);
+    }
+
+    /// Scrolls the window on the next [build](Self::build) so the given item is visible, with
+    /// `center_y_ratio` placing it at the top (`0.0`), center (`0.5`) or bottom (`1.0`).
+    pub fn scroll_to_item(&mut self, item_index: usize, center_y_ratio: f32) {
+        self.include_item_by_index(item_index);
+        self.scroll_to = Some((item_index, center_y_ratio));
+    }
+
+    /// Forgets all measured heights, so items are measured again when displayed.
+    pub fn invalidate_heights(&mut self) {
+        self.heights.iter_mut().for_each(|height| *height = -1.0);
+        self.offsets_valid = 0;
+    }
+
+    /// Returns the measured height of the given item, including item spacing, or `None` if it
+    /// hasn't been displayed yet.
+    pub fn item_height(&self, item_index: usize) -> Option<f32> {
+        self.heights
+            .get(item_index)
+            .copied()
+            .filter(|&height| height >= 0.0)
+    }
+
+    /// Displays the list at the cursor, calling `draw_item` for every visible or included item.
+    pub fn build(&mut self, ui: &Ui, items_count: usize, mut draw_item: impl FnMut(usize)) {
+        let estimated_height = if self.estimated_height > 0.0 {
+            self.estimated_height
+        } else {
+            ui.text_line_height_with_spacing()
+        };
+        self.heights.resize(items_count, -1.0);
+        self.offsets_valid = self.offsets_valid.min(items_count + 1);
+        self.update_offsets(estimated_height);
+
+        let [x, start_y] = ui.cursor_pos();
+        let scroll_y = ui.scroll_y();
+        let visible = self.item_at(scroll_y - start_y)
+            ..(self.item_at(scroll_y + ui.window_size()[1] - start_y) + 1).min(items_count);
+
+        if let Some((item_index, center_y_ratio)) = self.scroll_to.take() {
+            if item_index < items_count {
+                let height = self.heights[item_index];
+                let height = if height < 0.0 {
+                    estimated_height
+                } else {
+                    height
+                };
+                let item_y = start_y + self.offsets[item_index] + height * center_y_ratio;
+                ui.set_scroll_from_pos_y_with_ratio(item_y - scroll_y, center_y_ratio);
+            }
+        }
+
+        // draw the visible and included items, merging overlapping ranges
+        let mut ranges = std::mem::take(&mut self.included);
+        ranges.push(visible);
+        ranges.retain(|range| range.start < range.end.min(items_count));
+        ranges.sort_by_key(|range| range.start);
+        let mut next = 0;
+        for range in ranges {
+            let range = range.start.max(next)..range.end.min(items_count);
+            if range.is_empty() {
+                continue;
+            }
+            self.update_offsets(estimated_height);
+            ui.set_cursor_pos([x, start_y + self.offsets[range.start]]);
+            for item_index in range.clone() {
+                let item_start = ui.cursor_pos()[1];
+                draw_item(item_index);
+                let height = ui.cursor_pos()[1] - item_start;
+                if height != self.heights[item_index] {
+                    self.heights[item_index] = height;
+                    self.offsets_valid = self.offsets_valid.min(item_index + 1);
+                }
+            }
+            next = range.end;
+        }
+
+        // leave the cursor after the list, submitting an item so the window grows to fit it
+        self.update_offsets(estimated_height);
+        let spacing_y = unsafe { ui.style() }.item_spacing[1];
+        ui.set_cursor_pos([x, start_y + self.offsets[items_count] - spacing_y]);
+        ui.dummy([0.0, 0.0]);
+    }
+
+    fn update_offsets(&mut self, estimated_height: f32) {
+        self.offsets.resize(self.heights.len() + 1, 0.0);
+        for item_index in self.offsets_valid.max(1)..self.offsets.len() {
+            let height = self.heights[item_index - 1];
+            let height = if height < 0.0 {
+                estimated_height
+            } else {
+                height
+            };
+            self.offsets[item_index] = self.offsets[item_index - 1] + height;
+        }
+        self.offsets_valid = self.offsets.len();
+    }
+
+    /// Returns the index of the item at the given offset from the start of the list.
+    fn item_at(&self, offset: f32) -> usize {
+        self.offsets
+            .partition_point(|&item_offset| item_offset <= offset)
+            .saturating_sub(1)
+    }
+}
+
 #[test]
 fn cpp_style_usage() {
     // Setup
@@ -268,3 +497,75 @@ fn iterator_usage() {
     // Should be consistent with size in `cpp_style_usage`
     assert_eq!(ticks, 44);
 }
+
+#[test]
+fn include_and_scroll_to_item() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+
+    let mut rows = Vec::new();
+    let mut scroll = 0.0;
+    let mut line_height = 0.0;
+    for frame in 0..2 {
+        let ui = ctx.frame();
+        ui.window("Example")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([100.0, 800.0], crate::Condition::Always)
+            .build(|| {
+                let mut tok = ListClipper::new(1000).begin(ui);
+                if frame == 0 {
+                    tok.include_item_by_index(900);
+                    tok.scroll_to_item(500, 0.0);
+                }
+                rows = tok.iter().inspect(|_| ui.text("...")).collect();
+                scroll = ui.scroll_y();
+                line_height = ui.text_line_height_with_spacing();
+            });
+        ctx.render();
+    }
+
+    // the scroll is applied on the frame after it was requested
+    assert!(rows.contains(&500));
+    assert!(!rows.contains(&900));
+    assert!((scroll - 500.0 * line_height).abs() < line_height);
+}
+
+#[test]
+fn variable_heights() {
+    let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+    let mut clipper = VariableListClipper::new();
+    let draw = |ui: &Ui, clipper: &mut VariableListClipper, drawn: &mut Vec<usize>| {
+        let _window = ui
+            .window("Example")
+            .position([0.0, 0.0], crate::Condition::Always)
+            .size([100.0, 400.0], crate::Condition::Always)
+            .begin();
+        clipper.build(ui, 1000, |index| {
+            drawn.push(index);
+            // every other item is two lines high
+            ui.text(if index % 2 == 0 { "a" } else { "a\nb" });
+        });
+    };
+
+    let mut drawn = Vec::new();
+    draw(ctx.frame(), &mut clipper, &mut drawn);
+    ctx.render();
+    assert!(drawn.len() < 50);
+    assert!(clipper.item_height(1).unwrap() > clipper.item_height(0).unwrap());
+    assert_eq!(clipper.item_height(999), None);
+
+    drawn.clear();
+    clipper.include_item_by_index(999);
+    draw(ctx.frame(), &mut clipper, &mut drawn);
+    ctx.render();
+    assert_eq!(drawn.last(), Some(&999));
+    assert!(clipper.item_height(999).is_some());
+
+    clipper.scroll_to_item(600, 0.0);
+    draw(ctx.frame(), &mut clipper, &mut drawn);
+    ctx.render();
+    drawn.clear();
+    draw(ctx.frame(), &mut clipper, &mut drawn);
+    ctx.render();
+    assert!(drawn.contains(&600));
+    assert!(drawn[0] >= 595);
+}
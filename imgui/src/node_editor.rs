@@ -0,0 +1,1160 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::math::MintVec2;
+use crate::{ButtonFlags, DragDropFlags, Key, MouseButton, Ui};
+
+const PIN_PAYLOAD: &str = "imgui-rs-node-pin";
+const MIN_ZOOM: f32 = 0.2;
+const MAX_ZOOM: f32 = 3.0;
+
+/// Identifies a node of a [NodeGraph]. It must stay the same across frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(pub u64);
+
+/// Identifies a pin of a [NodeGraph]. It must stay the same across frames, and be unique
+/// among the pins of all nodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PinId(pub u64);
+
+/// Identifies a link of a [NodeGraph]. It must stay the same across frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LinkId(pub u64);
+
+/// Whether a pin is on the input (left) or the output (right) side of its node.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PinKind {
+    Input,
+    Output,
+}
+
+/// A change requested by the user in a [NodeEditor], as returned by [NodeEditor::build].
+///
+/// The node editor doesn't own the graph, so nothing changes until the application applies
+/// the event to its own data and submits the updated graph on the next frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NodeEditorEvent {
+    /// A link was dragged from the output pin `from` to the input pin `to`.
+    LinkCreated { from: PinId, to: PinId },
+    /// A link dragged from `pin` was dropped on the empty canvas at `position`, in graph
+    /// coordinates. This is typically used to offer creating a new node there.
+    LinkDropped { pin: PinId, position: [f32; 2] },
+    /// A link was deleted.
+    LinkDeleted(LinkId),
+    /// A node was dragged to `position`. This is sent every frame while dragging.
+    NodeMoved { node: NodeId, position: [f32; 2] },
+    /// A node was deleted.
+    NodeDeleted(NodeId),
+}
+
+/// The colors and sizes of a [NodeEditor]. Sizes are in graph units, which are pixels at a
+/// zoom of 1.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NodeEditorStyle {
+    pub grid_spacing: f32,
+    pub node_rounding: f32,
+    pub pin_radius: f32,
+    pub link_thickness: f32,
+    pub background: [f32; 4],
+    pub grid: [f32; 4],
+    pub node_background: [f32; 4],
+    pub node_title: [f32; 4],
+    pub node_border: [f32; 4],
+    pub selected: [f32; 4],
+    pub text: [f32; 4],
+    pub pin: [f32; 4],
+    pub pin_hovered: [f32; 4],
+    pub link: [f32; 4],
+    pub link_hovered: [f32; 4],
+    pub selection_rect: [f32; 4],
+    pub minimap_background: [f32; 4],
+}
+
+impl Default for NodeEditorStyle {
+    fn default() -> Self {
+        NodeEditorStyle {
+            grid_spacing: 32.0,
+            node_rounding: 4.0,
+            pin_radius: 4.5,
+            link_thickness: 2.5,
+            background: [0.16, 0.16, 0.18, 1.0],
+            grid: [0.78, 0.78, 0.78, 0.1],
+            node_background: [0.24, 0.24, 0.27, 0.95],
+            node_title: [0.16, 0.29, 0.48, 1.0],
+            node_border: [0.39, 0.39, 0.39, 1.0],
+            selected: [1.0, 0.69, 0.25, 1.0],
+            text: [0.95, 0.95, 0.95, 1.0],
+            pin: [0.6, 0.6, 0.6, 1.0],
+            pin_hovered: [0.95, 0.95, 0.95, 1.0],
+            link: [0.5, 0.6, 0.8, 1.0],
+            link_hovered: [0.7, 0.8, 1.0, 1.0],
+            selection_rect: [0.26, 0.59, 0.98, 0.3],
+            minimap_background: [0.1, 0.1, 0.1, 0.8],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PinData {
+    id: PinId,
+    kind: PinKind,
+    label: String,
+}
+
+#[derive(Clone, Debug)]
+struct NodeData {
+    id: NodeId,
+    position: [f32; 2],
+    title: String,
+    color: Option<[f32; 4]>,
+    pins: Vec<PinData>,
+}
+
+#[derive(Clone, Debug)]
+struct LinkData {
+    id: LinkId,
+    from: PinId,
+    to: PinId,
+    color: Option<[f32; 4]>,
+}
+
+/// The nodes and links submitted to a [NodeEditor] for one frame, in [NodeEditor::build].
+#[derive(Clone, Debug, Default)]
+pub struct NodeGraph {
+    nodes: Vec<NodeData>,
+    links: Vec<LinkData>,
+}
+
+impl NodeGraph {
+    /// Starts adding a node whose top-left corner is at `position`, in graph coordinates.
+    /// The node must be added with [NodeBuilder::build].
+    pub fn node(&mut self, id: NodeId, position: [f32; 2]) -> NodeBuilder<'_> {
+        NodeBuilder {
+            graph: self,
+            node: NodeData {
+                id,
+                position,
+                title: String::new(),
+                color: None,
+                pins: Vec::new(),
+            },
+        }
+    }
+
+    /// Adds a link from the pin `from` to the pin `to`. Links whose pins weren't submitted
+    /// aren't drawn.
+    pub fn link(&mut self, id: LinkId, from: PinId, to: PinId) {
+        self.links.push(LinkData {
+            id,
+            from,
+            to,
+            color: None,
+        });
+    }
+
+    /// Adds a link with its own color.
+    pub fn link_with_color(
+        &mut self,
+        id: LinkId,
+        from: PinId,
+        to: PinId,
+        color: impl Into<[f32; 4]>,
+    ) {
+        self.links.push(LinkData {
+            id,
+            from,
+            to,
+            color: Some(color.into()),
+        });
+    }
+}
+
+/// Builder for a node of a [NodeGraph], created with [NodeGraph::node].
+#[must_use = "call .build() to add the node"]
+pub struct NodeBuilder<'g> {
+    graph: &'g mut NodeGraph,
+    node: NodeData,
+}
+
+impl NodeBuilder<'_> {
+    /// Sets the title shown in the title bar.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.node.title = title.into();
+        self
+    }
+
+    /// Sets the color of the title bar.
+    pub fn color(mut self, color: impl Into<[f32; 4]>) -> Self {
+        self.node.color = Some(color.into());
+        self
+    }
+
+    /// Adds an input pin, on the left side of the node.
+    pub fn input(mut self, id: PinId, label: impl Into<String>) -> Self {
+        self.node.pins.push(PinData {
+            id,
+            kind: PinKind::Input,
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Adds an output pin, on the right side of the node.
+    pub fn output(mut self, id: PinId, label: impl Into<String>) -> Self {
+        self.node.pins.push(PinData {
+            id,
+            kind: PinKind::Output,
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Adds the node to the graph.
+    pub fn build(self) {
+        self.graph.nodes.push(self.node);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Interaction {
+    None,
+    DragNodes,
+    /// Box selection started at this point, in graph coordinates.
+    BoxSelect([f32; 2]),
+    Pan,
+    Minimap,
+}
+
+/// The position and size of a node and its pins, in graph coordinates.
+#[derive(Clone, Debug)]
+struct NodeLayout {
+    /// Index of the node in [NodeGraph::nodes].
+    index: usize,
+    min: [f32; 2],
+    max: [f32; 2],
+    title_height: f32,
+    /// Center of each pin, in the order of [NodeData::pins].
+    pins: Vec<[f32; 2]>,
+}
+
+/// Maps graph coordinates to screen coordinates.
+#[derive(Copy, Clone, Debug)]
+struct View {
+    origin: [f32; 2],
+    pan: [f32; 2],
+    zoom: f32,
+}
+
+impl View {
+    fn to_screen(self, p: [f32; 2]) -> [f32; 2] {
+        [
+            self.origin[0] + self.pan[0] + p[0] * self.zoom,
+            self.origin[1] + self.pan[1] + p[1] * self.zoom,
+        ]
+    }
+
+    fn to_graph(self, p: [f32; 2]) -> [f32; 2] {
+        [
+            (p[0] - self.origin[0] - self.pan[0]) / self.zoom,
+            (p[1] - self.origin[1] - self.pan[1]) / self.zoom,
+        ]
+    }
+}
+
+/// A node graph editor: nodes with input and output pins connected by links, on a canvas
+/// which can be panned with the middle mouse button and zoomed with the mouse wheel.
+///
+/// The editor owns no application data. Every frame, the application submits its nodes, pins
+/// and links to [build](Self::build), and receives [NodeEditorEvent]s describing what the user
+/// did: links created by dragging from one pin to another, nodes moved, and selected nodes and
+/// links deleted with the Delete key. Nodes are selected by clicking them, holding Ctrl or
+/// Shift to add to the selection, or by dragging a box on the canvas.
+///
+/// The editor only keeps view state, such as the pan, zoom and selection, so it should be
+/// stored alongside the rest of the UI state rather than recreated every frame.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut editor = NodeEditor::new("graph").minimap(true);
+/// let mut positions = [[20.0, 20.0], [220.0, 60.0]];
+/// let mut links = vec![(LinkId(0), PinId(1), PinId(2))];
+/// // Every frame:
+/// let events = editor.build(ui, [0.0, 0.0], |graph| {
+///     graph.node(NodeId(0), positions[0]).title("Source").output(PinId(1), "Value").build();
+///     graph.node(NodeId(1), positions[1]).title("Sink").input(PinId(2), "Value").build();
+///     for &(id, from, to) in &links {
+///         graph.link(id, from, to);
+///     }
+/// });
+/// for event in events {
+///     match event {
+///         NodeEditorEvent::NodeMoved { node, position } => positions[node.0 as usize] = position,
+///         NodeEditorEvent::LinkCreated { from, to } => {
+///             links.push((LinkId(links.len() as u64), from, to))
+///         }
+///         NodeEditorEvent::LinkDeleted(id) => links.retain(|link| link.0 != id),
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NodeEditor {
+    id: String,
+    style: NodeEditorStyle,
+    show_minimap: bool,
+    show_grid: bool,
+    pan: [f32; 2],
+    zoom: f32,
+    selected_nodes: HashSet<NodeId>,
+    selected_links: HashSet<LinkId>,
+    /// Nodes from bottom to top.
+    z_order: Vec<NodeId>,
+    interaction: Interaction,
+    /// The pin a link was being dragged from on the last frame.
+    linking: Option<PinId>,
+}
+
+impl NodeEditor {
+    /// Creates a new node editor with the given id.
+    pub fn new(id: impl Into<String>) -> Self {
+        NodeEditor {
+            id: id.into(),
+            style: NodeEditorStyle::default(),
+            show_minimap: false,
+            show_grid: true,
+            pan: [0.0, 0.0],
+            zoom: 1.0,
+            selected_nodes: HashSet::new(),
+            selected_links: HashSet::new(),
+            z_order: Vec::new(),
+            interaction: Interaction::None,
+            linking: None,
+        }
+    }
+
+    /// Sets the colors and sizes.
+    pub fn style(mut self, style: NodeEditorStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Enables/disables the minimap in the bottom-right corner, which shows all nodes and the
+    /// visible area, and can be clicked to move the view.
+    ///
+    /// Disabled by default.
+    pub fn minimap(mut self, show_minimap: bool) -> Self {
+        self.show_minimap = show_minimap;
+        self
+    }
+
+    /// Enables/disables the background grid.
+    ///
+    /// Enabled by default.
+    pub fn grid(mut self, show_grid: bool) -> Self {
+        self.show_grid = show_grid;
+        self
+    }
+
+    /// Returns the offset of the graph origin from the top-left corner of the canvas, in pixels.
+    pub fn pan(&self) -> [f32; 2] {
+        self.pan
+    }
+
+    /// Sets the offset of the graph origin from the top-left corner of the canvas, in pixels.
+    pub fn set_pan(&mut self, pan: [f32; 2]) {
+        self.pan = pan;
+    }
+
+    /// Returns the zoom factor.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Sets the zoom factor, which is clamped to `0.2..=3.0`.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    /// Returns the selected nodes.
+    pub fn selected_nodes(&self) -> &HashSet<NodeId> {
+        &self.selected_nodes
+    }
+
+    /// Returns the selected links.
+    pub fn selected_links(&self) -> &HashSet<LinkId> {
+        &self.selected_links
+    }
+
+    /// Adds a node to the selection.
+    pub fn select_node(&mut self, node: NodeId) {
+        self.selected_nodes.insert(node);
+    }
+
+    /// Adds a link to the selection.
+    pub fn select_link(&mut self, link: LinkId) {
+        self.selected_links.insert(link);
+    }
+
+    /// Clears the selection of nodes and links.
+    pub fn clear_selection(&mut self) {
+        self.selected_nodes.clear();
+        self.selected_links.clear();
+    }
+
+    /// Builds the editor, filling `size` like a child window: zero fills the available space,
+    /// negative values leave that much space.
+    ///
+    /// `f` submits the graph for this frame. Returns what the user did.
+    pub fn build(
+        &mut self,
+        ui: &Ui,
+        size: impl Into<MintVec2>,
+        f: impl FnOnce(&mut NodeGraph),
+    ) -> Vec<NodeEditorEvent> {
+        let mut graph = NodeGraph::default();
+        f(&mut graph);
+        let mut events = Vec::new();
+        let _id = ui.push_id(&self.id);
+        ui.child_window("##canvas")
+            .size(size)
+            .scroll_bar(false)
+            .scrollable(false)
+            .build(|| self.build_canvas(ui, &graph, &mut events));
+        events
+    }
+
+    fn build_canvas(&mut self, ui: &Ui, graph: &NodeGraph, events: &mut Vec<NodeEditorEvent>) {
+        let io = ui.io();
+        let mouse = io.mouse_pos;
+        let origin = ui.cursor_screen_pos();
+        let avail = ui.content_region_avail();
+        let canvas_size = [avail[0].max(1.0), avail[1].max(1.0)];
+        let canvas_max = [origin[0] + canvas_size[0], origin[1] + canvas_size[1]];
+        let font_size = ui.current_font_size();
+
+        // Pan and zoom first, so that everything below uses this frame's view
+        if self.interaction == Interaction::Pan {
+            if ui.is_mouse_down(MouseButton::Middle) {
+                self.pan[0] += io.mouse_delta[0];
+                self.pan[1] += io.mouse_delta[1];
+            } else {
+                self.interaction = Interaction::None;
+            }
+        }
+        if ui.is_window_hovered() && io.mouse_wheel != 0.0 {
+            let view = View {
+                origin,
+                pan: self.pan,
+                zoom: self.zoom,
+            };
+            let anchor = view.to_graph(mouse);
+            self.set_zoom(self.zoom * 1.1f32.powf(io.mouse_wheel));
+            // Keep the point under the mouse in place
+            self.pan = [
+                mouse[0] - origin[0] - anchor[0] * self.zoom,
+                mouse[1] - origin[1] - anchor[1] * self.zoom,
+            ];
+        }
+        let view = View {
+            origin,
+            pan: self.pan,
+            zoom: self.zoom,
+        };
+
+        // Move the dragged nodes
+        let mut drag_delta = [0.0, 0.0];
+        if self.interaction == Interaction::DragNodes {
+            if ui.is_mouse_down(MouseButton::Left) {
+                drag_delta = [io.mouse_delta[0] / view.zoom, io.mouse_delta[1] / view.zoom];
+            } else {
+                self.interaction = Interaction::None;
+            }
+        }
+        let dragging = drag_delta != [0.0, 0.0];
+        let positions: Vec<[f32; 2]> = graph
+            .nodes
+            .iter()
+            .map(|node| {
+                if dragging && self.selected_nodes.contains(&node.id) {
+                    let position = [
+                        node.position[0] + drag_delta[0],
+                        node.position[1] + drag_delta[1],
+                    ];
+                    events.push(NodeEditorEvent::NodeMoved {
+                        node: node.id,
+                        position,
+                    });
+                    position
+                } else {
+                    node.position
+                }
+            })
+            .collect();
+
+        // Lay out the nodes, from bottom to top
+        let index_of: HashMap<NodeId, usize> = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id, index))
+            .collect();
+        self.z_order.retain(|id| index_of.contains_key(id));
+        let known: HashSet<NodeId> = self.z_order.iter().copied().collect();
+        self.z_order.extend(
+            graph
+                .nodes
+                .iter()
+                .map(|node| node.id)
+                .filter(|id| !known.contains(id)),
+        );
+        let layouts: Vec<NodeLayout> = self
+            .z_order
+            .iter()
+            .map(|id| {
+                let index = index_of[id];
+                layout_node(
+                    &graph.nodes[index],
+                    index,
+                    positions[index],
+                    font_size,
+                    |text| ui.calc_text_size(text)[0],
+                )
+            })
+            .collect();
+        let mut pins: HashMap<PinId, (PinKind, NodeId, [f32; 2])> = HashMap::new();
+        for layout in &layouts {
+            let node = &graph.nodes[layout.index];
+            for (pin, &center) in node.pins.iter().zip(&layout.pins) {
+                pins.insert(pin.id, (pin.kind, node.id, center));
+            }
+        }
+        let link_ends = |link: &LinkData| {
+            let &(_, _, from) = pins.get(&link.from)?;
+            let &(_, _, to) = pins.get(&link.to)?;
+            Some((view.to_screen(from), view.to_screen(to)))
+        };
+        let hovered_link = if ui.is_window_hovered() {
+            graph
+                .links
+                .iter()
+                .rev()
+                .find(|link| {
+                    link_ends(link).is_some_and(|(from, to)| {
+                        let (cp0, cp1) = link_control_points(from, to, view.zoom);
+                        bezier_distance(from, cp0, cp1, to, mouse)
+                            < self.style.link_thickness * view.zoom + 3.0
+                    })
+                })
+                .map(|link| link.id)
+        } else {
+            None
+        };
+
+        // Finish a box selection
+        if let Interaction::BoxSelect(start) = self.interaction {
+            if !ui.is_mouse_down(MouseButton::Left) {
+                let end = view.to_graph(mouse);
+                let min = [start[0].min(end[0]), start[1].min(end[1])];
+                let max = [start[0].max(end[0]), start[1].max(end[1])];
+                for layout in &layouts {
+                    if layout.min[0] < max[0]
+                        && min[0] < layout.max[0]
+                        && layout.min[1] < max[1]
+                        && min[1] < layout.max[1]
+                    {
+                        self.selected_nodes.insert(graph.nodes[layout.index].id);
+                    }
+                }
+                self.interaction = Interaction::None;
+            }
+        }
+
+        // The canvas goes first, so that nodes and pins on top of it take the mouse
+        let additive = io.key_ctrl || io.key_shift;
+        ui.set_cursor_screen_pos(origin);
+        ui.set_next_item_allow_overlap();
+        ui.invisible_button_flags(
+            "##background",
+            canvas_size,
+            ButtonFlags::MOUSE_BUTTON_LEFT | ButtonFlags::MOUSE_BUTTON_MIDDLE,
+        );
+        if ui.is_item_activated() {
+            if ui.is_mouse_clicked(MouseButton::Middle) {
+                self.interaction = Interaction::Pan;
+            } else if let Some(link) = hovered_link {
+                if !additive {
+                    self.clear_selection();
+                }
+                if !self.selected_links.remove(&link) {
+                    self.selected_links.insert(link);
+                }
+            } else {
+                if !additive {
+                    self.clear_selection();
+                }
+                self.interaction = Interaction::BoxSelect(view.to_graph(mouse));
+            }
+        }
+
+        let mut linking = None;
+        let mut hovered_pin = None;
+        let mut link_created = false;
+        let mut raised = None;
+        for layout in &layouts {
+            let node = &graph.nodes[layout.index];
+            let _id = ui.push_id_u64(node.id.0);
+            let min = view.to_screen(layout.min);
+            let max = view.to_screen(layout.max);
+            ui.set_cursor_screen_pos(min);
+            ui.set_next_item_allow_overlap();
+            ui.invisible_button("##node", [max[0] - min[0], max[1] - min[1]]);
+            if ui.is_item_activated() {
+                raised = Some(node.id);
+                if additive {
+                    if !self.selected_nodes.remove(&node.id) {
+                        self.selected_nodes.insert(node.id);
+                    }
+                } else if !self.selected_nodes.contains(&node.id) {
+                    self.clear_selection();
+                    self.selected_nodes.insert(node.id);
+                }
+                if self.selected_nodes.contains(&node.id) {
+                    self.interaction = Interaction::DragNodes;
+                }
+            }
+
+            let hit_size = self.style.pin_radius * view.zoom * 4.0;
+            for (pin, &center) in node.pins.iter().zip(&layout.pins) {
+                let _id = ui.push_id_u64(pin.id.0);
+                let center = view.to_screen(center);
+                ui.set_cursor_screen_pos([center[0] - hit_size * 0.5, center[1] - hit_size * 0.5]);
+                ui.invisible_button("##pin", [hit_size, hit_size]);
+                if ui.is_item_hovered() {
+                    hovered_pin = Some(pin.id);
+                }
+                if ui
+                    .drag_drop_source_config(PIN_PAYLOAD)
+                    .flags(
+                        DragDropFlags::SOURCE_NO_PREVIEW_TOOLTIP
+                            | DragDropFlags::SOURCE_NO_DISABLE_HOVER,
+                    )
+                    .begin_payload(pin.id)
+                    .is_some()
+                {
+                    linking = Some(pin.id);
+                }
+                if let Some(target) = ui.drag_drop_target() {
+                    if let Some(Ok(payload)) = target.accept_payload::<PinId, _>(
+                        PIN_PAYLOAD,
+                        DragDropFlags::ACCEPT_NO_DRAW_DEFAULT_RECT,
+                    ) {
+                        if let Some((from, to)) = link_between(&pins, payload.data, pin.id) {
+                            events.push(NodeEditorEvent::LinkCreated { from, to });
+                            link_created = true;
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(node) = raised {
+            self.z_order.retain(|&id| id != node);
+            self.z_order.push(node);
+        }
+        if let (Some(pin), None, false) = (self.linking, linking, link_created) {
+            events.push(NodeEditorEvent::LinkDropped {
+                pin,
+                position: view.to_graph(mouse),
+            });
+        }
+        self.linking = linking;
+
+        // The minimap goes last, on top of everything
+        let minimap = if self.show_minimap && !layouts.is_empty() {
+            let size = [
+                (canvas_size[0] * 0.2).max(80.0),
+                (canvas_size[1] * 0.2).max(60.0),
+            ];
+            let margin = 8.0;
+            let min = [
+                canvas_max[0] - size[0] - margin,
+                canvas_max[1] - size[1] - margin,
+            ];
+            let view_min = view.to_graph(origin);
+            let view_max = view.to_graph(canvas_max);
+            let mut bounds_min = view_min;
+            let mut bounds_max = view_max;
+            for layout in &layouts {
+                bounds_min = [
+                    bounds_min[0].min(layout.min[0]),
+                    bounds_min[1].min(layout.min[1]),
+                ];
+                bounds_max = [
+                    bounds_max[0].max(layout.max[0]),
+                    bounds_max[1].max(layout.max[1]),
+                ];
+            }
+            let bounds_size = [bounds_max[0] - bounds_min[0], bounds_max[1] - bounds_min[1]];
+            let scale = (size[0] / bounds_size[0]).min(size[1] / bounds_size[1]);
+            // Centers the bounds in the minimap
+            let offset = [
+                min[0] + (size[0] - bounds_size[0] * scale) * 0.5 - bounds_min[0] * scale,
+                min[1] + (size[1] - bounds_size[1] * scale) * 0.5 - bounds_min[1] * scale,
+            ];
+            let minimap = Minimap {
+                min,
+                max: [min[0] + size[0], min[1] + size[1]],
+                offset,
+                scale,
+            };
+
+            ui.set_cursor_screen_pos(min);
+            ui.invisible_button("##minimap", size);
+            if ui.is_item_activated() {
+                self.interaction = Interaction::Minimap;
+            }
+            if self.interaction == Interaction::Minimap {
+                if ui.is_mouse_down(MouseButton::Left) {
+                    // Center the view on the point under the mouse
+                    let target = minimap.to_graph(mouse);
+                    self.pan = [
+                        canvas_size[0] * 0.5 - target[0] * view.zoom,
+                        canvas_size[1] * 0.5 - target[1] * view.zoom,
+                    ];
+                } else {
+                    self.interaction = Interaction::None;
+                }
+            }
+            Some((minimap, view_min, view_max))
+        } else {
+            None
+        };
+
+        // Delete the selection
+        if ui.is_window_focused() && ui.is_key_pressed(Key::Delete) {
+            let mut deleted_links = HashSet::new();
+            for link in &graph.links {
+                let attached = [link.from, link.to].iter().any(|pin| {
+                    pins.get(pin)
+                        .is_some_and(|(_, node, _)| self.selected_nodes.contains(node))
+                });
+                if (attached || self.selected_links.contains(&link.id))
+                    && deleted_links.insert(link.id)
+                {
+                    events.push(NodeEditorEvent::LinkDeleted(link.id));
+                }
+            }
+            for node in &graph.nodes {
+                if self.selected_nodes.contains(&node.id) {
+                    events.push(NodeEditorEvent::NodeDeleted(node.id));
+                }
+            }
+            self.clear_selection();
+        }
+
+        // Draw
+        let style = &self.style;
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(origin, canvas_max, style.background)
+            .filled(true)
+            .build();
+        if self.show_grid {
+            let spacing = style.grid_spacing * view.zoom;
+            let mut x = origin[0] + view.pan[0].rem_euclid(spacing);
+            while x < canvas_max[0] {
+                draw_list
+                    .add_line([x, origin[1]], [x, canvas_max[1]], style.grid)
+                    .build();
+                x += spacing;
+            }
+            let mut y = origin[1] + view.pan[1].rem_euclid(spacing);
+            while y < canvas_max[1] {
+                draw_list
+                    .add_line([origin[0], y], [canvas_max[0], y], style.grid)
+                    .build();
+                y += spacing;
+            }
+        }
+
+        draw_list.channels_split(3, |channels| {
+            // Nodes are drawn first, in their own channel, as links are drawn below them
+            channels.set_current(1);
+            let text_size = font_size * view.zoom;
+            let padding = font_size * 0.5 * view.zoom;
+            let rounding = style.node_rounding * view.zoom;
+            for layout in &layouts {
+                let node = &graph.nodes[layout.index];
+                let min = view.to_screen(layout.min);
+                let max = view.to_screen(layout.max);
+                let title_max = [max[0], min[1] + layout.title_height * view.zoom];
+                draw_list
+                    .add_rect(min, max, style.node_background)
+                    .rounding(rounding)
+                    .filled(true)
+                    .build();
+                draw_list
+                    .add_rect(min, title_max, node.color.unwrap_or(style.node_title))
+                    .rounding(rounding)
+                    .round_bot_left(false)
+                    .round_bot_right(false)
+                    .filled(true)
+                    .build();
+                let selected = self.selected_nodes.contains(&node.id);
+                draw_list
+                    .add_rect(
+                        min,
+                        max,
+                        if selected {
+                            style.selected
+                        } else {
+                            style.node_border
+                        },
+                    )
+                    .rounding(rounding)
+                    .thickness(if selected { 2.0 } else { 1.0 })
+                    .build();
+                draw_list.add_text_with_font_size(
+                    [min[0] + padding, min[1] + padding],
+                    style.text,
+                    text_size,
+                    &node.title,
+                );
+
+                for (pin, &center) in node.pins.iter().zip(&layout.pins) {
+                    let center = view.to_screen(center);
+                    let color = if hovered_pin == Some(pin.id) || linking == Some(pin.id) {
+                        style.pin_hovered
+                    } else {
+                        style.pin
+                    };
+                    draw_list
+                        .add_circle(center, style.pin_radius * view.zoom, color)
+                        .filled(true)
+                        .build();
+                    let label_width = ui.calc_text_size(&pin.label)[0] * view.zoom;
+                    let label_x = match pin.kind {
+                        PinKind::Input => center[0] + padding,
+                        PinKind::Output => center[0] - padding - label_width,
+                    };
+                    draw_list.add_text_with_font_size(
+                        [label_x, center[1] - text_size * 0.5],
+                        style.text,
+                        text_size,
+                        &pin.label,
+                    );
+                }
+            }
+
+            channels.set_current(0);
+            for link in &graph.links {
+                let Some((from, to)) = link_ends(link) else {
+                    continue;
+                };
+                let color = if self.selected_links.contains(&link.id) {
+                    style.selected
+                } else if hovered_link == Some(link.id) {
+                    style.link_hovered
+                } else {
+                    link.color.unwrap_or(style.link)
+                };
+                let (cp0, cp1) = link_control_points(from, to, view.zoom);
+                draw_list
+                    .add_bezier_curve(from, cp0, cp1, to, color)
+                    .thickness(style.link_thickness * view.zoom)
+                    .build();
+            }
+
+            channels.set_current(2);
+            if let Some(&(kind, _, center)) = linking.and_then(|pin| pins.get(&pin)) {
+                let pin = view.to_screen(center);
+                let (from, to) = match kind {
+                    PinKind::Output => (pin, mouse),
+                    PinKind::Input => (mouse, pin),
+                };
+                let (cp0, cp1) = link_control_points(from, to, view.zoom);
+                draw_list
+                    .add_bezier_curve(from, cp0, cp1, to, style.link_hovered)
+                    .thickness(style.link_thickness * view.zoom)
+                    .build();
+            }
+            if let Interaction::BoxSelect(start) = self.interaction {
+                let start = view.to_screen(start);
+                draw_list
+                    .add_rect(start, mouse, style.selection_rect)
+                    .filled(true)
+                    .build();
+                let mut border = style.selection_rect;
+                border[3] = 1.0;
+                draw_list.add_rect(start, mouse, border).build();
+            }
+            if let Some((minimap, view_min, view_max)) = &minimap {
+                draw_list
+                    .add_rect(minimap.min, minimap.max, style.minimap_background)
+                    .filled(true)
+                    .build();
+                draw_list.with_clip_rect_intersect(minimap.min, minimap.max, || {
+                    for layout in &layouts {
+                        let node = &graph.nodes[layout.index];
+                        let color = if self.selected_nodes.contains(&node.id) {
+                            style.selected
+                        } else {
+                            node.color.unwrap_or(style.node_title)
+                        };
+                        draw_list
+                            .add_rect(
+                                minimap.to_screen(layout.min),
+                                minimap.to_screen(layout.max),
+                                color,
+                            )
+                            .filled(true)
+                            .build();
+                    }
+                    draw_list
+                        .add_rect(
+                            minimap.to_screen(*view_min),
+                            minimap.to_screen(*view_max),
+                            style.text,
+                        )
+                        .build();
+                });
+            }
+        });
+    }
+}
+
+/// Maps graph coordinates to the minimap.
+#[derive(Copy, Clone, Debug)]
+struct Minimap {
+    min: [f32; 2],
+    max: [f32; 2],
+    offset: [f32; 2],
+    scale: f32,
+}
+
+impl Minimap {
+    fn to_screen(self, p: [f32; 2]) -> [f32; 2] {
+        [
+            self.offset[0] + p[0] * self.scale,
+            self.offset[1] + p[1] * self.scale,
+        ]
+    }
+
+    fn to_graph(self, p: [f32; 2]) -> [f32; 2] {
+        [
+            (p[0] - self.offset[0]) / self.scale,
+            (p[1] - self.offset[1]) / self.scale,
+        ]
+    }
+}
+
+fn layout_node(
+    node: &NodeData,
+    index: usize,
+    position: [f32; 2],
+    font_size: f32,
+    text_width: impl Fn(&str) -> f32,
+) -> NodeLayout {
+    let padding = font_size * 0.5;
+    let row_height = font_size + padding * 0.5;
+    let title_height = font_size + padding * 2.0;
+    let label_width = |kind| {
+        node.pins
+            .iter()
+            .filter(|pin| pin.kind == kind)
+            .map(|pin| text_width(&pin.label))
+            .fold(0.0, f32::max)
+    };
+    let width = (text_width(&node.title) + padding * 2.0)
+        .max(label_width(PinKind::Input) + label_width(PinKind::Output) + padding * 6.0)
+        .max(font_size * 6.0);
+
+    let mut rows = [0, 0];
+    let pins = node
+        .pins
+        .iter()
+        .map(|pin| {
+            let (side, x) = match pin.kind {
+                PinKind::Input => (0, position[0]),
+                PinKind::Output => (1, position[0] + width),
+            };
+            let row = rows[side];
+            rows[side] += 1;
+            let y = position[1] + title_height + padding * 0.5 + (row as f32 + 0.5) * row_height;
+            [x, y]
+        })
+        .collect();
+    let height = title_height + padding + rows[0].max(rows[1]) as f32 * row_height;
+
+    NodeLayout {
+        index,
+        min: position,
+        max: [position[0] + width, position[1] + height],
+        title_height,
+        pins,
+    }
+}
+
+/// Returns the output and input pin of a link between `a` and `b`, if they can be linked.
+fn link_between(
+    pins: &HashMap<PinId, (PinKind, NodeId, [f32; 2])>,
+    a: PinId,
+    b: PinId,
+) -> Option<(PinId, PinId)> {
+    let &(kind_a, node_a, _) = pins.get(&a)?;
+    let &(kind_b, node_b, _) = pins.get(&b)?;
+    if kind_a == kind_b || node_a == node_b {
+        return None;
+    }
+    match kind_a {
+        PinKind::Output => Some((a, b)),
+        PinKind::Input => Some((b, a)),
+    }
+}
+
+fn link_control_points(from: [f32; 2], to: [f32; 2], zoom: f32) -> ([f32; 2], [f32; 2]) {
+    let dx = ((to[0] - from[0]).abs() * 0.5).max(50.0 * zoom);
+    ([from[0] + dx, from[1]], [to[0] - dx, to[1]])
+}
+
+/// Returns the approximate distance from `p` to a cubic Bezier curve.
+fn bezier_distance(p0: [f32; 2], p1: [f32; 2], p2: [f32; 2], p3: [f32; 2], p: [f32; 2]) -> f32 {
+    const SEGMENTS: usize = 24;
+    let point = |t: f32| {
+        let u = 1.0 - t;
+        let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+        [
+            a * p0[0] + b * p1[0] + c * p2[0] + d * p3[0],
+            a * p0[1] + b * p1[1] + c * p2[1] + d * p3[1],
+        ]
+    };
+    let mut distance = f32::MAX;
+    let mut previous = p0;
+    for i in 1..=SEGMENTS {
+        let next = point(i as f32 / SEGMENTS as f32);
+        distance = distance.min(segment_distance(previous, next, p));
+        previous = next;
+    }
+    distance
+}
+
+fn segment_distance(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    let ab = [b[0] - a[0], b[1] - a[1]];
+    let ap = [p[0] - a[0], p[1] - a[1]];
+    let length_sq = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if length_sq > 0.0 {
+        ((ap[0] * ab[0] + ap[1] * ab[1]) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = [a[0] + ab[0] * t, a[1] + ab[1] * t];
+    ((p[0] - closest[0]).powi(2) + (p[1] - closest[1]).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Condition;
+
+    fn node(id: u64, pins: &[(u64, PinKind)]) -> NodeData {
+        NodeData {
+            id: NodeId(id),
+            position: [0.0, 0.0],
+            title: "Node".into(),
+            color: None,
+            pins: pins
+                .iter()
+                .map(|&(pin, kind)| PinData {
+                    id: PinId(pin),
+                    kind,
+                    label: "Pin".into(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_layout_and_link_rules() {
+        let data = node(
+            0,
+            &[
+                (1, PinKind::Input),
+                (2, PinKind::Output),
+                (3, PinKind::Input),
+            ],
+        );
+        let layout = layout_node(&data, 4, [10.0, 20.0], 10.0, |text| text.len() as f32 * 5.0);
+        assert_eq!(layout.index, 4);
+        assert_eq!(layout.min, [10.0, 20.0]);
+        // Inputs on the left edge, outputs on the right, rows shared between the two sides
+        assert_eq!(layout.pins[0][0], 10.0);
+        assert_eq!(layout.pins[1][0], layout.max[0]);
+        assert_eq!(layout.pins[0][1], layout.pins[1][1]);
+        assert!(layout.pins[2][1] > layout.pins[0][1]);
+        assert!(layout.pins[2][1] < layout.max[1]);
+
+        let mut pins = HashMap::new();
+        pins.insert(PinId(1), (PinKind::Input, NodeId(0), [0.0, 0.0]));
+        pins.insert(PinId(2), (PinKind::Output, NodeId(0), [0.0, 0.0]));
+        pins.insert(PinId(3), (PinKind::Output, NodeId(1), [0.0, 0.0]));
+        pins.insert(PinId(4), (PinKind::Input, NodeId(1), [0.0, 0.0]));
+        // Links always go from an output to an input, whichever end was dragged
+        assert_eq!(
+            link_between(&pins, PinId(1), PinId(3)),
+            Some((PinId(3), PinId(1)))
+        );
+        assert_eq!(
+            link_between(&pins, PinId(2), PinId(4)),
+            Some((PinId(2), PinId(4)))
+        );
+        assert_eq!(link_between(&pins, PinId(1), PinId(4)), None);
+        assert_eq!(link_between(&pins, PinId(1), PinId(2)), None);
+        assert_eq!(link_between(&pins, PinId(1), PinId(5)), None);
+    }
+
+    #[test]
+    fn test_drag_node() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut editor = NodeEditor::new("graph");
+        let mut position = [50.0, 50.0];
+        let steps: [([f32; 2], bool); 5] = [
+            ([100.0, 90.0], false),
+            ([100.0, 90.0], false),
+            ([100.0, 90.0], true),
+            ([130.0, 110.0], true),
+            ([130.0, 110.0], false),
+        ];
+        let mut moved = false;
+        for (mouse, down) in steps {
+            ctx.io_mut().add_mouse_pos_event(mouse);
+            ctx.io_mut()
+                .add_mouse_button_event(crate::MouseButton::Left, down);
+            let ui = ctx.frame();
+            ui.window("Editor")
+                .position([0.0, 0.0], Condition::Always)
+                .size([600.0, 400.0], Condition::Always)
+                .build(|| {
+                    let events = editor.build(ui, [0.0, 0.0], |graph| {
+                        graph
+                            .node(NodeId(7), position)
+                            .title("Node")
+                            .input(PinId(1), "In")
+                            .build();
+                    });
+                    for event in events {
+                        if let NodeEditorEvent::NodeMoved { node, position: p } = event {
+                            assert_eq!(node, NodeId(7));
+                            position = p;
+                            moved = true;
+                        }
+                    }
+                });
+            ctx.render();
+        }
+        assert!(moved);
+        assert_eq!(position, [80.0, 70.0]);
+        assert!(editor.selected_nodes().contains(&NodeId(7)));
+    }
+}
@@ -0,0 +1,1031 @@
+use std::collections::HashSet;
+
+use crate::math::MintVec2;
+use crate::value_format::{format_number, nice_step, utc_date_time};
+use crate::{MouseButton, StyleColor, Ui};
+
+/// The default series colors, used in order of submission.
+const PALETTE: [[f32; 4]; 10] = [
+    [0.30, 0.45, 0.69, 1.0],
+    [0.87, 0.52, 0.32, 1.0],
+    [0.33, 0.66, 0.41, 1.0],
+    [0.77, 0.31, 0.32, 1.0],
+    [0.51, 0.45, 0.70, 1.0],
+    [0.58, 0.47, 0.38, 1.0],
+    [0.85, 0.55, 0.76, 1.0],
+    [0.55, 0.55, 0.55, 1.0],
+    [0.80, 0.73, 0.46, 1.0],
+    [0.39, 0.71, 0.80, 1.0],
+];
+
+/// Steps tried for time axes, in seconds, before falling back to multiples of days.
+const TIME_STEPS: [f64; 29] = [
+    0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0,
+    120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0, 7200.0, 10800.0, 21600.0, 43200.0, 86400.0,
+    172800.0, 604800.0,
+];
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// How the values of a [Plot] axis are shown in tick labels and tooltips.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AxisFormat {
+    /// Plain numbers, with as many decimals as the tick spacing needs.
+    #[default]
+    Number,
+    /// Seconds since the Unix epoch, shown as UTC dates and times.
+    Time,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum SeriesKind {
+    Line,
+    Scatter,
+    Bars,
+    Shaded,
+}
+
+#[derive(Clone, Debug)]
+struct SeriesData<'p> {
+    label: String,
+    kind: SeriesKind,
+    xs: &'p [f64],
+    ys: &'p [f64],
+    color: Option<[f32; 4]>,
+    weight: Option<f32>,
+    bar_width: Option<f64>,
+    reference: f64,
+}
+
+impl SeriesData<'_> {
+    fn points(&self) -> impl Iterator<Item = (usize, [f64; 2])> + '_ {
+        self.xs
+            .iter()
+            .zip(self.ys)
+            .enumerate()
+            .map(|(i, (&x, &y))| (i, [x, y]))
+    }
+
+    /// The width of the bars, which defaults to two thirds of the smallest gap between xs.
+    fn bar_width(&self) -> f64 {
+        self.bar_width.unwrap_or_else(|| {
+            let gap = self
+                .xs
+                .windows(2)
+                .map(|w| (w[1] - w[0]).abs())
+                .filter(|gap| *gap > 0.0)
+                .fold(f64::INFINITY, f64::min);
+            if gap.is_finite() {
+                gap * 0.67
+            } else {
+                0.67
+            }
+        })
+    }
+}
+
+/// The series submitted to a [Plot] for one frame, in [Plot::build].
+///
+/// Every series is made of points whose coordinates are taken from `xs` and `ys`. If they
+/// don't have the same length, the extra values are ignored. Points with non-finite
+/// coordinates are skipped, which also breaks lines.
+#[derive(Clone, Debug, Default)]
+pub struct PlotItems<'p> {
+    series: Vec<SeriesData<'p>>,
+}
+
+impl<'p> PlotItems<'p> {
+    /// Adds a series drawn as a line through its points.
+    pub fn line(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'p [f64],
+        ys: &'p [f64],
+    ) -> PlotSeriesBuilder<'_, 'p> {
+        self.series(label, SeriesKind::Line, xs, ys)
+    }
+
+    /// Adds a series drawn as a marker at each point.
+    pub fn scatter(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'p [f64],
+        ys: &'p [f64],
+    ) -> PlotSeriesBuilder<'_, 'p> {
+        self.series(label, SeriesKind::Scatter, xs, ys)
+    }
+
+    /// Adds a series drawn as vertical bars centered on each x, from the
+    /// [reference](PlotSeriesBuilder::reference) to y.
+    pub fn bars(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'p [f64],
+        ys: &'p [f64],
+    ) -> PlotSeriesBuilder<'_, 'p> {
+        self.series(label, SeriesKind::Bars, xs, ys)
+    }
+
+    /// Adds a series drawn as a line with the area between it and the
+    /// [reference](PlotSeriesBuilder::reference) filled.
+    pub fn shaded(
+        &mut self,
+        label: impl Into<String>,
+        xs: &'p [f64],
+        ys: &'p [f64],
+    ) -> PlotSeriesBuilder<'_, 'p> {
+        self.series(label, SeriesKind::Shaded, xs, ys)
+    }
+
+    fn series(
+        &mut self,
+        label: impl Into<String>,
+        kind: SeriesKind,
+        xs: &'p [f64],
+        ys: &'p [f64],
+    ) -> PlotSeriesBuilder<'_, 'p> {
+        PlotSeriesBuilder {
+            items: self,
+            series: SeriesData {
+                label: label.into(),
+                kind,
+                xs,
+                ys,
+                color: None,
+                weight: None,
+                bar_width: None,
+                reference: 0.0,
+            },
+        }
+    }
+}
+
+/// Builder for a series of a [Plot], created with the methods of [PlotItems].
+#[must_use = "call .build() to add the series"]
+pub struct PlotSeriesBuilder<'a, 'p> {
+    items: &'a mut PlotItems<'p>,
+    series: SeriesData<'p>,
+}
+
+impl PlotSeriesBuilder<'_, '_> {
+    /// Sets the color. By default, series take colors from a palette in order.
+    pub fn color(mut self, color: impl Into<[f32; 4]>) -> Self {
+        self.series.color = Some(color.into());
+        self
+    }
+
+    /// Sets the line thickness of line and shaded series, or the marker radius of scatter
+    /// series, in pixels.
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.series.weight = Some(weight);
+        self
+    }
+
+    /// Sets the width of bars, in x units. Defaults to two thirds of the smallest gap between
+    /// two consecutive xs.
+    pub fn bar_width(mut self, bar_width: f64) -> Self {
+        self.series.bar_width = Some(bar_width);
+        self
+    }
+
+    /// Sets the y value bars and shaded areas start from. Defaults to 0.
+    pub fn reference(mut self, reference: f64) -> Self {
+        self.series.reference = reference;
+        self
+    }
+
+    /// Adds the series to the plot.
+    pub fn build(self) {
+        self.items.series.push(self.series);
+    }
+}
+
+/// The point of a series closest to the mouse, as returned in [PlotResponse].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PlotPoint {
+    /// Index of the series, in order of submission.
+    pub series: usize,
+    /// Index of the point in the series.
+    pub index: usize,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// What happened to a [Plot] during [Plot::build].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PlotResponse {
+    /// Whether the mouse is over the plot.
+    pub hovered: bool,
+    /// The position of the mouse in plot coordinates, if it is over the plot area.
+    pub mouse_position: Option<[f64; 2]>,
+    /// The visible point closest to the mouse, if it is over the plot area.
+    pub nearest: Option<PlotPoint>,
+}
+
+/// Maps plot coordinates to screen coordinates.
+#[derive(Copy, Clone, Debug)]
+struct Transform {
+    min: [f32; 2],
+    max: [f32; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Transform {
+    fn to_screen(self, p: [f64; 2]) -> [f32; 2] {
+        let tx = (p[0] - self.x[0]) / (self.x[1] - self.x[0]);
+        let ty = (p[1] - self.y[0]) / (self.y[1] - self.y[0]);
+        [
+            self.min[0] + (tx * (self.max[0] - self.min[0]) as f64) as f32,
+            self.max[1] - (ty * (self.max[1] - self.min[1]) as f64) as f32,
+        ]
+    }
+
+    fn to_plot(self, p: [f32; 2]) -> [f64; 2] {
+        let tx = ((p[0] - self.min[0]) / (self.max[0] - self.min[0])) as f64;
+        let ty = ((self.max[1] - p[1]) / (self.max[1] - self.min[1])) as f64;
+        [
+            self.x[0] + tx * (self.x[1] - self.x[0]),
+            self.y[0] + ty * (self.y[1] - self.y[0]),
+        ]
+    }
+}
+
+/// A plot of one or several series of points, with axes, tick labels and a legend.
+///
+/// Series are drawn as lines, markers, bars or shaded areas, and submitted every frame to
+/// [build](Self::build), borrowing the application's data. The mouse wheel zooms around the
+/// mouse, over the plot area or over an axis to only zoom that axis, dragging pans, and
+/// double-clicking fits the view to the data again. Hovering the plot shows a tooltip with
+/// the nearest point, and clicking a legend entry hides or shows its series.
+///
+/// By default the view follows the data, which suits streaming data; it stops once the user
+/// zooms or pans. The `Plot` keeps this view state, so it should be stored alongside the rest
+/// of the UI state rather than recreated every frame.
+///
+/// This is a lighter alternative to [PlotLines](crate::PlotLines) and
+/// [PlotHistogram](crate::PlotHistogram) for when axes or interaction are needed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut plot = Plot::new("latency")
+///     .title("Latency")
+///     .y_label("ms")
+///     .x_format(AxisFormat::Time);
+/// let times = [1.7e9, 1.7e9 + 1.0, 1.7e9 + 2.0];
+/// let p50 = [12.0, 14.0, 11.0];
+/// let p99 = [40.0, 52.0, 38.0];
+/// // Every frame:
+/// plot.build(ui, [0.0, 200.0], |items| {
+///     items.shaded("p50", &times, &p50).build();
+///     items.line("p99", &times, &p99).weight(2.0).build();
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Plot {
+    id: String,
+    title: Option<String>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    x_format: AxisFormat,
+    y_format: AxisFormat,
+    show_legend: bool,
+    x_limits: [f64; 2],
+    y_limits: [f64; 2],
+    auto_fit: bool,
+    hidden: HashSet<String>,
+    dragging: bool,
+}
+
+impl Plot {
+    /// Creates a new plot with the given id.
+    pub fn new(id: impl Into<String>) -> Self {
+        Plot {
+            id: id.into(),
+            title: None,
+            x_label: None,
+            y_label: None,
+            x_format: AxisFormat::Number,
+            y_format: AxisFormat::Number,
+            show_legend: true,
+            x_limits: [0.0, 1.0],
+            y_limits: [0.0, 1.0],
+            auto_fit: true,
+            hidden: HashSet::new(),
+            dragging: false,
+        }
+    }
+
+    /// Sets the title shown above the plot.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the label shown below the x axis.
+    pub fn x_label(mut self, label: impl Into<String>) -> Self {
+        self.x_label = Some(label.into());
+        self
+    }
+
+    /// Sets the label shown above the y axis.
+    pub fn y_label(mut self, label: impl Into<String>) -> Self {
+        self.y_label = Some(label.into());
+        self
+    }
+
+    /// Sets how x values are shown.
+    pub fn x_format(mut self, format: AxisFormat) -> Self {
+        self.x_format = format;
+        self
+    }
+
+    /// Sets how y values are shown.
+    pub fn y_format(mut self, format: AxisFormat) -> Self {
+        self.y_format = format;
+        self
+    }
+
+    /// Enables/disables the legend.
+    ///
+    /// Enabled by default.
+    pub fn legend(mut self, show_legend: bool) -> Self {
+        self.show_legend = show_legend;
+        self
+    }
+
+    /// Returns the visible range of the x axis.
+    pub fn x_limits(&self) -> [f64; 2] {
+        self.x_limits
+    }
+
+    /// Returns the visible range of the y axis.
+    pub fn y_limits(&self) -> [f64; 2] {
+        self.y_limits
+    }
+
+    /// Sets the visible range of the x axis, and stops fitting the view to the data.
+    pub fn set_x_limits(&mut self, min: f64, max: f64) {
+        self.x_limits = [min, max];
+        self.auto_fit = false;
+    }
+
+    /// Sets the visible range of the y axis, and stops fitting the view to the data.
+    pub fn set_y_limits(&mut self, min: f64, max: f64) {
+        self.y_limits = [min, max];
+        self.auto_fit = false;
+    }
+
+    /// Fits the view to the data, on this frame and the following ones until the user zooms
+    /// or pans.
+    pub fn fit(&mut self) {
+        self.auto_fit = true;
+    }
+
+    /// Returns whether the view follows the data.
+    pub fn is_auto_fit(&self) -> bool {
+        self.auto_fit
+    }
+
+    /// Returns whether the series with the given label is hidden.
+    pub fn is_series_hidden(&self, label: &str) -> bool {
+        self.hidden.contains(label)
+    }
+
+    /// Hides or shows the series with the given label.
+    pub fn set_series_hidden(&mut self, label: impl Into<String>, hidden: bool) {
+        let label = label.into();
+        if hidden {
+            self.hidden.insert(label);
+        } else {
+            self.hidden.remove(&label);
+        }
+    }
+
+    /// Builds the plot with the given size: zero fills the available space, negative values
+    /// leave that much space.
+    ///
+    /// `f` submits the series for this frame.
+    pub fn build<'p>(
+        &mut self,
+        ui: &Ui,
+        size: impl Into<MintVec2>,
+        f: impl FnOnce(&mut PlotItems<'p>),
+    ) -> PlotResponse {
+        let mut items = PlotItems::default();
+        f(&mut items);
+        let series = &items.series;
+
+        let size: [f32; 2] = size.into().into();
+        let avail = ui.content_region_avail();
+        let size = [
+            if size[0] > 0.0 {
+                size[0]
+            } else {
+                avail[0] + size[0]
+            }
+            .max(1.0),
+            if size[1] > 0.0 {
+                size[1]
+            } else {
+                avail[1] + size[1]
+            }
+            .max(1.0),
+        ];
+        let frame_min = ui.cursor_screen_pos();
+        let frame_max = [frame_min[0] + size[0], frame_min[1] + size[1]];
+        let font_size = ui.current_font_size();
+        let padding = (font_size * 0.5).floor();
+
+        if self.auto_fit {
+            if let Some((x, y)) = data_bounds(series, &self.hidden) {
+                self.x_limits = x;
+                self.y_limits = y;
+            }
+        }
+
+        ui.invisible_button(&self.id, size);
+        // Keeps the mouse wheel from also scrolling the window
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "docking")] {
+                unsafe { sys::igSetItemKeyOwner_Nil(sys::ImGuiKey_MouseWheelY) }
+            } else {
+                unsafe { sys::igSetItemKeyOwner(sys::ImGuiKey_MouseWheelY) }
+            }
+        }
+        let hovered = ui.is_item_hovered();
+        let io = ui.io();
+        let mouse = io.mouse_pos;
+
+        // The top row holds the title and the y label, the bottom one the x ticks and label
+        let top = if self.title.is_some() || self.y_label.is_some() {
+            font_size + padding
+        } else {
+            padding
+        };
+        let bottom = font_size
+            + padding * 1.5
+            + if self.x_label.is_some() {
+                font_size + padding * 0.5
+            } else {
+                0.0
+            };
+        let y_tick_count = ((size[1] - top - bottom) / (font_size * 2.5)).max(2.0) as usize;
+        let y_ticks = axis_ticks(self.y_limits, y_tick_count, self.y_format);
+        let y_labels: Vec<String> = y_ticks
+            .ticks
+            .iter()
+            .map(|&v| format_value(v, y_ticks.step, self.y_format))
+            .collect();
+        let left = y_labels
+            .iter()
+            .map(|label| ui.calc_text_size(label)[0])
+            .fold(0.0, f32::max)
+            + padding * 2.0;
+        let plot_min = [frame_min[0] + left, frame_min[1] + top];
+        let plot_max = [
+            (frame_max[0] - padding).max(plot_min[0] + 1.0),
+            (frame_max[1] - bottom).max(plot_min[1] + 1.0),
+        ];
+        let plot_size = [plot_max[0] - plot_min[0], plot_max[1] - plot_min[1]];
+
+        // Legend entries: (series index, swatch and label rect)
+        let mut legend = Vec::new();
+        if self.show_legend {
+            let mut seen = HashSet::new();
+            let mut y = plot_min[1] + padding;
+            for (index, s) in series.iter().enumerate() {
+                if s.label.is_empty() || !seen.insert(s.label.as_str()) {
+                    continue;
+                }
+                let width = font_size + padding + ui.calc_text_size(&s.label)[0];
+                let min = [plot_min[0] + padding, y];
+                legend.push((index, min, [min[0] + width, min[1] + font_size]));
+                y += font_size + padding * 0.5;
+            }
+        }
+        let inside = |p: [f32; 2], min: [f32; 2], max: [f32; 2]| {
+            p[0] >= min[0] && p[0] < max[0] && p[1] >= min[1] && p[1] < max[1]
+        };
+
+        // Interaction, before drawing so that the view is up to date
+        if ui.is_item_activated() {
+            let clicked = legend
+                .iter()
+                .find(|(_, min, max)| inside(mouse, *min, *max));
+            if let Some(&(index, _, _)) = clicked {
+                let label = &series[index].label;
+                let hidden = self.is_series_hidden(label);
+                self.set_series_hidden(label.clone(), !hidden);
+            } else {
+                self.dragging = true;
+            }
+        }
+        if !ui.is_item_active() {
+            self.dragging = false;
+        }
+        let x_range = self.x_limits[1] - self.x_limits[0];
+        let y_range = self.y_limits[1] - self.y_limits[0];
+        if self.dragging && io.mouse_delta != [0.0, 0.0] {
+            let dx = -(io.mouse_delta[0] / plot_size[0]) as f64 * x_range;
+            let dy = (io.mouse_delta[1] / plot_size[1]) as f64 * y_range;
+            self.x_limits = [self.x_limits[0] + dx, self.x_limits[1] + dx];
+            self.y_limits = [self.y_limits[0] + dy, self.y_limits[1] + dy];
+            self.auto_fit = false;
+        }
+        if hovered && io.mouse_wheel != 0.0 {
+            let transform = Transform {
+                min: plot_min,
+                max: plot_max,
+                x: self.x_limits,
+                y: self.y_limits,
+            };
+            let anchor = transform.to_plot(mouse);
+            let factor = 0.9f64.powf(io.mouse_wheel as f64);
+            // Over an axis, only that axis zooms
+            if mouse[0] >= plot_min[0] {
+                self.x_limits = zoom_range(self.x_limits, anchor[0], factor);
+            }
+            if mouse[1] < plot_max[1] {
+                self.y_limits = zoom_range(self.y_limits, anchor[1], factor);
+            }
+            self.auto_fit = false;
+        }
+        if hovered && ui.is_mouse_double_clicked(MouseButton::Left) {
+            self.auto_fit = true;
+        }
+
+        let transform = Transform {
+            min: plot_min,
+            max: plot_max,
+            x: self.x_limits,
+            y: self.y_limits,
+        };
+        let y_ticks = axis_ticks(self.y_limits, y_tick_count, self.y_format);
+        let x_tick_count = (plot_size[0] / (font_size * 7.0)).max(2.0) as usize;
+        let x_ticks = axis_ticks(self.x_limits, x_tick_count, self.x_format);
+
+        let text_color = ui.style_color(StyleColor::Text);
+        let disabled_color = ui.style_color(StyleColor::TextDisabled);
+        let mut grid_color = text_color;
+        grid_color[3] *= 0.15;
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(plot_min, plot_max, ui.style_color(StyleColor::FrameBg))
+            .filled(true)
+            .build();
+
+        // Axes
+        draw_list.with_clip_rect_intersect(frame_min, frame_max, || {
+            for &v in &x_ticks.ticks {
+                let x = transform.to_screen([v, 0.0])[0].round();
+                if x < plot_min[0] || x > plot_max[0] {
+                    continue;
+                }
+                draw_list
+                    .add_line([x, plot_min[1]], [x, plot_max[1]], grid_color)
+                    .build();
+                let label = format_value(v, x_ticks.step, self.x_format);
+                let width = ui.calc_text_size(&label)[0];
+                draw_list.add_text(
+                    [x - width * 0.5, plot_max[1] + padding * 0.5],
+                    text_color,
+                    label,
+                );
+            }
+            for &v in &y_ticks.ticks {
+                let y = transform.to_screen([0.0, v])[1].round();
+                if y < plot_min[1] || y > plot_max[1] {
+                    continue;
+                }
+                draw_list
+                    .add_line([plot_min[0], y], [plot_max[0], y], grid_color)
+                    .build();
+                let label = format_value(v, y_ticks.step, self.y_format);
+                let width = ui.calc_text_size(&label)[0];
+                draw_list.add_text(
+                    [plot_min[0] - padding - width, y - font_size * 0.5],
+                    text_color,
+                    label,
+                );
+            }
+            if let Some(title) = &self.title {
+                let width = ui.calc_text_size(title)[0];
+                draw_list.add_text(
+                    [(plot_min[0] + plot_max[0] - width) * 0.5, frame_min[1]],
+                    text_color,
+                    title,
+                );
+            }
+            if let Some(label) = &self.y_label {
+                draw_list.add_text([frame_min[0], frame_min[1]], text_color, label);
+            }
+            if let Some(label) = &self.x_label {
+                let width = ui.calc_text_size(label)[0];
+                draw_list.add_text(
+                    [
+                        (plot_min[0] + plot_max[0] - width) * 0.5,
+                        frame_max[1] - font_size,
+                    ],
+                    text_color,
+                    label,
+                );
+            }
+        });
+
+        // Series
+        let color_of = |index: usize| {
+            series[index]
+                .color
+                .unwrap_or(PALETTE[index % PALETTE.len()])
+        };
+        let mouse_in_plot = hovered && inside(mouse, plot_min, plot_max);
+        let mut nearest: Option<(PlotPoint, f32)> = None;
+        draw_list.with_clip_rect_intersect(plot_min, plot_max, || {
+            for (index, s) in series.iter().enumerate() {
+                if self.hidden.contains(&s.label) {
+                    continue;
+                }
+                let color = color_of(index);
+                match s.kind {
+                    SeriesKind::Line | SeriesKind::Shaded => {
+                        if s.kind == SeriesKind::Shaded {
+                            let mut fill = color;
+                            fill[3] *= 0.3;
+                            draw_shaded(&draw_list, transform, s, fill);
+                        }
+                        let thickness = s.weight.unwrap_or(1.5);
+                        let mut line: Vec<[f32; 2]> = Vec::new();
+                        for (_, p) in s.points() {
+                            if p[0].is_finite() && p[1].is_finite() {
+                                line.push(transform.to_screen(p));
+                            } else if !line.is_empty() {
+                                draw_list
+                                    .add_polyline(std::mem::take(&mut line), color)
+                                    .thickness(thickness)
+                                    .build();
+                            }
+                        }
+                        if !line.is_empty() {
+                            draw_list
+                                .add_polyline(line, color)
+                                .thickness(thickness)
+                                .build();
+                        }
+                    }
+                    SeriesKind::Scatter => {
+                        let radius = s.weight.unwrap_or(3.0);
+                        for (_, p) in s.points() {
+                            let p = transform.to_screen(p);
+                            if inside(p, plot_min, plot_max) {
+                                draw_list.add_circle(p, radius, color).filled(true).build();
+                            }
+                        }
+                    }
+                    SeriesKind::Bars => {
+                        let half_width = s.bar_width() * 0.5;
+                        for (_, p) in s.points() {
+                            if !p[0].is_finite() || !p[1].is_finite() {
+                                continue;
+                            }
+                            let a = transform.to_screen([p[0] - half_width, s.reference]);
+                            let b = transform.to_screen([p[0] + half_width, p[1]]);
+                            draw_list
+                                .add_rect(
+                                    [a[0].min(b[0]), a[1].min(b[1])],
+                                    [a[0].max(b[0]), a[1].max(b[1])],
+                                    color,
+                                )
+                                .filled(true)
+                                .build();
+                        }
+                    }
+                }
+
+                if mouse_in_plot && !self.dragging {
+                    for (i, p) in s.points() {
+                        let screen = transform.to_screen(p);
+                        if !inside(screen, plot_min, plot_max) {
+                            continue;
+                        }
+                        let distance =
+                            (screen[0] - mouse[0]).powi(2) + (screen[1] - mouse[1]).powi(2);
+                        if nearest.is_none_or(|(_, d)| distance < d) {
+                            let point = PlotPoint {
+                                series: index,
+                                index: i,
+                                x: p[0],
+                                y: p[1],
+                            };
+                            nearest = Some((point, distance));
+                        }
+                    }
+                }
+            }
+
+            if let Some((point, _)) = nearest {
+                let p = transform.to_screen([point.x, point.y]);
+                draw_list
+                    .add_circle(p, font_size * 0.3, text_color)
+                    .thickness(1.5)
+                    .build();
+            }
+        });
+        draw_list
+            .add_rect(plot_min, plot_max, ui.style_color(StyleColor::Border))
+            .build();
+
+        // Legend
+        if let (Some(&(_, first_min, _)), Some(&(_, last_min, _))) = (legend.first(), legend.last())
+        {
+            let width = legend
+                .iter()
+                .map(|(_, min, max)| max[0] - min[0])
+                .fold(0.0, f32::max);
+            let min = [first_min[0] - padding * 0.5, first_min[1] - padding * 0.5];
+            let max = [
+                first_min[0] + width + padding * 0.5,
+                last_min[1] + font_size + padding * 0.5,
+            ];
+            draw_list
+                .add_rect(min, max, ui.style_color(StyleColor::PopupBg))
+                .filled(true)
+                .build();
+            draw_list
+                .add_rect(min, max, ui.style_color(StyleColor::Border))
+                .build();
+            for &(index, min, _) in &legend {
+                let label = &series[index].label;
+                let hidden = self.hidden.contains(label);
+                let swatch_max = [min[0] + font_size, min[1] + font_size];
+                let inset = [min[0] + 2.0, min[1] + 2.0];
+                let inset_max = [swatch_max[0] - 2.0, swatch_max[1] - 2.0];
+                if hidden {
+                    draw_list
+                        .add_rect(inset, inset_max, color_of(index))
+                        .build();
+                } else {
+                    draw_list
+                        .add_rect(inset, inset_max, color_of(index))
+                        .filled(true)
+                        .build();
+                }
+                draw_list.add_text(
+                    [swatch_max[0] + padding, min[1]],
+                    if hidden { disabled_color } else { text_color },
+                    label,
+                );
+            }
+        }
+
+        let nearest = nearest.map(|(point, _)| point);
+        let over_legend = legend.iter().any(|(_, min, max)| inside(mouse, *min, *max));
+        if let Some(point) = nearest.filter(|_| !over_legend) {
+            ui.tooltip(|| {
+                ui.text(&series[point.series].label);
+                ui.text(format!(
+                    "x: {}",
+                    format_precise(point.x, x_ticks.step, self.x_format)
+                ));
+                ui.text(format!(
+                    "y: {}",
+                    format_precise(point.y, y_ticks.step, self.y_format)
+                ));
+            });
+        }
+
+        PlotResponse {
+            hovered,
+            mouse_position: mouse_in_plot.then(|| transform.to_plot(mouse)),
+            nearest,
+        }
+    }
+}
+
+/// Fills the area between a series and its reference with triangles, splitting segments where
+/// they cross the reference so that every polygon stays convex.
+fn draw_shaded(
+    draw_list: &crate::DrawListMut<'_>,
+    transform: Transform,
+    series: &SeriesData<'_>,
+    color: [f32; 4],
+) {
+    let reference = series.reference;
+    let quad = |a: [f64; 2], b: [f64; 2]| {
+        let a0 = transform.to_screen([a[0], reference]);
+        let b0 = transform.to_screen([b[0], reference]);
+        let a1 = transform.to_screen(a);
+        let b1 = transform.to_screen(b);
+        draw_list
+            .add_triangle(a0, a1, b1, color)
+            .filled(true)
+            .build();
+        draw_list
+            .add_triangle(a0, b1, b0, color)
+            .filled(true)
+            .build();
+    };
+    let points: Vec<[f64; 2]> = series.points().map(|(_, p)| p).collect();
+    for w in points.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        if !(a[0].is_finite() && a[1].is_finite() && b[0].is_finite() && b[1].is_finite()) {
+            continue;
+        }
+        let (da, db) = (a[1] - reference, b[1] - reference);
+        if da * db < 0.0 {
+            let t = da / (da - db);
+            let crossing = [a[0] + (b[0] - a[0]) * t, reference];
+            quad(a, crossing);
+            quad(crossing, b);
+        } else {
+            quad(a, b);
+        }
+    }
+}
+
+/// Returns the range of the visible series, with a margin, or `None` if there is no data.
+fn data_bounds(
+    series: &[SeriesData<'_>],
+    hidden: &HashSet<String>,
+) -> Option<([f64; 2], [f64; 2])> {
+    let mut x = [f64::INFINITY, f64::NEG_INFINITY];
+    let mut y = [f64::INFINITY, f64::NEG_INFINITY];
+    let include = |range: &mut [f64; 2], v: f64| {
+        range[0] = range[0].min(v);
+        range[1] = range[1].max(v);
+    };
+    for s in series.iter().filter(|s| !hidden.contains(&s.label)) {
+        let half_width = if s.kind == SeriesKind::Bars {
+            s.bar_width() * 0.5
+        } else {
+            0.0
+        };
+        let mut any = false;
+        for (_, p) in s.points() {
+            if p[0].is_finite() && p[1].is_finite() {
+                include(&mut x, p[0] - half_width);
+                include(&mut x, p[0] + half_width);
+                include(&mut y, p[1]);
+                any = true;
+            }
+        }
+        if any && matches!(s.kind, SeriesKind::Bars | SeriesKind::Shaded) {
+            include(&mut y, s.reference);
+        }
+    }
+    if x[0] > x[1] {
+        return None;
+    }
+    let pad = |range: [f64; 2]| {
+        let size = range[1] - range[0];
+        if size > 0.0 {
+            [range[0] - size * 0.05, range[1] + size * 0.05]
+        } else {
+            let half = (range[0].abs() * 0.1).max(0.5);
+            [range[0] - half, range[1] + half]
+        }
+    };
+    Some((pad(x), pad(y)))
+}
+
+fn zoom_range(range: [f64; 2], anchor: f64, factor: f64) -> [f64; 2] {
+    [
+        anchor + (range[0] - anchor) * factor,
+        anchor + (range[1] - anchor) * factor,
+    ]
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct AxisTicks {
+    ticks: Vec<f64>,
+    step: f64,
+}
+
+/// Returns ticks at round values within `range`, at most about `max_count` of them.
+fn axis_ticks(range: [f64; 2], max_count: usize, format: AxisFormat) -> AxisTicks {
+    let (min, max) = (range[0].min(range[1]), range[0].max(range[1]));
+    let span = max - min;
+    if !(span > 0.0 && span.is_finite()) {
+        return AxisTicks {
+            ticks: vec![min],
+            step: 1.0,
+        };
+    }
+    let max_count = max_count.max(2) as f64;
+    let step = match format {
+        AxisFormat::Time => match TIME_STEPS.iter().find(|&&step| span / step <= max_count) {
+            Some(&step) => step,
+            None => nice_step(span / SECONDS_PER_DAY, max_count) * SECONDS_PER_DAY,
+        },
+        AxisFormat::Number => nice_step(span, max_count),
+    };
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    AxisTicks {
+        ticks: (first..=last).map(|i| i as f64 * step).collect(),
+        step,
+    }
+}
+
+/// Formats a tick label, with the precision needed for ticks `step` apart.
+fn format_value(value: f64, step: f64, format: AxisFormat) -> String {
+    match format {
+        AxisFormat::Number => format_number(value, step),
+        AxisFormat::Time => {
+            let (date, time) = utc_date_time(value);
+            if step >= SECONDS_PER_DAY {
+                date
+            } else if step >= 60.0 {
+                time[..5].to_string()
+            } else if step >= 1.0 {
+                time[..8].to_string()
+            } else {
+                time
+            }
+        }
+    }
+}
+
+/// Formats a value in a tooltip, more precisely than the ticks.
+fn format_precise(value: f64, step: f64, format: AxisFormat) -> String {
+    match format {
+        AxisFormat::Number => format_value(value, step / 100.0, format),
+        AxisFormat::Time => {
+            let (date, time) = utc_date_time(value);
+            format!("{} {}", date, time)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Condition;
+
+    #[test]
+    fn test_axis_ticks() {
+        let ticks = axis_ticks([0.0, 1.0], 5, AxisFormat::Number);
+        assert_eq!(ticks.step, 0.2);
+        assert_eq!(ticks.ticks.len(), 6);
+        assert_eq!(
+            format_value(ticks.ticks[3], ticks.step, AxisFormat::Number),
+            "0.6"
+        );
+
+        let ticks = axis_ticks([-1234.0, 5678.0], 8, AxisFormat::Number);
+        assert_eq!(ticks.step, 1000.0);
+        assert_eq!(ticks.ticks.first(), Some(&-1000.0));
+        assert_eq!(ticks.ticks.last(), Some(&5000.0));
+        assert_eq!(format_value(-1e-13, 0.1, AxisFormat::Number), "0.0");
+
+        // Time axes step through seconds, minutes and hours
+        let ticks = axis_ticks([0.0, 3.0 * 3600.0], 6, AxisFormat::Time);
+        assert_eq!(ticks.step, 1800.0);
+        assert_eq!(
+            format_value(ticks.ticks[1], ticks.step, AxisFormat::Time),
+            "00:30"
+        );
+    }
+
+    #[test]
+    fn test_fit_and_zoom() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut plot = Plot::new("plot");
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [1.0, 3.0, 2.0, 5.0];
+        let mut response = PlotResponse::default();
+        for wheel in [0.0, 0.0, 1.0] {
+            ctx.io_mut().add_mouse_pos_event([300.0, 200.0]);
+            if wheel != 0.0 {
+                ctx.io_mut().add_mouse_wheel_event([0.0, wheel]);
+            }
+            let ui = ctx.frame();
+            ui.window("Plot")
+                .position([0.0, 0.0], Condition::Always)
+                .size([600.0, 400.0], Condition::Always)
+                .build(|| {
+                    response = plot.build(ui, [0.0, 0.0], |items| {
+                        items.line("line", &xs, &ys).build();
+                        items.bars("bars", &xs, &ys).build();
+                    });
+                });
+            ctx.render();
+            if wheel == 0.0 {
+                // Bars are 0.67 wide and start from 0, with a 5% margin
+                assert!(plot.is_auto_fit());
+                let x = plot.x_limits();
+                assert!((x[0] + 0.335 + 0.1835).abs() < 1e-9, "{:?}", x);
+                let y = plot.y_limits();
+                assert!((y[0] + 0.25).abs() < 1e-9 && (y[1] - 5.25).abs() < 1e-9);
+            }
+        }
+        assert!(response.hovered);
+        assert!(response.nearest.is_some());
+        assert!(!plot.is_auto_fit());
+        let x = plot.x_limits();
+        assert!(x[1] - x[0] < 3.67 * 1.1 * 0.95);
+    }
+}
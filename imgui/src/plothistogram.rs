@@ -1,12 +1,13 @@
 use std::os::raw::c_float;
 use std::{f32, mem};
 
+use super::plotlines::{with_values_getter, PlotValues};
 use super::Ui;
 
 #[must_use]
-pub struct PlotHistogram<'ui, 'p, Label, Overlay = &'static str> {
+pub struct PlotHistogram<'ui, 'p, Label, Overlay = &'static str, Getter = fn(usize) -> f32> {
     label: Label,
-    values: &'p [f32],
+    values: PlotValues<'p, Getter>,
     values_offset: usize,
     overlay_text: Option<Overlay>,
     scale_min: f32,
@@ -19,7 +20,32 @@ impl<'ui, 'p, Label: AsRef<str>> PlotHistogram<'ui, 'p, Label> {
     pub fn new(ui: &'ui Ui, label: Label, values: &'p [f32]) -> Self {
         PlotHistogram {
             label,
-            values,
+            values: PlotValues::Slice(values),
+            values_offset: 0usize,
+            overlay_text: None,
+            scale_min: f32::MAX,
+            scale_max: f32::MAX,
+            graph_size: [0.0, 0.0],
+            ui,
+        }
+    }
+
+    /// Plots `values_count` values, which are read by calling `values_getter` with their index.
+    ///
+    /// This avoids copying values which aren't stored in a contiguous `f32` slice, such as
+    /// ring buffers, fields of structs or `f64`s.
+    pub fn from_fn<Getter: Fn(usize) -> f32>(
+        ui: &'ui Ui,
+        label: Label,
+        values_count: usize,
+        values_getter: Getter,
+    ) -> PlotHistogram<'ui, 'p, Label, &'static str, Getter> {
+        PlotHistogram {
+            label,
+            values: PlotValues::Getter {
+                count: values_count,
+                getter: values_getter,
+            },
             values_offset: 0usize,
             overlay_text: None,
             scale_min: f32::MAX,
@@ -30,7 +56,9 @@ impl<'ui, 'p, Label: AsRef<str>> PlotHistogram<'ui, 'p, Label> {
     }
 }
 
-impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Label, Overlay> {
+impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>, Getter: Fn(usize) -> f32>
+    PlotHistogram<'ui, 'p, Label, Overlay, Getter>
+{
     pub fn values_offset(mut self, values_offset: usize) -> Self {
         self.values_offset = values_offset;
         self
@@ -39,7 +67,7 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Lab
     pub fn overlay_text<NewOverlay: AsRef<str>>(
         self,
         overlay_text: NewOverlay,
-    ) -> PlotHistogram<'ui, 'p, Label, NewOverlay> {
+    ) -> PlotHistogram<'ui, 'p, Label, NewOverlay, Getter> {
         PlotHistogram {
             label: self.label,
             values: self.values,
@@ -71,17 +99,34 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotHistogram<'ui, 'p, Lab
         unsafe {
             let (label, overlay_text) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
 
-            sys::igPlotHistogram_FloatPtr(
-                label,
-                self.values.as_ptr() as *const c_float,
-                self.values.len() as i32,
-                self.values_offset as i32,
-                overlay_text,
-                self.scale_min,
-                self.scale_max,
-                self.graph_size.into(),
-                mem::size_of::<f32>() as i32,
-            );
+            match &self.values {
+                PlotValues::Slice(values) => sys::igPlotHistogram_FloatPtr(
+                    label,
+                    values.as_ptr() as *const c_float,
+                    values.len() as i32,
+                    self.values_offset as i32,
+                    overlay_text,
+                    self.scale_min,
+                    self.scale_max,
+                    self.graph_size.into(),
+                    mem::size_of::<f32>() as i32,
+                ),
+                PlotValues::Getter { count, getter } => {
+                    with_values_getter(getter, |values_getter, data| {
+                        sys::igPlotHistogram_FnFloatPtr(
+                            label,
+                            Some(values_getter),
+                            data,
+                            *count as i32,
+                            self.values_offset as i32,
+                            overlay_text,
+                            self.scale_min,
+                            self.scale_max,
+                            self.graph_size.into(),
+                        )
+                    })
+                }
+            }
         }
     }
 }
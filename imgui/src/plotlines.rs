@@ -1,12 +1,14 @@
-use std::os::raw::c_float;
+use std::any::Any;
+use std::os::raw::{c_float, c_int, c_void};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::{f32, mem};
 
 use super::Ui;
 
 #[must_use]
-pub struct PlotLines<'ui, 'p, Label, Overlay = &'static str> {
+pub struct PlotLines<'ui, 'p, Label, Overlay = &'static str, Getter = fn(usize) -> f32> {
     label: Label,
-    values: &'p [f32],
+    values: PlotValues<'p, Getter>,
     values_offset: usize,
     overlay_text: Option<Overlay>,
     scale_min: f32,
@@ -19,7 +21,32 @@ impl<'ui, 'p, Label: AsRef<str>> PlotLines<'ui, 'p, Label> {
     pub fn new(ui: &'ui Ui, label: Label, values: &'p [f32]) -> Self {
         PlotLines {
             label,
-            values,
+            values: PlotValues::Slice(values),
+            values_offset: 0usize,
+            overlay_text: None,
+            scale_min: f32::MAX,
+            scale_max: f32::MAX,
+            graph_size: [0.0, 0.0],
+            ui,
+        }
+    }
+
+    /// Plots `values_count` values, which are read by calling `values_getter` with their index.
+    ///
+    /// This avoids copying values which aren't stored in a contiguous `f32` slice, such as
+    /// ring buffers, fields of structs or `f64`s.
+    pub fn from_fn<Getter: Fn(usize) -> f32>(
+        ui: &'ui Ui,
+        label: Label,
+        values_count: usize,
+        values_getter: Getter,
+    ) -> PlotLines<'ui, 'p, Label, &'static str, Getter> {
+        PlotLines {
+            label,
+            values: PlotValues::Getter {
+                count: values_count,
+                getter: values_getter,
+            },
             values_offset: 0usize,
             overlay_text: None,
             scale_min: f32::MAX,
@@ -30,7 +57,9 @@ impl<'ui, 'p, Label: AsRef<str>> PlotLines<'ui, 'p, Label> {
     }
 }
 
-impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label, Overlay> {
+impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>, Getter: Fn(usize) -> f32>
+    PlotLines<'ui, 'p, Label, Overlay, Getter>
+{
     pub fn values_offset(mut self, values_offset: usize) -> Self {
         self.values_offset = values_offset;
         self
@@ -39,7 +68,7 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label,
     pub fn overlay_text<Overlay2: AsRef<str>>(
         self,
         overlay_text: Overlay2,
-    ) -> PlotLines<'ui, 'p, Label, Overlay2> {
+    ) -> PlotLines<'ui, 'p, Label, Overlay2, Getter> {
         PlotLines {
             label: self.label,
             values: self.values,
@@ -71,17 +100,134 @@ impl<'ui, 'p, Label: AsRef<str>, Overlay: AsRef<str>> PlotLines<'ui, 'p, Label,
         unsafe {
             let (label, overlay) = self.ui.scratch_txt_with_opt(self.label, self.overlay_text);
 
-            sys::igPlotLines_FloatPtr(
-                label,
-                self.values.as_ptr() as *const c_float,
-                self.values.len() as i32,
-                self.values_offset as i32,
-                overlay,
-                self.scale_min,
-                self.scale_max,
-                self.graph_size.into(),
-                mem::size_of::<f32>() as i32,
-            );
+            match &self.values {
+                PlotValues::Slice(values) => sys::igPlotLines_FloatPtr(
+                    label,
+                    values.as_ptr() as *const c_float,
+                    values.len() as i32,
+                    self.values_offset as i32,
+                    overlay,
+                    self.scale_min,
+                    self.scale_max,
+                    self.graph_size.into(),
+                    mem::size_of::<f32>() as i32,
+                ),
+                PlotValues::Getter { count, getter } => {
+                    with_values_getter(getter, |values_getter, data| {
+                        sys::igPlotLines_FnFloatPtr(
+                            label,
+                            Some(values_getter),
+                            data,
+                            *count as i32,
+                            self.values_offset as i32,
+                            overlay,
+                            self.scale_min,
+                            self.scale_max,
+                            self.graph_size.into(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// The values plotted by [PlotLines] and [PlotHistogram](crate::PlotHistogram).
+pub(crate) enum PlotValues<'p, Getter> {
+    Slice(&'p [f32]),
+    Getter { count: usize, getter: Getter },
+}
+
+type ValuesGetterFn = unsafe extern "C" fn(data: *mut c_void, idx: c_int) -> f32;
+
+struct ValuesGetter<'a, Getter> {
+    getter: &'a Getter,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+/// Calls `plot` with a callback and its user data, which read the values from `getter`.
+///
+/// Unwinding through Dear ImGui is undefined behaviour, so a panic of `getter` is caught, the
+/// remaining values are read as `0.0`, and the panic is resumed once `plot` returned.
+pub(crate) unsafe fn with_values_getter<Getter: Fn(usize) -> f32>(
+    getter: &Getter,
+    plot: impl FnOnce(ValuesGetterFn, *mut c_void),
+) {
+    let mut data = ValuesGetter {
+        getter,
+        panic: None,
+    };
+    plot(
+        call_values_getter::<Getter>,
+        &mut data as *mut ValuesGetter<'_, Getter> as *mut c_void,
+    );
+    if let Some(payload) = data.panic {
+        resume_unwind(payload);
+    }
+}
+
+unsafe extern "C" fn call_values_getter<Getter: Fn(usize) -> f32>(
+    data: *mut c_void,
+    idx: c_int,
+) -> f32 {
+    let data = &mut *(data as *mut ValuesGetter<'_, Getter>);
+    if data.panic.is_some() {
+        return 0.0;
+    }
+    let getter = data.getter;
+    match catch_unwind(AssertUnwindSafe(|| getter(idx as usize))) {
+        Ok(value) => value,
+        Err(payload) => {
+            data.panic = Some(payload);
+            0.0
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[test]
+    fn test_plot_from_fn() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let samples: VecDeque<f64> = (0..8).map(f64::from).collect();
+        let read = RefCell::new(Vec::new());
+        let ui = ctx.frame();
+        ui.plot_lines_from_fn("lines", samples.len(), |i| {
+            read.borrow_mut().push(i);
+            samples[i] as f32
+        })
+        .build();
+        ui.plot_histogram_from_fn("histogram", samples.len(), |i| samples[i] as f32)
+            .scale_min(0.0)
+            .build();
+        let mut read = read.into_inner();
+        read.sort_unstable();
+        read.dedup();
+        assert_eq!(read, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_plot_from_fn_panic() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let calls = Cell::new(0);
+        let ui = ctx.frame();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            ui.plot_lines_from_fn("lines", 8, |i| {
+                calls.set(calls.get() + 1);
+                if i == 3 {
+                    panic!("no value");
+                }
+                i as f32
+            })
+            .build()
+        }));
+        assert_eq!(result.unwrap_err().downcast_ref(), Some(&"no value"));
+        // the getter isn't called again once it panicked
+        assert_eq!(calls.get(), 4);
+        ctx.render();
+    }
+}
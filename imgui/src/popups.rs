@@ -1,8 +1,53 @@
 use std::ptr;
 
+use bitflags::bitflags;
+
+use crate::input::mouse::MouseButton;
 use crate::sys;
 use crate::window::WindowFlags;
-use crate::Ui;
+use crate::{Id, Ui};
+
+bitflags! {
+    /// Flags for opening popups and checking whether they are open.
+    ///
+    /// The low bits hold the mouse button used by the `*_on_item_click` and `begin_popup_context_*`
+    /// functions, which is a value rather than a set of bits: use [`PopupFlags::mouse_button`] or
+    /// one of the `MOUSE_BUTTON_*` constants, and leave it out for functions which ignore it.
+    #[repr(transparent)]
+    pub struct PopupFlags: u32 {
+        /// Open on left mouse button release
+        const MOUSE_BUTTON_LEFT = sys::ImGuiPopupFlags_MouseButtonLeft;
+        /// Open on right mouse button release
+        const MOUSE_BUTTON_RIGHT = sys::ImGuiPopupFlags_MouseButtonRight;
+        /// Open on middle mouse button release
+        const MOUSE_BUTTON_MIDDLE = sys::ImGuiPopupFlags_MouseButtonMiddle;
+        /// The bits holding the mouse button, e.g. to read it back with
+        /// `flags & PopupFlags::MOUSE_BUTTON_MASK`
+        const MOUSE_BUTTON_MASK = sys::ImGuiPopupFlags_MouseButtonMask_;
+        /// Don't reopen the same popup if it is already open, which would reposition it and
+        /// reinitialize its navigation
+        const NO_REOPEN = sys::ImGuiPopupFlags_NoReopen;
+        /// Don't open if there is already a popup open at the same level of the popup stack
+        const NO_OPEN_OVER_EXISTING_POPUP = sys::ImGuiPopupFlags_NoOpenOverExistingPopup;
+        /// For `begin_popup_context_window`: don't return true when hovering items, only when
+        /// hovering empty space
+        const NO_OPEN_OVER_ITEMS = sys::ImGuiPopupFlags_NoOpenOverItems;
+        /// For `is_popup_open`: ignore the id and test for any popup at the current level
+        const ANY_POPUP_ID = sys::ImGuiPopupFlags_AnyPopupId;
+        /// For `is_popup_open`: search through the whole popup stack rather than only the
+        /// current level
+        const ANY_POPUP_LEVEL = sys::ImGuiPopupFlags_AnyPopupLevel;
+        /// For `is_popup_open`: test for any popup at all
+        const ANY_POPUP = Self::ANY_POPUP_ID.bits | Self::ANY_POPUP_LEVEL.bits;
+    }
+}
+
+impl PopupFlags {
+    /// Flags which open a popup with the given mouse button.
+    pub fn mouse_button(button: MouseButton) -> Self {
+        Self::from_bits_truncate(button as u32)
+    }
+}
 
 /// Create a modal pop-up.
 ///
@@ -159,7 +204,92 @@ impl Ui {
     /// The confusing aspect to popups is that ImGui holds control over the popup itself.
     #[doc(alias = "OpenPopup")]
     pub fn open_popup(&self, str_id: impl AsRef<str>) {
-        unsafe { sys::igOpenPopup_Str(self.scratch_txt(str_id), 0) };
+        self.open_popup_with_flags(str_id, PopupFlags::empty());
+    }
+
+    /// Instructs ImGui that a popup is open, see [`open_popup`](Self::open_popup).
+    ///
+    /// Pass [`PopupFlags::NO_REOPEN`] to leave an already open popup where it is, or
+    /// [`PopupFlags::NO_OPEN_OVER_EXISTING_POPUP`] to not open over another popup.
+    #[doc(alias = "OpenPopup")]
+    pub fn open_popup_with_flags(&self, str_id: impl AsRef<str>, flags: PopupFlags) {
+        unsafe { sys::igOpenPopup_Str(self.scratch_txt(str_id), flags.bits() as i32) };
+    }
+
+    /// Instructs ImGui that the popup with the given id is open.
+    ///
+    /// This is useful to open a popup from outside of the id stack it is begun in: create the
+    /// id with [`new_id_str`](Self::new_id_str) while that stack is current, and open it from
+    /// anywhere later on. The popup is then begun with the same string as usual.
+    #[doc(alias = "OpenPopupID")]
+    pub fn open_popup_id(&self, id: Id, flags: PopupFlags) {
+        unsafe { sys::igOpenPopup_ID(id.0, flags.bits() as i32) };
+    }
+
+    /// Opens a popup when the last item is clicked with the mouse button given in `flags`,
+    /// defaulting to the left mouse button.
+    ///
+    /// Without a label, the popup uses the id of the last item, so it should be begun with
+    /// [`begin_popup_context_item`](Self::begin_popup_context_item). Unlike the
+    /// `begin_popup_context_*` functions this only opens the popup, which is useful to open
+    /// the same popup from several items.
+    #[doc(alias = "OpenPopupOnItemClick")]
+    pub fn open_popup_on_item_click(&self, str_id: Option<&str>, flags: PopupFlags) {
+        unsafe { sys::igOpenPopupOnItemClick(self.scratch_txt_opt(str_id), flags.bits() as i32) };
+    }
+
+    /// Returns true if the popup with the given label is open at the current level of the
+    /// popup stack.
+    #[doc(alias = "IsPopupOpen")]
+    pub fn is_popup_open(&self, str_id: impl AsRef<str>) -> bool {
+        self.is_popup_open_with_flags(str_id, PopupFlags::empty())
+    }
+
+    /// Returns true if the popup with the given label is open, with [`PopupFlags::ANY_POPUP_ID`]
+    /// and [`PopupFlags::ANY_POPUP_LEVEL`] widening the check. The label is ignored when
+    /// `ANY_POPUP_ID` is set.
+    #[doc(alias = "IsPopupOpen")]
+    pub fn is_popup_open_with_flags(&self, str_id: impl AsRef<str>, flags: PopupFlags) -> bool {
+        let str_id = self.scratch_txt(str_id);
+        let flags = flags.bits() as i32;
+        unsafe {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "docking")] {
+                    sys::igIsPopupOpen_Str(str_id, flags)
+                } else {
+                    sys::igIsPopupOpen(str_id, flags)
+                }
+            }
+        }
+    }
+
+    /// Returns true if any popup is open, anywhere in the popup stack.
+    #[doc(alias = "IsPopupOpen")]
+    pub fn is_any_popup_open(&self) -> bool {
+        self.is_popup_open_with_flags("", PopupFlags::ANY_POPUP)
+    }
+
+    /// Returns true if any modal popup is open and visible, e.g. to skip global shortcuts while
+    /// one is shown.
+    #[doc(alias = "GetTopMostAndVisiblePopupModal")]
+    pub fn is_any_modal_popup_open(&self) -> bool {
+        unsafe { sys::imgui_rs_IsAnyModalPopupOpen() }
+    }
+
+    /// Returns the number of open popups, which is the level that
+    /// [`close_popups_to_level`](Self::close_popups_to_level) would keep everything open at.
+    pub fn open_popup_count(&self) -> usize {
+        unsafe { sys::imgui_rs_GetOpenPopupCount() as usize }
+    }
+
+    /// Closes all popups above the first `remaining` ones in the popup stack, so `0` closes
+    /// every popup. Does nothing if fewer than `remaining + 1` popups are open.
+    ///
+    /// When `restore_focus` is true, focus goes back to the window under the last closed popup.
+    #[doc(alias = "ClosePopupToLevel")]
+    pub fn close_popups_to_level(&self, remaining: usize, restore_focus: bool) {
+        let remaining = i32::try_from(remaining).unwrap_or(i32::MAX);
+        unsafe { sys::imgui_rs_ClosePopupToLevel(remaining, restore_focus) };
     }
 
     /// Construct a popup that can have any kind of content.
@@ -287,6 +417,27 @@ impl Ui {
         }
     }
 
+    /// Open and begin popup when the last item is clicked with the mouse button given in `flags`.
+    ///
+    /// Without a label, this uses the id of the last item like
+    /// [begin_popup_context_item](Self::begin_popup_context_item).
+    #[doc(alias = "BeginPopupContextItem")]
+    pub fn begin_popup_context_item_with_flags(
+        &self,
+        str_id: Option<&str>,
+        flags: PopupFlags,
+    ) -> Option<PopupToken<'_>> {
+        let render = unsafe {
+            sys::igBeginPopupContextItem(self.scratch_txt_opt(str_id), flags.bits() as i32)
+        };
+
+        if render {
+            Some(PopupToken::new(self))
+        } else {
+            None
+        }
+    }
+
     /// Open and begin popup when clicked on current window.
     ///
     /// This does not take a label, which means that multiple calls will use the same provided label.
@@ -331,6 +482,25 @@ impl Ui {
         }
     }
 
+    /// Open and begin popup when the current window is clicked with the mouse button given in
+    /// `flags`. Pass [`PopupFlags::NO_OPEN_OVER_ITEMS`] to only open when clicking empty space.
+    #[doc(alias = "BeginPopupContextWindow")]
+    pub fn begin_popup_context_window_with_flags(
+        &self,
+        str_id: Option<&str>,
+        flags: PopupFlags,
+    ) -> Option<PopupToken<'_>> {
+        let render = unsafe {
+            sys::igBeginPopupContextWindow(self.scratch_txt_opt(str_id), flags.bits() as i32)
+        };
+
+        if render {
+            Some(PopupToken::new(self))
+        } else {
+            None
+        }
+    }
+
     /// Open and begin popup when right clicked in void (where there are no windows).
     ///
     /// This does not take a label, which means that multiple calls will use the same provided label.
@@ -384,3 +554,71 @@ create_token!(
     /// to drop on its own.
     drop { sys::igEndPopup() }
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_check_and_close_popups() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+
+        let mut frame = |f: &dyn Fn(&Ui)| {
+            let ui = ctx.frame();
+            ui.window("Popups").build(|| f(ui));
+            ctx.render();
+        };
+
+        frame(&|ui| {
+            assert!(!ui.is_any_popup_open());
+            ui.open_popup("outer");
+            assert!(ui.is_popup_open_with_flags("", PopupFlags::ANY_POPUP_ID));
+            let outer = ui.begin_popup("outer").unwrap();
+            assert!(!ui.is_popup_open_with_flags("", PopupFlags::ANY_POPUP_ID));
+            ui.open_popup_id(ui.new_id_str("inner"), PopupFlags::empty());
+            ui.popup("inner", || {});
+            outer.end();
+        });
+        frame(&|ui| {
+            assert!(ui.is_popup_open("outer"));
+            assert!(!ui.is_popup_open("inner"));
+            ui.popup("outer", || assert!(ui.is_popup_open("inner")));
+            assert_eq!(ui.open_popup_count(), 2);
+            assert!(!ui.is_any_modal_popup_open());
+
+            ui.close_popups_to_level(1, false);
+            assert_eq!(ui.open_popup_count(), 1);
+            ui.close_popups_to_level(1, false);
+            assert_eq!(ui.open_popup_count(), 1);
+
+            ui.open_popup("modal");
+            ui.modal_popup("modal", || {});
+        });
+        frame(&|ui| {
+            ui.modal_popup("modal", || {});
+            assert!(ui.is_any_modal_popup_open());
+            ui.close_popups_to_level(0, false);
+            assert!(!ui.is_any_popup_open());
+        });
+    }
+
+    #[test]
+    fn test_mouse_button_flags() {
+        assert_eq!(
+            PopupFlags::mouse_button(MouseButton::Right),
+            PopupFlags::MOUSE_BUTTON_RIGHT
+        );
+        assert_eq!(
+            PopupFlags::mouse_button(MouseButton::Middle) | PopupFlags::NO_REOPEN,
+            PopupFlags::MOUSE_BUTTON_MIDDLE | PopupFlags::NO_REOPEN
+        );
+        assert_eq!(
+            PopupFlags::mouse_button(MouseButton::Left),
+            PopupFlags::empty()
+        );
+        assert_eq!(PopupFlags::mouse_button(MouseButton::Extra1).bits(), 3);
+        assert_eq!(PopupFlags::mouse_button(MouseButton::Extra2).bits(), 4);
+        let flags = PopupFlags::mouse_button(MouseButton::Extra2) | PopupFlags::NO_REOPEN;
+        assert_eq!((flags & PopupFlags::MOUSE_BUTTON_MASK).bits(), 4);
+    }
+}
@@ -0,0 +1,513 @@
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::drag_drop::DragDropSourceToolTip;
+use crate::{DragDropFlags, StyleColor, Ui};
+
+/// What changed when items were dropped on a [ReorderableList], as returned by
+/// [ReorderableListToken::end].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReorderEvent {
+    /// Items of the list were moved within it. `from` are their former indices, in ascending
+    /// order, and they are now at `to..to + from.len()`.
+    Moved { from: Vec<usize>, to: usize },
+    /// Items of another list in the same group were dropped on this one. They haven't been
+    /// moved yet, see [ListMove::apply].
+    Received(ListMove),
+}
+
+/// Items dragged from one [ReorderableList] to another, see [ReorderEvent::Received].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListMove {
+    /// The name of the list the items come from.
+    pub source: String,
+    /// The indices of the items in that list, in ascending order.
+    pub indices: Vec<usize>,
+    /// Where the items go in the list they were dropped on.
+    pub to: usize,
+}
+
+impl ListMove {
+    /// Moves the items from the `source` list to the `target` one, keeping their order. The
+    /// selection of the source [ReorderableList] should be cleared afterwards, since it no
+    /// longer holds the items.
+    pub fn apply<T>(&self, source: &mut Vec<T>, target: &mut Vec<T>) {
+        let moved = remove_indices(source, &self.indices);
+        let to = self.to.min(target.len());
+        target.splice(to..to, moved);
+    }
+}
+
+/// What a [ReorderableList] drags, a handle to which is held by Dear ImGui.
+#[derive(Debug)]
+struct ReorderPayload {
+    list: u64,
+    name: String,
+    indices: Vec<usize>,
+}
+
+/// Tells lists apart, since several can share a name within a group.
+static NEXT_LIST: AtomicU64 = AtomicU64::new(1);
+
+/// Turns a `Vec` of items drawn with [Selectable](crate::Selectable)s or tree nodes into a
+/// list which can be reordered by dragging, e.g. layers or tracks.
+///
+/// Items can be selected with a click, ctrl+click and shift+click, and all selected items are
+/// moved together. A line shows where dragged items will be inserted, and the window scrolls
+/// when they are dragged near its top or bottom edge. Items can also be dragged to another
+/// list in the same [group](Self::group), which is reported as a [ReorderEvent::Received].
+///
+/// The list keeps its selection between frames, so it should be stored alongside the rest of
+/// the UI state.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut layers = vec!["Background", "Terrain", "Characters", "UI"];
+/// let mut list = ReorderableList::new("layers");
+/// // Every frame:
+/// let mut reorder = list.begin(ui, layers.len());
+/// for (index, layer) in layers.iter().enumerate() {
+///     ui.selectable_config(layer)
+///         .selected(reorder.is_selected(index))
+///         .build();
+///     reorder.item(index);
+/// }
+/// if let Some(event) = reorder.end(&mut layers) {
+///     println!("{:?}", event);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ReorderableList {
+    name: String,
+    group: String,
+    id: u64,
+    multi_select: bool,
+    auto_scroll: bool,
+    selection: BTreeSet<usize>,
+    last_clicked: Option<usize>,
+    /// A selected item which was pressed, and becomes the only selected one if it's released
+    /// without being dragged.
+    pending_click: Option<usize>,
+}
+
+impl ReorderableList {
+    /// Creates a new list with the given name, which is also its group.
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        ReorderableList {
+            group: name.clone(),
+            name,
+            id: NEXT_LIST.fetch_add(1, Ordering::Relaxed),
+            multi_select: true,
+            auto_scroll: true,
+            selection: BTreeSet::new(),
+            last_clicked: None,
+            pending_click: None,
+        }
+    }
+
+    /// Sets the group of the list. Items can be dragged between lists in the same group. The
+    /// group is used as the drag and drop payload name, so it must be shorter than 32 bytes.
+    ///
+    /// Default: the name of the list
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = group.into();
+        self
+    }
+
+    /// Sets whether several items can be selected with ctrl+click and shift+click.
+    ///
+    /// Default: `true`
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Sets whether the window scrolls when items are dragged near its top or bottom edge.
+    ///
+    /// Default: `true`
+    pub fn auto_scroll(mut self, auto_scroll: bool) -> Self {
+        self.auto_scroll = auto_scroll;
+        self
+    }
+
+    /// Returns the name of the list.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the indices of the selected items.
+    pub fn selection(&self) -> &BTreeSet<usize> {
+        &self.selection
+    }
+
+    /// Replaces the selected items.
+    pub fn set_selection(&mut self, selection: impl IntoIterator<Item = usize>) {
+        self.selection = selection.into_iter().collect();
+        self.last_clicked = self.selection.first().copied();
+    }
+
+    /// Deselects all items.
+    pub fn clear_selection(&mut self) {
+        self.set_selection([]);
+    }
+
+    /// Starts drawing the list of `len` items. Call [item](ReorderableListToken::item) after
+    /// drawing each item, then [end](ReorderableListToken::end).
+    pub fn begin<'a, 'ui>(&'a mut self, ui: &'ui Ui, len: usize) -> ReorderableListToken<'a, 'ui> {
+        // The list may have been changed elsewhere
+        self.selection.retain(|&index| index < len);
+        self.last_clicked = self.last_clicked.filter(|&index| index < len);
+        self.pending_click = self.pending_click.filter(|&index| index < len);
+        ReorderableListToken {
+            list: self,
+            ui,
+            len,
+            rects: Vec::new(),
+        }
+    }
+
+    fn click(&mut self, index: usize, ctrl: bool, shift: bool) {
+        self.pending_click = None;
+        match self.last_clicked {
+            Some(last) if self.multi_select && shift => {
+                self.selection = (last.min(index)..=last.max(index)).collect();
+            }
+            _ if self.multi_select && ctrl => {
+                if !self.selection.remove(&index) {
+                    self.selection.insert(index);
+                }
+                self.last_clicked = Some(index);
+            }
+            // Keep the selection in case it's dragged
+            _ if self.selection.contains(&index) => {
+                self.pending_click = Some(index);
+                self.last_clicked = Some(index);
+            }
+            _ => {
+                self.selection = BTreeSet::from([index]);
+                self.last_clicked = Some(index);
+            }
+        }
+    }
+
+    fn drop<T>(
+        &mut self,
+        payload: ReorderPayload,
+        to: usize,
+        items: &mut Vec<T>,
+    ) -> Option<ReorderEvent> {
+        let mut indices = payload.indices;
+        indices.sort_unstable();
+        indices.dedup();
+        if payload.list != self.id {
+            let count = indices.len();
+            self.selection = (to..to + count).collect();
+            self.last_clicked = Some(to);
+            return Some(ReorderEvent::Received(ListMove {
+                source: payload.name,
+                indices,
+                to,
+            }));
+        }
+
+        indices.retain(|&index| index < items.len());
+        if indices.is_empty() {
+            return None;
+        }
+        let to = to - indices.iter().filter(|&&index| index < to).count();
+        let moved = remove_indices(items, &indices);
+        let count = moved.len();
+        items.splice(to..to, moved);
+        self.selection = (to..to + count).collect();
+        self.last_clicked = Some(to);
+        if indices.iter().copied().eq(to..to + count) {
+            return None;
+        }
+        Some(ReorderEvent::Moved { from: indices, to })
+    }
+}
+
+/// Tracks the items of a [ReorderableList] while they're drawn, see [ReorderableList::begin].
+#[derive(Debug)]
+pub struct ReorderableListToken<'a, 'ui> {
+    list: &'a mut ReorderableList,
+    ui: &'ui Ui,
+    len: usize,
+    /// Index, min and max of the items drawn so far.
+    rects: Vec<(usize, [f32; 2], [f32; 2])>,
+}
+
+impl<'ui> ReorderableListToken<'_, 'ui> {
+    /// Returns `true` if the item at `index` is selected, to draw it as such.
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.list.selection.contains(&index)
+    }
+
+    /// Makes the last item, e.g. a selectable or a tree node header, the item at `index` of
+    /// the list, which can be clicked to select it and dragged to move it along with the other
+    /// selected items. Items may be skipped, e.g. by a [ListClipper](crate::ListClipper), but
+    /// must be in order.
+    ///
+    /// Returns a handle on the drag tooltip while the item is dragged, to show a preview.
+    pub fn item(&mut self, index: usize) -> Option<DragDropSourceToolTip<'ui>> {
+        let ui = self.ui;
+        self.rects
+            .push((index, ui.item_rect_min(), ui.item_rect_max()));
+        let list = &mut *self.list;
+        if ui.is_item_clicked() {
+            let io = ui.io();
+            list.click(index, io.key_ctrl, io.key_shift);
+        }
+        if ui.is_item_deactivated() && list.pending_click == Some(index) {
+            list.selection = BTreeSet::from([index]);
+            list.pending_click = None;
+        }
+
+        let tooltip = ui
+            .drag_drop_source_config(&list.group)
+            .begin_owned_payload(|| ReorderPayload {
+                list: list.id,
+                name: list.name.clone(),
+                indices: if list.selection.contains(&index) {
+                    list.selection.iter().copied().collect()
+                } else {
+                    vec![index]
+                },
+            });
+        if tooltip.is_some() {
+            list.pending_click = None;
+        }
+        tooltip
+    }
+
+    /// Finishes the list, accepting items dropped on it. Items moved within the list are
+    /// moved in `items`, while items from another list have to be moved with
+    /// [ListMove::apply].
+    pub fn end<T>(self, items: &mut Vec<T>) -> Option<ReorderEvent> {
+        let ui = self.ui;
+        let list = self.list;
+        let spacing = unsafe { ui.style() }.item_spacing[1];
+
+        // An empty list still needs some room to drop onto
+        let (min, max) = if self.rects.is_empty() {
+            ui.dummy([ui.content_region_avail()[0], ui.text_line_height()]);
+            (ui.item_rect_min(), ui.item_rect_max())
+        } else {
+            let left = self
+                .rects
+                .iter()
+                .map(|(_, min, _)| min[0])
+                .fold(f32::MAX, f32::min);
+            let right = self
+                .rects
+                .iter()
+                .map(|(_, _, max)| max[0])
+                .fold(f32::MIN, f32::max);
+            let top = self.rects[0].1[1] - spacing * 0.5;
+            let bottom = ui.cursor_screen_pos()[1];
+            ([left, top], [right, bottom])
+        };
+
+        if list.auto_scroll {
+            let dragging = ui
+                .drag_drop_payload()
+                .is_some_and(|payload| payload.is_data_type(&list.group));
+            if dragging {
+                auto_scroll(ui);
+            }
+        }
+
+        // Insert before the first item whose middle is below the mouse
+        let mouse_y = ui.io().mouse_pos[1];
+        let (to, line_y) = match self
+            .rects
+            .iter()
+            .find(|(_, min, max)| mouse_y < (min[1] + max[1]) * 0.5)
+        {
+            Some(&(index, min, _)) => (index, min[1] - spacing * 0.5),
+            None => match self.rects.last() {
+                Some(&(index, _, max)) => (index + 1, max[1] + spacing * 0.5),
+                None => (0, min[1]),
+            },
+        };
+        let to = to.min(self.len);
+
+        let id = ui.new_id_u64(list.id);
+        let target = ui.drag_drop_target_custom(min, max, id)?;
+        let flags =
+            DragDropFlags::ACCEPT_BEFORE_DELIVERY | DragDropFlags::ACCEPT_NO_DRAW_DEFAULT_RECT;
+        let Some(Ok(payload)) =
+            target.accept_owned_payload::<ReorderPayload, _>(&list.group, flags)
+        else {
+            return None;
+        };
+        ui.get_window_draw_list()
+            .add_line(
+                [min[0], line_y],
+                [max[0], line_y],
+                ui.style_color(StyleColor::DragDropTarget),
+            )
+            .thickness(2.0)
+            .build();
+        list.drop(payload.data?, to, items)
+    }
+}
+
+/// Scrolls the current window when the mouse is near its top or bottom edge, faster the closer
+/// it is.
+fn auto_scroll(ui: &Ui) {
+    let io = ui.io();
+    let [x, y] = io.mouse_pos;
+    let pos = ui.window_pos();
+    let size = ui.window_size();
+    if x < pos[0] || x > pos[0] + size[0] {
+        return;
+    }
+    let zone = ui.text_line_height_with_spacing() * 2.0;
+    let speed = zone * 10.0 * io.delta_time;
+    let top = y - pos[1];
+    let bottom = pos[1] + size[1] - y;
+    if (0.0..zone).contains(&top) {
+        let scroll = ui.scroll_y() - speed * (1.0 - top / zone);
+        ui.set_scroll_y(scroll.max(0.0));
+    } else if (0.0..zone).contains(&bottom) {
+        let scroll = ui.scroll_y() + speed * (1.0 - bottom / zone);
+        ui.set_scroll_y(scroll.min(ui.scroll_max_y()));
+    }
+}
+
+/// Removes the items at the sorted `indices`, returning them in order. Indices past the end are
+/// ignored.
+fn remove_indices<T>(items: &mut Vec<T>, indices: &[usize]) -> Vec<T> {
+    let mut removed = Vec::with_capacity(indices.len());
+    for &index in indices.iter().rev() {
+        if index < items.len() {
+            removed.push(items.remove(index));
+        }
+    }
+    removed.reverse();
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Condition, MouseButton};
+
+    #[test]
+    fn test_moves_selection() {
+        let mut list = ReorderableList::new("layers");
+        let mut items = vec!['a', 'b', 'c', 'd', 'e'];
+        list.click(0, false, false);
+        list.click(2, true, false);
+        assert_eq!(list.selection, BTreeSet::from([0, 2]));
+
+        let payload = |list: &ReorderableList| ReorderPayload {
+            list: list.id,
+            name: list.name.clone(),
+            indices: list.selection.iter().copied().collect(),
+        };
+        let event = list.drop(payload(&list), 4, &mut items);
+        assert_eq!(items, ['b', 'd', 'a', 'c', 'e']);
+        assert_eq!(
+            event,
+            Some(ReorderEvent::Moved {
+                from: vec![0, 2],
+                to: 2
+            })
+        );
+        assert_eq!(list.selection, BTreeSet::from([2, 3]));
+        // Dropping them where they already are changes nothing
+        assert_eq!(list.drop(payload(&list), 2, &mut items), None);
+        assert_eq!(items, ['b', 'd', 'a', 'c', 'e']);
+
+        // Between lists, the receiving list reports the move
+        let mut other = ReorderableList::new("other").group("layers");
+        let mut other_items = vec!['x', 'y'];
+        let event = other.drop(payload(&list), 1, &mut other_items);
+        let Some(ReorderEvent::Received(moved)) = event else {
+            panic!("unexpected event {:?}", event);
+        };
+        assert_eq!(moved.source, "layers");
+        moved.apply(&mut items, &mut other_items);
+        assert_eq!(items, ['b', 'd', 'e']);
+        assert_eq!(other_items, ['x', 'a', 'c', 'y']);
+        assert_eq!(other.selection, BTreeSet::from([1, 2]));
+    }
+
+    #[test]
+    fn test_stale_click_is_forgotten() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut list = ReorderableList::new("layers");
+        list.set_selection([4]);
+        let ui = ctx.frame();
+        // The list shrank since the last click
+        list.begin(ui, 2).end(&mut vec!['a', 'b']);
+        ctx.render();
+        list.click(1, false, true);
+        assert_eq!(list.selection, BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn test_drag_item_below_another() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut list = ReorderableList::new("layers");
+        let mut items = vec!["a", "b", "c", "d"];
+        let mut events = Vec::new();
+
+        // Returns the positions of the items
+        let mut frame = |ctx: &mut crate::Context, mouse: Option<([f32; 2], bool)>| {
+            if let Some((pos, down)) = mouse {
+                ctx.io_mut().add_mouse_pos_event(pos);
+                ctx.io_mut().add_mouse_button_event(MouseButton::Left, down);
+            }
+            let mut rects = Vec::new();
+            let ui = ctx.frame();
+            ui.window("list")
+                .position([0.0, 0.0], Condition::Always)
+                .size([200.0, 200.0], Condition::Always)
+                .build(|| {
+                    let mut reorder = list.begin(ui, items.len());
+                    for (index, item) in items.iter().enumerate() {
+                        ui.selectable_config(item)
+                            .selected(reorder.is_selected(index))
+                            .build();
+                        rects.push(ui.item_rect_min());
+                        reorder.item(index);
+                    }
+                    events.extend(reorder.end(&mut items));
+                });
+            ctx.render();
+            rects
+        };
+
+        let rects = frame(&mut ctx, None);
+        let from = [rects[0][0] + 4.0, rects[0][1] + 4.0];
+        // The lower half of "c"
+        let to = [rects[2][0] + 4.0, rects[2][1] + 10.0];
+        frame(&mut ctx, Some((from, false)));
+        frame(&mut ctx, Some((from, true)));
+        for step in 1..=4 {
+            let t = step as f32 / 4.0;
+            let pos = [from[0], from[1] + (to[1] - from[1]) * t];
+            frame(&mut ctx, Some((pos, true)));
+        }
+        frame(&mut ctx, Some((to, false)));
+        frame(&mut ctx, None);
+
+        assert_eq!(items, ["b", "c", "a", "d"]);
+        assert_eq!(
+            events,
+            [ReorderEvent::Moved {
+                from: vec![0],
+                to: 2
+            }]
+        );
+        assert_eq!(list.selection, BTreeSet::from([2]));
+    }
+}
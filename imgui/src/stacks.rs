@@ -1,9 +1,10 @@
 use crate::fonts::atlas::FontId;
 use crate::internal::RawCast;
 use crate::math::MintVec4;
-use crate::style::{StyleColor, StyleVar};
+use crate::style::{StyleColor, StyleOverride, StyleVar};
 use crate::sys;
 use crate::Ui;
+use std::marker::PhantomData;
 use std::os::raw::c_char;
 
 /// # Parameter stacks (shared)
@@ -84,6 +85,40 @@ impl Ui {
         unsafe { push_style_var(style_var) };
         StyleStackToken::new(self)
     }
+
+    /// Applies every style variable and color in a [`StyleOverride`] by pushing them to the
+    /// style and color stacks.
+    ///
+    /// Returns a `StyleOverrideToken` which pops all of the changes at once when `.pop()` is
+    /// called or when it is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use imgui::*;
+    /// # let mut ctx = Context::create();
+    /// # let ui = ctx.frame();
+    /// let subtle = StyleOverride::new()
+    ///     .var(StyleVar::Alpha(0.5))
+    ///     .color(StyleColor::Text, [0.7, 0.7, 0.7, 1.0]);
+    /// let style = ui.push_style_override(&subtle);
+    /// ui.text("I'm subtle!");
+    /// style.pop();
+    /// ```
+    #[doc(alias = "PushStyleVar", alias = "PushStyleColor")]
+    pub fn push_style_override(&self, style_override: &StyleOverride) -> StyleOverrideToken<'_> {
+        for &style_var in style_override.vars() {
+            unsafe { push_style_var(style_var) };
+        }
+        for &(style_color, color) in style_override.colors() {
+            unsafe { sys::igPushStyleColor_Vec4(style_color as i32, color.into()) };
+        }
+        StyleOverrideToken {
+            vars: style_override.vars().len() as i32,
+            colors: style_override.colors().len() as i32,
+            _phantom: PhantomData,
+        }
+    }
 }
 
 create_token!(
@@ -134,12 +169,46 @@ impl StyleStackToken<'_> {
     }
 }
 
+/// Tracks the changes made by [`Ui::push_style_override`], which are all popped by calling
+/// `.pop()` or by dropping.
+#[must_use]
+#[derive(Debug)]
+pub struct StyleOverrideToken<'ui> {
+    vars: i32,
+    colors: i32,
+    _phantom: PhantomData<&'ui Ui>,
+}
+
+impl StyleOverrideToken<'_> {
+    /// Pops all changes from the style and color stacks.
+    #[inline]
+    pub fn end(self) {
+        // left empty for drop
+    }
+
+    /// Pops all changes from the style and color stacks.
+    pub fn pop(self) {
+        self.end()
+    }
+}
+
+impl Drop for StyleOverrideToken<'_> {
+    #[doc(alias = "PopStyleVar", alias = "PopStyleColor")]
+    fn drop(&mut self) {
+        unsafe {
+            sys::igPopStyleVar(self.vars);
+            sys::igPopStyleColor(self.colors);
+        }
+    }
+}
+
 #[inline]
 unsafe fn push_style_var(style_var: StyleVar) {
     use crate::style::StyleVar::*;
     use crate::sys::{igPushStyleVar_Float, igPushStyleVar_Vec2};
     match style_var {
         Alpha(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_Alpha as i32, v),
+        DisabledAlpha(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_DisabledAlpha as i32, v),
         WindowPadding(v) => igPushStyleVar_Vec2(sys::ImGuiStyleVar_WindowPadding as i32, v.into()),
         WindowRounding(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_WindowRounding as i32, v),
         WindowBorderSize(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_WindowBorderSize as i32, v),
@@ -166,6 +235,18 @@ unsafe fn push_style_var(style_var: StyleVar) {
         GrabMinSize(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_GrabMinSize as i32, v),
         GrabRounding(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_GrabRounding as i32, v),
         TabRounding(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_TabRounding as i32, v),
+        TabBorderSize(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_TabBorderSize as i32, v),
+        TabBarBorderSize(v) => igPushStyleVar_Float(sys::ImGuiStyleVar_TabBarBorderSize as i32, v),
+        TabBarOverlineSize(v) => {
+            igPushStyleVar_Float(sys::ImGuiStyleVar_TabBarOverlineSize as i32, v)
+        }
+        TableAngledHeadersAngle(v) => {
+            igPushStyleVar_Float(sys::ImGuiStyleVar_TableAngledHeadersAngle as i32, v)
+        }
+        TableAngledHeadersTextAlign(v) => igPushStyleVar_Vec2(
+            sys::ImGuiStyleVar_TableAngledHeadersTextAlign as i32,
+            v.into(),
+        ),
         ButtonTextAlign(v) => {
             igPushStyleVar_Vec2(sys::ImGuiStyleVar_ButtonTextAlign as i32, v.into())
         }
@@ -173,6 +254,19 @@ unsafe fn push_style_var(style_var: StyleVar) {
             igPushStyleVar_Vec2(sys::ImGuiStyleVar_SelectableTextAlign as i32, v.into())
         }
         CellPadding(v) => igPushStyleVar_Vec2(sys::ImGuiStyleVar_CellPadding as i32, v.into()),
+        SeparatorTextBorderSize(v) => {
+            igPushStyleVar_Float(sys::ImGuiStyleVar_SeparatorTextBorderSize as i32, v)
+        }
+        SeparatorTextAlign(v) => {
+            igPushStyleVar_Vec2(sys::ImGuiStyleVar_SeparatorTextAlign as i32, v.into())
+        }
+        SeparatorTextPadding(v) => {
+            igPushStyleVar_Vec2(sys::ImGuiStyleVar_SeparatorTextPadding as i32, v.into())
+        }
+        #[cfg(feature = "docking")]
+        DockingSeparatorSize(v) => {
+            igPushStyleVar_Float(sys::ImGuiStyleVar_DockingSeparatorSize as i32, v)
+        }
     }
 }
 
@@ -422,6 +516,16 @@ impl Ui {
         IdStackToken::new(self)
     }
 
+    /// Pushes a `u64` to the ID stack, hashing all of its bits even where `usize` is 32 bits.
+    pub(crate) fn push_id_u64(&self, id: u64) -> IdStackToken<'_> {
+        let bytes = id.to_le_bytes();
+        unsafe {
+            let start = bytes.as_ptr() as *const c_char;
+            sys::igPushID_StrStr(start, start.add(bytes.len()))
+        }
+        IdStackToken::new(self)
+    }
+
     /// Pushes an `i32` to the ID stack.
     ///
     /// Returns an `IdStackToken` that can be popped by calling `.end()`
@@ -3,6 +3,7 @@ use std::fmt;
 use std::ops::{Index, IndexMut};
 
 use crate::internal::RawCast;
+use crate::math::MintVec4;
 use crate::Direction;
 use crate::{sys, HoveredFlags};
 
@@ -918,6 +919,8 @@ impl std::error::Error for InvalidStyleColorValue {}
 pub enum StyleVar {
     /// Global alpha applies to everything
     Alpha(f32),
+    /// Additional alpha multiplier applied to disabled elements
+    DisabledAlpha(f32),
     /// Padding within a window
     WindowPadding([f32; 2]),
     /// Rounding radius of window corners
@@ -959,12 +962,128 @@ pub enum StyleVar {
     GrabRounding(f32),
     /// Rounding radius of upper corners of tabs
     TabRounding(f32),
+    /// Thickness of border around tabs
+    TabBorderSize(f32),
+    /// Thickness of tab-bar separator
+    TabBarBorderSize(f32),
+    /// Thickness of tab-bar overline
+    TabBarOverlineSize(f32),
+    /// Angle of angled table headers, in radians
+    TableAngledHeadersAngle(f32),
+    /// Alignment of angled table headers within the cell
+    TableAngledHeadersTextAlign([f32; 2]),
     /// Alignment of button text when button is larger than text
     ButtonTextAlign([f32; 2]),
     /// Alignment of selectable text when selectable is larger than text
     SelectableTextAlign([f32; 2]),
     /// Padding within a table cell
     CellPadding([f32; 2]),
+    /// Thickness of border in [`Ui::separator_with_text`](crate::Ui::separator_with_text)
+    SeparatorTextBorderSize(f32),
+    /// Alignment of text within the separator
+    SeparatorTextAlign([f32; 2]),
+    /// Horizontal offset of text from each edge of the separator + spacing on other axis
+    SeparatorTextPadding([f32; 2]),
+    /// Thickness of resizing border between docked windows
+    #[cfg(feature = "docking")]
+    DockingSeparatorSize(f32),
+}
+
+/// A partial set of style variables and colors which can be applied all at once with
+/// [`Ui::push_style_override`](crate::Ui::push_style_override).
+///
+/// This is useful for components which want to carry their own look without touching
+/// the global [`Style`]. Only the variables and colors that were set are changed; everything
+/// else keeps its current value.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let danger = StyleOverride::new()
+///     .var(StyleVar::FrameRounding(4.0))
+///     .color(StyleColor::Button, [0.8, 0.1, 0.1, 1.0])
+///     .color(StyleColor::ButtonHovered, [0.9, 0.2, 0.2, 1.0]);
+///
+/// let _style = ui.push_style_override(&danger);
+/// ui.button("Delete");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleOverride {
+    vars: Vec<StyleVar>,
+    colors: Vec<(StyleColor, [f32; 4])>,
+}
+
+impl StyleOverride {
+    /// Creates an empty override, which changes nothing when pushed.
+    pub const fn new() -> Self {
+        Self {
+            vars: Vec::new(),
+            colors: Vec::new(),
+        }
+    }
+
+    /// Sets a style variable. Setting the same variable twice keeps the last value.
+    pub fn var(mut self, style_var: StyleVar) -> Self {
+        self.set_var(style_var);
+        self
+    }
+
+    /// Sets a style color. Setting the same color twice keeps the last value.
+    pub fn color(mut self, style_color: StyleColor, color: impl Into<MintVec4>) -> Self {
+        self.set_color(style_color, color);
+        self
+    }
+
+    /// Sets a style variable in place. Setting the same variable twice keeps the last value.
+    pub fn set_var(&mut self, style_var: StyleVar) {
+        let discriminant = std::mem::discriminant(&style_var);
+        match self
+            .vars
+            .iter_mut()
+            .find(|v| std::mem::discriminant(*v) == discriminant)
+        {
+            Some(existing) => *existing = style_var,
+            None => self.vars.push(style_var),
+        }
+    }
+
+    /// Sets a style color in place. Setting the same color twice keeps the last value.
+    pub fn set_color(&mut self, style_color: StyleColor, color: impl Into<MintVec4>) {
+        let color: [f32; 4] = color.into().into();
+        match self.colors.iter_mut().find(|(c, _)| *c == style_color) {
+            Some(existing) => existing.1 = color,
+            None => self.colors.push((style_color, color)),
+        }
+    }
+
+    /// Adds every variable and color of `other` to this override, with the values in `other`
+    /// taking precedence.
+    pub fn extend(&mut self, other: &StyleOverride) {
+        for &style_var in &other.vars {
+            self.set_var(style_var);
+        }
+        for &(style_color, color) in &other.colors {
+            self.set_color(style_color, color);
+        }
+    }
+
+    /// The style variables set in this override
+    pub fn vars(&self) -> &[StyleVar] {
+        &self.vars
+    }
+
+    /// The style colors set in this override
+    pub fn colors(&self) -> &[(StyleColor, [f32; 4])] {
+        &self.colors
+    }
+
+    /// Returns `true` if this override changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty() && self.colors.is_empty()
+    }
 }
 
 // lerps a color with the given value
@@ -1027,6 +1146,42 @@ mod tests {
         assert_eq!(style.cell_padding, [58.0, 60.0]);
     }
 
+    #[test]
+    fn test_style_override_keeps_last_value() {
+        let mut style_override = StyleOverride::new()
+            .var(StyleVar::Alpha(0.5))
+            .var(StyleVar::FramePadding([1.0, 2.0]))
+            .var(StyleVar::Alpha(0.25))
+            .color(StyleColor::Text, [1.0, 0.0, 0.0, 1.0]);
+        style_override.extend(&StyleOverride::new().color(StyleColor::Text, [0.0, 1.0, 0.0, 1.0]));
+        assert_eq!(
+            style_override.vars(),
+            &[StyleVar::Alpha(0.25), StyleVar::FramePadding([1.0, 2.0])]
+        );
+        assert_eq!(
+            style_override.colors(),
+            &[(StyleColor::Text, [0.0, 1.0, 0.0, 1.0])]
+        );
+    }
+
+    #[test]
+    fn test_push_style_override() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let ui = ctx.frame();
+        let before = ui.clone_style();
+        let style_override = StyleOverride::new()
+            .var(StyleVar::DisabledAlpha(0.1))
+            .var(StyleVar::SeparatorTextPadding([5.0, 6.0]))
+            .color(StyleColor::Border, [0.1, 0.2, 0.3, 0.4]);
+        let token = ui.push_style_override(&style_override);
+        let pushed = ui.clone_style();
+        assert_eq!(pushed.disabled_alpha, 0.1);
+        assert_eq!(pushed.separator_text_padding, [5.0, 6.0]);
+        assert_eq!(pushed[StyleColor::Border], [0.1, 0.2, 0.3, 0.4]);
+        token.pop();
+        assert_eq!(ui.clone_style(), before);
+    }
+
     #[test]
     fn test_style_color_indexing() {
         let (_guard, ctx) = crate::test::test_ctx();
@@ -1142,7 +1297,7 @@ mod tests {
 
             println!("Checking {}..", StyleColor::try_from(i).unwrap());
 
-            for (imgui_color, our_color) in imgui_color.into_iter().zip(our_color.into_iter()) {
+            for (imgui_color, our_color) in imgui_color.into_iter().zip(our_color) {
                 approx::assert_abs_diff_eq!(imgui_color, our_color, epsilon = 0.01);
             }
         }
@@ -1155,7 +1310,7 @@ mod tests {
             println!("Checking {}..", StyleColor::try_from(i).unwrap());
             println!("{:?} vs {:?}", imgui_color, our_color);
 
-            for (imgui_color, our_color) in imgui_color.into_iter().zip(our_color.into_iter()) {
+            for (imgui_color, our_color) in imgui_color.into_iter().zip(our_color) {
                 approx::assert_abs_diff_eq!(imgui_color, our_color, epsilon = 0.01);
             }
         }
@@ -1167,7 +1322,7 @@ mod tests {
 
             println!("Checking {}..", StyleColor::try_from(i).unwrap());
 
-            for (imgui_color, our_color) in imgui_color.into_iter().zip(our_color.into_iter()) {
+            for (imgui_color, our_color) in imgui_color.into_iter().zip(our_color) {
                 approx::assert_abs_diff_eq!(imgui_color, our_color, epsilon = 0.01);
             }
         }
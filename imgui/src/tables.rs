@@ -145,6 +145,11 @@ bitflags! {
         const SORT_MULTI = sys::ImGuiTableFlags_SortMulti;
         /// Allow no sorting, disable default sorting. `table_get_sort_specs` may return specs where `[specs_count] == 0`.
         const SORT_TRISTATE = sys::ImGuiTableFlags_SortTristate;
+
+        // Miscellaneous
+
+        /// Highlight column headers when hovered (may evolve into a fuller highlight).
+        const HIGHLIGHT_HOVERED_COLUMN = sys::ImGuiTableFlags_HighlightHoveredColumn;
     }
 }
 
@@ -165,6 +170,9 @@ bitflags! {
     pub struct TableColumnFlags: u32 {
         // Input configuration flags
 
+        /// Overriding/master disable flag: hide column, won't show in context menu (unlike calling
+        /// [table_set_enabled](crate::Ui::table_set_enabled) which manipulates the user accessible state).
+        const DISABLED = sys::ImGuiTableColumnFlags_Disabled;
         /// Default as a hidden/disabled column.
         const DEFAULT_HIDE = sys::ImGuiTableColumnFlags_DefaultHide;
         /// Default as a sorting column.
@@ -195,6 +203,9 @@ bitflags! {
         const NO_SORT_DESCENDING = sys::ImGuiTableColumnFlags_NoSortDescending;
         /// Disable header text width contribution to automatic column width.
         const NO_HEADER_WIDTH = sys::ImGuiTableColumnFlags_NoHeaderWidth;
+        /// Don't submit the column name in the header row. Useful for small columns; the name
+        /// will still be shown in the context menu.
+        const NO_HEADER_LABEL = sys::ImGuiTableColumnFlags_NoHeaderLabel;
         /// Make the initial sort direction Ascending when first sorting on this column (default).
         const PREFER_SORT_ASCENDING = sys::ImGuiTableColumnFlags_PreferSortAscending;
         /// Make the initial sort direction Descending when first sorting on this column.
@@ -204,6 +215,9 @@ bitflags! {
         /// Ignore current Indent value when entering cell (default for columns > 0).
         /// Indentation changes _within_ the cell will still be honored.
         const INDENT_DISABLE = sys::ImGuiTableColumnFlags_IndentDisable;
+        /// Header is drawn at an angle by
+        /// [table_angled_headers_row](crate::Ui::table_angled_headers_row).
+        const ANGLED_HEADER = sys::ImGuiTableColumnFlags_AngledHeader;
 
         // Output status flags, read-only via [table_get_column_flags]
 
@@ -594,6 +608,18 @@ impl Ui {
         }
     }
 
+    /// Submits a row with angled headers for every column with the
+    /// [TableColumnFlags::ANGLED_HEADER] flag. This must be the first row of the table.
+    ///
+    /// The angle is set with [StyleVar::TableAngledHeadersAngle](crate::StyleVar::TableAngledHeadersAngle).
+    /// Columns with angled headers typically also have [TableColumnFlags::NO_HEADER_LABEL], so
+    /// their name isn't shown again in the regular [table_headers_row](Self::table_headers_row).
+    pub fn table_angled_headers_row(&self) {
+        unsafe {
+            sys::igTableAngledHeadersRow();
+        }
+    }
+
     /// Use this function to manually declare a column cell to be a header.
     ///
     /// You generally should avoid using this outside of specific cases,
@@ -620,6 +646,21 @@ impl Ui {
         unsafe { sys::igTableGetRowIndex() as usize }
     }
 
+    /// Gets the index of the column hovered by the mouse in the current table, or `None` if
+    /// no column is hovered.
+    ///
+    /// Unlike checking [TableColumnFlags::IS_HOVERED] on each column, this can be called
+    /// anywhere within the table.
+    pub fn table_hovered_column(&self) -> Option<usize> {
+        let column = unsafe { sys::igTableGetHoveredColumn() };
+        // imgui returns the column count when the unused space right of the columns is hovered
+        if column < 0 || column >= unsafe { sys::igTableGetColumnCount() } {
+            None
+        } else {
+            Some(column as usize)
+        }
+    }
+
     /// Gets the name of the current column. If there is no currently bound name
     /// for this column, we will return an empty string.
     ///
@@ -732,6 +773,167 @@ impl Ui {
         unsafe { sys::igTableSetColumnEnabled(column_idx as i32, enabled) }
     }
 
+    /// Gets the current width of the given column in the current table.
+    ///
+    /// Before the first row of a frame, this is the width of the previous frame.
+    pub fn table_column_width(&self, column: usize) -> f32 {
+        self.table_column_state(column).width
+    }
+
+    /// Sets the width of the given column in the current table, as if resized by the user.
+    ///
+    /// This must be called before the layout of the table is locked, which happens on the
+    /// first call to [table_next_row](Self::table_next_row), [table_next_column](Self::table_next_column)
+    /// or [table_headers_row](Self::table_headers_row), and panics otherwise.
+    pub fn table_set_column_width(&self, column: usize, width: f32) {
+        self.assert_valid_column(column);
+        self.assert_layout_unlocked();
+        unsafe { sys::imgui_rs_TableSetColumnWidth(column as i32, width) }
+    }
+
+    /// Gets the display order of the given column in the current table, which differs from its
+    /// index if the user reordered the columns with [TableFlags::REORDERABLE].
+    pub fn table_column_display_order(&self, column: usize) -> usize {
+        self.table_column_state(column).display_order
+    }
+
+    /// Moves the given column in the current table to the given display order, shifting the
+    /// columns in between, as if dragged by the user.
+    ///
+    /// Like [table_set_column_width](Self::table_set_column_width), this must be called before
+    /// the layout of the table is locked.
+    pub fn table_set_column_display_order(&self, column: usize, display_order: usize) {
+        self.assert_valid_column(column);
+        self.assert_layout_unlocked();
+        assert!(
+            display_order < self.table_column_count(),
+            "display order out of range"
+        );
+        unsafe { sys::imgui_rs_TableSetColumnDisplayOrder(column as i32, display_order as i32) }
+    }
+
+    /// Opens the context menu of the current table, the same one the user gets when
+    /// right-clicking a header. Call this from the headers row or the table body.
+    ///
+    /// Use [table_open_context_menu_with_column](Self::table_open_context_menu_with_column)
+    /// to open it for an arbitrary column.
+    pub fn table_open_context_menu(&self) {
+        assert!(self.table_column_count() > 0, "not within a table");
+        unsafe { sys::imgui_rs_TableOpenContextMenu(-1) }
+    }
+
+    /// Opens the context menu of the current table for the given column.
+    pub fn table_open_context_menu_with_column(&self, column: usize) {
+        self.assert_valid_column(column);
+        unsafe { sys::imgui_rs_TableOpenContextMenu(column as i32) }
+    }
+
+    /// Reads back the user adjustable state of every column in the current table: their
+    /// width, display order, visibility and sorting.
+    ///
+    /// Together with [table_apply_column_states](Self::table_apply_column_states), this allows
+    /// saving column presets outside of the `.ini` file.
+    pub fn table_column_states(&self) -> Vec<TableColumnState> {
+        (0..self.table_column_count())
+            .map(|column| self.table_column_state(column))
+            .collect()
+    }
+
+    /// Restores column states read back with [table_column_states](Self::table_column_states),
+    /// matching them to columns by their index in the slice.
+    ///
+    /// This must be called right after setting up the columns, before the layout of the table is
+    /// locked. Visibility is only restored on tables with [TableFlags::HIDEABLE], and sorting
+    /// only if some column in `states` is sorted.
+    pub fn table_apply_column_states(&self, states: &[TableColumnState]) {
+        let states = &states[..states.len().min(self.table_column_count())];
+
+        let flags = TableFlags::from_bits_truncate(unsafe { sys::imgui_rs_TableGetFlags() } as u32);
+        let hideable = flags.contains(TableFlags::HIDEABLE);
+        for (column, state) in states.iter().enumerate() {
+            self.table_set_column_width(column, state.width);
+            if hideable {
+                self.table_set_enabled_with_column(state.enabled, column);
+            }
+        }
+
+        // move columns into place from the left, so later moves don't disturb earlier ones
+        let mut by_order: Vec<_> = states.iter().enumerate().collect();
+        by_order.sort_by_key(|(_, state)| state.display_order);
+        for (display_order, (column, _)) in by_order.into_iter().enumerate() {
+            self.table_set_column_display_order(column, display_order);
+        }
+
+        let mut sorted: Vec<_> = states
+            .iter()
+            .enumerate()
+            .filter_map(|(column, state)| state.sort.map(|(dir, order)| (order, dir, column)))
+            .collect();
+        sorted.sort_by_key(|&(order, _, _)| order);
+        for (i, (_, direction, column)) in sorted.into_iter().enumerate() {
+            let direction = match direction {
+                TableSortDirection::Ascending => sys::ImGuiSortDirection_Ascending,
+                TableSortDirection::Descending => sys::ImGuiSortDirection_Descending,
+            };
+            unsafe {
+                sys::imgui_rs_TableSetColumnSortDirection(column as i32, direction as i32, i > 0)
+            }
+        }
+    }
+
+    fn table_column_state(&self, column: usize) -> TableColumnState {
+        self.assert_valid_column(column);
+
+        let mut user_id = 0;
+        let mut width = 0.0;
+        let mut display_order = 0;
+        let mut enabled = false;
+        let mut sort_order = 0;
+        let mut sort_direction = 0;
+        unsafe {
+            sys::imgui_rs_TableGetColumnState(
+                column as i32,
+                &mut user_id,
+                &mut width,
+                &mut display_order,
+                &mut enabled,
+                &mut sort_order,
+                &mut sort_direction,
+            );
+        }
+
+        let direction = match sort_direction as sys::ImGuiSortDirection {
+            sys::ImGuiSortDirection_Ascending => Some(TableSortDirection::Ascending),
+            sys::ImGuiSortDirection_Descending => Some(TableSortDirection::Descending),
+            _ => None,
+        };
+        let sort = direction.filter(|_| sort_order >= 0);
+
+        TableColumnState {
+            user_id: Id(user_id),
+            width,
+            display_order: display_order as usize,
+            enabled,
+            sort: sort.map(|direction| (direction, sort_order as usize)),
+        }
+    }
+
+    /// imgui asserts on bad columns from C, which aborts instead of unwinding, so check here.
+    fn assert_valid_column(&self, column: usize) {
+        assert!(
+            column < self.table_column_count(),
+            "column {} out of range, or not within a table",
+            column
+        );
+    }
+
+    fn assert_layout_unlocked(&self) {
+        assert!(
+            unsafe { !sys::imgui_rs_TableIsLayoutLocked() },
+            "the table layout is locked, columns must be changed before the first row"
+        );
+    }
+
     /// Gets the sorting data for a table. This will be `None` when not sorting.
     ///
     /// See the examples folder for how to use the sorting API.
@@ -772,6 +974,25 @@ impl<Name: AsRef<str>> TableColumnSetup<Name> {
     }
 }
 
+/// The state of a table column which the user can change, as read back by
+/// [table_column_states](Ui::table_column_states) and restored by
+/// [table_apply_column_states](Ui::table_apply_column_states).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TableColumnState {
+    /// The user id given to the column when it was set up, which can be used to match saved
+    /// states to columns if the columns of a table change.
+    pub user_id: Id,
+    /// The width of the column.
+    pub width: f32,
+    /// The position the column is displayed at.
+    pub display_order: usize,
+    /// Whether the column is shown, i.e. not hidden by the user.
+    pub enabled: bool,
+    /// The sort direction of the column and its index within the sort specs, if the table is
+    /// sorted on this column.
+    pub sort: Option<(TableSortDirection, usize)>,
+}
+
 /// A wrapper around table sort specs.
 ///
 /// To use this simply, use [conditional_sort] and provide a closure --
@@ -885,3 +1106,82 @@ create_token!(
     /// Ends the table.
     drop { sys::igEndTable() }
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_column_states_round_trip() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let flags = TableFlags::RESIZABLE
+            | TableFlags::REORDERABLE
+            | TableFlags::HIDEABLE
+            | TableFlags::SORTABLE
+            | TableFlags::SIZING_FIXED_FIT;
+
+        let saved = vec![
+            TableColumnState {
+                user_id: Id(0),
+                width: 50.0,
+                display_order: 2,
+                enabled: true,
+                sort: None,
+            },
+            TableColumnState {
+                user_id: Id(0),
+                width: 60.0,
+                display_order: 0,
+                enabled: false,
+                sort: None,
+            },
+            TableColumnState {
+                user_id: Id(0),
+                width: 70.0,
+                display_order: 1,
+                enabled: true,
+                sort: Some((TableSortDirection::Descending, 0)),
+            },
+        ];
+
+        let mut states = Vec::new();
+        for frame in 0..3 {
+            let ui = ctx.new_frame();
+            ui.window("table").build(|| {
+                let _t = ui.begin_table_with_flags("t", 3, flags).unwrap();
+                for name in ["a", "b", "c"] {
+                    ui.table_setup_column(name);
+                }
+                if frame == 0 {
+                    ui.table_apply_column_states(&saved);
+                }
+                states = ui.table_column_states();
+                ui.table_headers_row();
+            });
+            ctx.render();
+        }
+
+        for (state, saved) in states.iter().zip(&saved) {
+            assert_eq!(state.width, saved.width);
+            assert_eq!(state.display_order, saved.display_order);
+            assert_eq!(state.enabled, saved.enabled);
+            assert_eq!(state.sort, saved.sort);
+        }
+    }
+
+    #[test]
+    fn test_column_edits_after_layout_lock_panic() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let ui = ctx.new_frame();
+        ui.window("table").build(|| {
+            let _t = ui.begin_table("t", 2).unwrap();
+            ui.table_set_column_width(0, 40.0);
+            ui.table_next_row();
+            let panics =
+                |f: &dyn Fn()| std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).is_err();
+            assert!(panics(&|| ui.table_set_column_width(0, 50.0)));
+            assert!(panics(&|| ui.table_set_column_display_order(0, 1)));
+        });
+        ctx.render();
+    }
+}
@@ -1,5 +1,7 @@
 use crate::sys;
+use crate::text_match::{chars_eq_ignore_case, fuzzy_match_into};
 use crate::Ui;
+use std::ops::Range;
 use std::ptr;
 
 /// Helper to parse and apply text filters
@@ -37,21 +39,22 @@ impl TextFilter {
     }
 
     /// Draws an [InputText](crate::input_widget::InputText) to control the filter of the TextFilter.
+    /// Returns `true` if the filter was edited.
     ///
     /// This is equivalent of [draw_with_size](Self::draw_with_size) with `size` set to `0.0`.
-    pub fn draw(&self) {
-        self.draw_with_size(0.0);
+    pub fn draw(&self) -> bool {
+        self.draw_with_size(0.0)
     }
 
     /// Draws an [InputText](crate::input_widget::InputText) to control the filter of the TextFilter.
     ///
-    /// The InputText has the size passed in parameters.
-    pub fn draw_with_size(&self, size: f32) {
+    /// The InputText has the size passed in parameters. Returns `true` if the filter was edited.
+    pub fn draw_with_size(&self, size: f32) -> bool {
         unsafe {
             let mut id = self.id.clone();
             id.push('\0');
             let ptr = id.as_mut_ptr();
-            sys::ImGuiTextFilter_Draw(self.raw, ptr as *mut core::ffi::c_char, size);
+            sys::ImGuiTextFilter_Draw(self.raw, ptr as *mut core::ffi::c_char, size)
         }
     }
 
@@ -104,3 +107,426 @@ impl Ui {
         TextFilter::new_with_filter(label, filter)
     }
 }
+
+/// How the terms of a [SearchFilter] are matched.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SearchFilterMode {
+    /// Text which contains the term, like [TextFilter].
+    #[default]
+    Substring,
+    /// Text which contains the characters of the term in order, e.g. `lgerr` matches
+    /// `log_error`.
+    Fuzzy,
+    /// Text matching the term as a regular expression. Invalid terms match nothing, so a filter
+    /// whose only terms are invalid passes no text, see [SearchFilter::error]. Requires the
+    /// `regex` feature.
+    #[cfg(feature = "regex")]
+    Regex,
+}
+
+#[derive(Clone, Debug)]
+struct SearchTerm {
+    needle: String,
+    exclude: bool,
+    #[cfg(feature = "regex")]
+    regex: Option<regex::Regex>,
+}
+
+/// A text filter with the same `inc,-exc` syntax as [TextFilter], implemented in Rust.
+///
+/// The filter is a comma separated list of terms, with surrounding whitespace ignored. Text
+/// passes if it matches none of the terms starting with `-`, and any of the others, or there
+/// are no others. Use `\,` for a comma inside a term.
+///
+/// Unlike [TextFilter] it works on `&str` directly, without copying every line to a C string,
+/// can be case sensitive, match fuzzily or with regular expressions, and returns the byte
+/// ranges which matched so they can be highlighted.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// # let lines = ["[info] started", "[error] failed"];
+/// let mut filter = SearchFilter::new("Filter");
+/// // Every frame:
+/// filter.draw(ui);
+/// let mut ranges = Vec::new();
+/// for line in lines {
+///     if filter.match_ranges(line, &mut ranges) {
+///         ui.text(line);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SearchFilter {
+    label: String,
+    filter: String,
+    mode: SearchFilterMode,
+    case_sensitive: bool,
+    terms: Vec<SearchTerm>,
+    has_includes: bool,
+    error: Option<String>,
+}
+
+impl SearchFilter {
+    /// Creates a new filter with an empty filter, which passes everything.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self::new_with_filter(label, String::new())
+    }
+
+    /// Creates a new filter with the given filter.
+    pub fn new_with_filter(label: impl Into<String>, filter: impl Into<String>) -> Self {
+        let mut search_filter = SearchFilter {
+            label: label.into(),
+            filter: filter.into(),
+            mode: SearchFilterMode::default(),
+            case_sensitive: false,
+            terms: Vec::new(),
+            has_includes: false,
+            error: None,
+        };
+        search_filter.parse();
+        search_filter
+    }
+
+    /// Sets how terms are matched.
+    ///
+    /// Default: `SearchFilterMode::Substring`
+    pub fn mode(mut self, mode: SearchFilterMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    /// Sets whether terms are matched case sensitively.
+    ///
+    /// Default: `false`
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.set_case_sensitive(case_sensitive);
+        self
+    }
+
+    /// Returns how terms are matched.
+    pub fn current_mode(&self) -> SearchFilterMode {
+        self.mode
+    }
+
+    /// Changes how terms are matched.
+    pub fn set_mode(&mut self, mode: SearchFilterMode) {
+        self.mode = mode;
+        self.parse();
+    }
+
+    /// Returns `true` if terms are matched case sensitively.
+    pub fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Changes whether terms are matched case sensitively.
+    pub fn set_case_sensitive(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+        self.parse();
+    }
+
+    /// Returns the filter, e.g. `error,-debug`.
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Replaces the filter.
+    pub fn set_filter(&mut self, filter: impl Into<String>) {
+        self.filter = filter.into();
+        self.parse();
+    }
+
+    /// Clears the filter.
+    pub fn clear(&mut self) {
+        self.set_filter(String::new());
+    }
+
+    /// Returns the error of the first invalid regular expression in the filter.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Draws an [InputText](crate::input_widget::InputText) to edit the filter. Returns
+    /// `true` if the filter was edited.
+    pub fn draw(&mut self, ui: &Ui) -> bool {
+        self.draw_with_size(ui, 0.0)
+    }
+
+    /// Draws an [InputText](crate::input_widget::InputText) of the given width to edit the
+    /// filter, with `0.0` for the default width. Returns `true` if the filter was edited.
+    pub fn draw_with_size(&mut self, ui: &Ui, size: f32) -> bool {
+        if size != 0.0 {
+            ui.set_next_item_width(size);
+        }
+        let edited = ui.input_text(&self.label, &mut self.filter).build();
+        if edited {
+            self.parse();
+        }
+        edited
+    }
+
+    /// Returns `true` if the filter has any terms.
+    pub fn is_active(&self) -> bool {
+        !self.terms.is_empty()
+    }
+
+    /// Returns `true` if `text` passes the filter.
+    #[doc(alias = "PassFilter")]
+    pub fn pass_filter(&self, text: &str) -> bool {
+        let mut included = !self.has_includes;
+        for term in &self.terms {
+            if term.exclude {
+                if self.is_match(term, text) {
+                    return false;
+                }
+            } else if !included {
+                included = self.is_match(term, text);
+            }
+        }
+        included
+    }
+
+    /// Returns `true` if `text` passes the filter, and replaces the contents of `ranges` with
+    /// the sorted, non-overlapping byte ranges of `text` matched by the terms which aren't
+    /// excluded. `ranges` can be reused between calls to avoid allocating.
+    pub fn match_ranges(&self, text: &str, ranges: &mut Vec<Range<usize>>) -> bool {
+        ranges.clear();
+        if !self.pass_filter(text) {
+            return false;
+        }
+        for term in self.terms.iter().filter(|term| !term.exclude) {
+            if self.mode == SearchFilterMode::Fuzzy {
+                fuzzy_match_into(text, &term.needle, self.case_sensitive, Some(ranges));
+                continue;
+            }
+            let mut from = 0;
+            while let Some(range) = self.find(term, text, from) {
+                // Step over empty matches, which a regular expression can have
+                from = if range.is_empty() {
+                    match text[range.end..].chars().next() {
+                        Some(c) => range.end + c.len_utf8(),
+                        None => break,
+                    }
+                } else {
+                    range.end
+                };
+                if !range.is_empty() {
+                    ranges.push(range);
+                }
+            }
+        }
+        ranges.sort_unstable_by_key(|range| range.start);
+        ranges.dedup_by(|next, prev| {
+            if next.start <= prev.end {
+                prev.end = prev.end.max(next.end);
+                true
+            } else {
+                false
+            }
+        });
+        true
+    }
+
+    fn parse(&mut self) {
+        let mut terms = Vec::new();
+        let mut term = String::new();
+        let mut chars = self.filter.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if chars.peek() == Some(&',') => term.push(chars.next().unwrap()),
+                ',' => terms.push(std::mem::take(&mut term)),
+                c => term.push(c),
+            }
+        }
+        terms.push(term);
+
+        self.terms.clear();
+        self.error = None;
+        for term in &terms {
+            self.push_term(term);
+        }
+        self.has_includes = self.terms.iter().any(|term| !term.exclude);
+    }
+
+    fn push_term(&mut self, term: &str) {
+        let term = term.trim();
+        let (needle, exclude) = match term.strip_prefix('-') {
+            Some(needle) => (needle, true),
+            None => (term, false),
+        };
+        if needle.is_empty() {
+            return;
+        }
+        #[cfg(feature = "regex")]
+        let regex = if self.mode == SearchFilterMode::Regex {
+            match regex::RegexBuilder::new(needle)
+                .case_insensitive(!self.case_sensitive)
+                .build()
+            {
+                Ok(regex) => Some(regex),
+                Err(error) => {
+                    // Keep the term, so an invalid include term doesn't make everything pass
+                    self.error.get_or_insert_with(|| error.to_string());
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        self.terms.push(SearchTerm {
+            needle: needle.to_owned(),
+            exclude,
+            #[cfg(feature = "regex")]
+            regex,
+        });
+    }
+
+    fn is_match(&self, term: &SearchTerm, text: &str) -> bool {
+        match self.mode {
+            SearchFilterMode::Fuzzy => {
+                fuzzy_match_into(text, &term.needle, self.case_sensitive, None).is_some()
+            }
+            _ => self.find(term, text, 0).is_some(),
+        }
+    }
+
+    /// Returns the first match of `term` in `text` at or after the byte `from`.
+    fn find(&self, term: &SearchTerm, text: &str, from: usize) -> Option<Range<usize>> {
+        let haystack = &text[from..];
+        let found = match self.mode {
+            SearchFilterMode::Substring if self.case_sensitive => haystack
+                .find(&term.needle)
+                .map(|start| start..start + term.needle.len()),
+            SearchFilterMode::Substring if term.needle.is_ascii() => {
+                find_ascii_ignore_case(haystack.as_bytes(), term.needle.as_bytes())
+            }
+            SearchFilterMode::Substring => find_ignore_case(haystack, &term.needle),
+            // Fuzzy terms match characters rather than a range, see `fuzzy_match_into`
+            SearchFilterMode::Fuzzy => None,
+            #[cfg(feature = "regex")]
+            SearchFilterMode::Regex => term
+                .regex
+                .as_ref()
+                .and_then(|regex| regex.find_at(text, from))
+                .map(|found| found.start() - from..found.end() - from),
+        };
+        found.map(|range| range.start + from..range.end + from)
+    }
+}
+
+/// Returns the range of the first occurrence of the ASCII `needle` in `haystack`, ignoring
+/// ASCII case. ASCII bytes never occur inside multi-byte characters, so the range is always on
+/// character boundaries.
+fn find_ascii_ignore_case(haystack: &[u8], needle: &[u8]) -> Option<Range<usize>> {
+    let (&first, rest) = needle.split_first()?;
+    let (lower, upper) = (first.to_ascii_lowercase(), first.to_ascii_uppercase());
+    let last_start = haystack.len().checked_sub(needle.len())?;
+    (0..=last_start)
+        .find(|&start| {
+            let b = haystack[start];
+            (b == lower || b == upper)
+                && haystack[start + 1..start + needle.len()].eq_ignore_ascii_case(rest)
+        })
+        .map(|start| start..start + needle.len())
+}
+
+/// Returns the byte range of the first occurrence of `needle` in `haystack`, ignoring case.
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<Range<usize>> {
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut rest = haystack[start..].char_indices();
+        for n in needle.chars() {
+            match rest.next() {
+                Some((_, c)) if chars_eq_ignore_case(c, n) => {}
+                _ => return None,
+            }
+        }
+        let end = rest.next().map_or(haystack.len(), |(i, _)| start + i);
+        Some(start..end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(filter: &SearchFilter, text: &str) -> Option<Vec<(usize, usize)>> {
+        let mut ranges = Vec::new();
+        let passed = filter.match_ranges(text, &mut ranges);
+        passed.then(|| ranges.iter().map(|r| (r.start, r.end)).collect())
+    }
+
+    #[test]
+    fn test_search_filter_syntax() {
+        let filter = SearchFilter::new("filter");
+        assert!(!filter.is_active());
+        assert!(filter.pass_filter("anything"));
+
+        let filter = SearchFilter::new_with_filter("filter", " error , warn,-debug,,- ");
+        assert!(filter.is_active());
+        assert!(filter.pass_filter("[ERROR] disk full"));
+        assert!(filter.pass_filter("[warn] low memory"));
+        assert!(!filter.pass_filter("[info] started"));
+        // Excludes win regardless of their position, unlike in `TextFilter`
+        assert!(!filter.pass_filter("[error] debug build"));
+
+        let filter = SearchFilter::new_with_filter("filter", "-debug");
+        assert!(filter.pass_filter("[info] started"));
+        assert!(!filter.pass_filter("[info] Debug build"));
+
+        let filter = SearchFilter::new_with_filter("filter", r"1\,000");
+        assert!(filter.pass_filter("1,000 lines"));
+        assert!(!filter.pass_filter("1000 lines"));
+    }
+
+    #[test]
+    fn test_search_filter_ranges() {
+        let mut filter = SearchFilter::new_with_filter("filter", "ab,b");
+        assert_eq!(ranges(&filter, "xAbab Cb"), Some(vec![(1, 5), (7, 8)]));
+        assert_eq!(ranges(&filter, "xyz"), None);
+
+        filter.set_case_sensitive(true);
+        assert_eq!(ranges(&filter, "xAbab Cb"), Some(vec![(2, 5), (7, 8)]));
+
+        // Non-ASCII terms and text
+        let filter = SearchFilter::new_with_filter("filter", "GRÖ");
+        assert_eq!(ranges(&filter, "Die Größe"), Some(vec![(4, 8)]));
+        let filter = SearchFilter::new_with_filter("filter", "e");
+        assert_eq!(ranges(&filter, "Größe"), Some(vec![(6, 7)]));
+
+        let filter =
+            SearchFilter::new_with_filter("filter", "lgerr,-warn").mode(SearchFilterMode::Fuzzy);
+        assert_eq!(
+            ranges(&filter, "log_error"),
+            Some(vec![(0, 1), (2, 3), (4, 7)])
+        );
+        assert_eq!(ranges(&filter, "log_warning_error"), None);
+        assert_eq!(ranges(&filter, "error_log"), None);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_search_filter_regex() {
+        let mut filter = SearchFilter::new_with_filter("filter", r"^\d{2\,3},x*,-skip")
+            .mode(SearchFilterMode::Regex);
+        assert_eq!(filter.error(), None);
+        assert_eq!(ranges(&filter, "123 abc"), Some(vec![(0, 3)]));
+        assert_eq!(ranges(&filter, "12 xx abc"), Some(vec![(0, 2), (3, 5)]));
+        assert_eq!(ranges(&filter, "12 skip"), None);
+
+        filter.set_filter("(,abc");
+        assert!(filter.error().is_some());
+        assert!(filter.pass_filter("ABC"));
+        assert!(!filter.pass_filter("("));
+
+        // A filter of only invalid terms passes nothing
+        filter.set_filter("(");
+        assert!(filter.error().is_some());
+        assert!(filter.is_active());
+        assert!(!filter.pass_filter("("));
+        assert!(!filter.pass_filter("abc"));
+    }
+}
@@ -0,0 +1,99 @@
+//! Text matching shared by the filters and searchable widgets.
+
+use std::ops::Range;
+
+pub(crate) fn chars_eq_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Matches the characters of `needle` in order against `haystack`, ignoring case. Returns a
+/// score, higher is better, and the matched byte ranges.
+pub(crate) fn fuzzy_match(haystack: &str, needle: &str) -> Option<(i32, Vec<Range<usize>>)> {
+    let mut ranges = Vec::new();
+    let score = fuzzy_match_into(haystack, needle, false, Some(&mut ranges))?;
+    Some((score, ranges))
+}
+
+/// Matches the characters of `needle` in order against `haystack`. Returns a score, higher is
+/// better, and appends the matched byte ranges to `ranges`, which is left as it was if `needle`
+/// doesn't match.
+pub(crate) fn fuzzy_match_into(
+    haystack: &str,
+    needle: &str,
+    case_sensitive: bool,
+    mut ranges: Option<&mut Vec<Range<usize>>>,
+) -> Option<i32> {
+    let start_len = ranges.as_ref().map_or(0, |ranges| ranges.len());
+    let mut score = 0;
+    let mut matched_any = false;
+    let mut needle = needle.chars().peekable();
+    let mut prev: Option<char> = None;
+    let mut prev_end = None;
+    for (i, c) in haystack.char_indices() {
+        let Some(&n) = needle.peek() else {
+            break;
+        };
+        let matched = if case_sensitive {
+            c == n
+        } else {
+            chars_eq_ignore_case(c, n)
+        };
+        if matched {
+            needle.next();
+            score += 1;
+            let word_start = match prev {
+                None => true,
+                Some(p) => !p.is_alphanumeric() || (p.is_lowercase() && c.is_uppercase()),
+            };
+            if word_start {
+                score += 8;
+            }
+            let end = i + c.len_utf8();
+            if prev_end == Some(i) {
+                score += 5;
+            }
+            if let Some(ranges) = ranges.as_deref_mut() {
+                match ranges.last_mut() {
+                    Some(range) if prev_end == Some(i) => range.end = end,
+                    _ => ranges.push(i..end),
+                }
+            }
+            matched_any = true;
+            prev_end = Some(end);
+        } else if matched_any {
+            // Gaps only count once something matched, so leading ones are free
+            score -= 1;
+        }
+        prev = Some(c);
+    }
+    if needle.peek().is_some() {
+        if let Some(ranges) = ranges {
+            ranges.truncate(start_len);
+        }
+        return None;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(fuzzy_match("stone_wall", "sw").unwrap().1, vec![0..1, 6..7]);
+        assert_eq!(fuzzy_match("stone_wall", "wal").unwrap().1, vec![6..9]);
+        assert_eq!(fuzzy_match("stone_wall", "ws"), None);
+        let word_starts = fuzzy_match("StoneWall", "sw").unwrap().0;
+        let inside = fuzzy_match("grassway", "sw").unwrap().0;
+        assert!(word_starts > inside);
+
+        assert_eq!(fuzzy_match_into("Stone", "s", true, None), None);
+        let mut ranges = vec![0..1, 2..3];
+        assert_eq!(
+            fuzzy_match_into("stone", "sz", false, Some(&mut ranges)),
+            None
+        );
+        assert_eq!(ranges, vec![0..1, 2..3]);
+    }
+}
@@ -0,0 +1,958 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::math::MintVec2;
+use crate::value_format::{format_number, nice_step};
+use crate::{
+    ListClipper, MouseButton, MouseCursor, StyleColor, StyleVar, Ui, WindowFlags,
+    WindowHoveredFlags,
+};
+
+const MIN_PIXELS_PER_SECOND: f32 = 1.0;
+const MAX_PIXELS_PER_SECOND: f32 = 10000.0;
+
+/// Identifies a track of a [Timeline]. It must stay the same across frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TrackId(pub u64);
+
+/// Identifies a keyframe of a [Timeline]. It must stay the same across frames, and be unique
+/// among the keyframes of all tracks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyframeId(pub u64);
+
+/// Identifies a clip of a [Timeline]. It must stay the same across frames, and be unique
+/// among the clips of all tracks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClipId(pub u64);
+
+/// A change requested by the user in a [Timeline], as returned by [Timeline::build].
+///
+/// Times are in seconds. The timeline doesn't own the tracks, so nothing changes until the
+/// application applies the event to its own data and submits it again on the next frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimelineEvent {
+    /// The playhead was scrubbed to a new time.
+    PlayheadMoved(f64),
+    /// A keyframe was dragged to a new time. This is sent every frame while dragging, for
+    /// every selected keyframe.
+    KeyframeMoved { key: KeyframeId, time: f64 },
+    /// A clip was moved or resized. This is sent every frame while dragging.
+    ClipChanged { clip: ClipId, start: f64, end: f64 },
+}
+
+#[derive(Clone, Debug)]
+struct ClipData {
+    id: ClipId,
+    start: f64,
+    end: f64,
+    label: String,
+}
+
+#[derive(Clone, Debug)]
+struct TrackData {
+    id: TrackId,
+    label: String,
+    color: Option<[f32; 4]>,
+    keyframes: Vec<(KeyframeId, f64)>,
+    clips: Vec<ClipData>,
+}
+
+/// The tracks submitted to a [Timeline] for one frame, in [Timeline::build].
+#[derive(Clone, Debug, Default)]
+pub struct TimelineItems {
+    tracks: Vec<TrackData>,
+}
+
+impl TimelineItems {
+    /// Starts adding a track, shown below the previously added ones. The track must be added
+    /// with [TrackBuilder::build].
+    pub fn track(&mut self, id: TrackId, label: impl Into<String>) -> TrackBuilder<'_> {
+        TrackBuilder {
+            items: self,
+            track: TrackData {
+                id,
+                label: label.into(),
+                color: None,
+                keyframes: Vec::new(),
+                clips: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Builder for a track of a [Timeline], created with [TimelineItems::track].
+#[must_use = "call .build() to add the track"]
+pub struct TrackBuilder<'a> {
+    items: &'a mut TimelineItems,
+    track: TrackData,
+}
+
+impl TrackBuilder<'_> {
+    /// Sets the color of the keyframes and clips of the track.
+    pub fn color(mut self, color: impl Into<[f32; 4]>) -> Self {
+        self.track.color = Some(color.into());
+        self
+    }
+
+    /// Adds a keyframe at `time`, in seconds.
+    pub fn keyframe(mut self, id: KeyframeId, time: f64) -> Self {
+        self.track.keyframes.push((id, time));
+        self
+    }
+
+    /// Adds a clip from `start` to `end`, in seconds.
+    pub fn clip(mut self, id: ClipId, start: f64, end: f64, label: impl Into<String>) -> Self {
+        self.track.clips.push(ClipData {
+            id,
+            start,
+            end,
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Adds the track to the timeline.
+    pub fn build(self) {
+        self.items.tracks.push(self.track);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ClipDrag {
+    Move,
+    ResizeStart,
+    ResizeEnd,
+}
+
+#[derive(Clone, Debug)]
+enum Interaction {
+    None,
+    Scrub,
+    Pan,
+    /// Dragging the selected keyframes. `grab` is the time under the mouse when the drag
+    /// started, and `origin` the times of the keyframes then.
+    DragKeyframes {
+        grab: f64,
+        grab_time: f64,
+        origin: HashMap<KeyframeId, f64>,
+    },
+    DragClip {
+        clip: ClipId,
+        mode: ClipDrag,
+        grab: f64,
+        start: f64,
+        end: f64,
+    },
+    /// Box selection started at this time and y, relative to the top of the tracks.
+    BoxSelect([f64; 2]),
+}
+
+/// Maps times to screen x coordinates.
+#[derive(Copy, Clone, Debug)]
+struct TimeAxis {
+    x: f32,
+    scroll_time: f64,
+    pixels_per_second: f32,
+}
+
+impl TimeAxis {
+    fn to_x(self, time: f64) -> f32 {
+        self.x + ((time - self.scroll_time) * self.pixels_per_second as f64) as f32
+    }
+
+    fn to_time(self, x: f32) -> f64 {
+        self.scroll_time + ((x - self.x) / self.pixels_per_second) as f64
+    }
+}
+
+/// A timeline, or sequencer: tracks of keyframes and clips laid out along a time ruler, with a
+/// playhead.
+///
+/// The timeline owns no application data. Every frame, the application submits its tracks to
+/// [build](Self::build), and receives [TimelineEvent]s describing what the user did:
+///
+/// * clicking or dragging in the ruler scrubs the playhead,
+/// * dragging keyframes moves them, along with the other selected keyframes,
+/// * dragging clips moves them, and dragging their edges resizes them.
+///
+/// Keyframes are selected by clicking them, holding Ctrl to add to the selection, or by
+/// dragging a box over empty parts of the tracks. Times snap to the [snap](Self::snap)
+/// interval unless Alt is held. Ctrl + mouse wheel zooms the time axis around the mouse, and
+/// Shift + mouse wheel or dragging with the middle mouse button scrolls it. Tracks are drawn
+/// with a [ListClipper], so only the visible ones cost anything to draw.
+///
+/// The timeline only keeps view state, such as the zoom and selection, so it should be stored
+/// alongside the rest of the UI state rather than recreated every frame.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut timeline = Timeline::new("cutscene").duration(30.0).snap(0.25);
+/// let mut playhead = 0.0;
+/// let mut keys = vec![(KeyframeId(0), 1.0), (KeyframeId(1), 4.5)];
+/// // Every frame:
+/// let events = timeline.build(ui, [0.0, 200.0], playhead, |items| {
+///     let mut track = items.track(TrackId(0), "Camera");
+///     for &(id, time) in &keys {
+///         track = track.keyframe(id, time);
+///     }
+///     track.clip(ClipId(0), 2.0, 6.0, "Dolly").build();
+/// });
+/// for event in events {
+///     match event {
+///         TimelineEvent::PlayheadMoved(time) => playhead = time,
+///         TimelineEvent::KeyframeMoved { key, time } => keys[key.0 as usize].1 = time,
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Timeline {
+    id: String,
+    duration: f64,
+    snap: f64,
+    label_width: f32,
+    track_height: Option<f32>,
+    pixels_per_second: f32,
+    scroll_time: f64,
+    selected_keyframes: HashSet<KeyframeId>,
+    selected_clips: HashSet<ClipId>,
+    interaction: Interaction,
+}
+
+impl Timeline {
+    /// Creates a new timeline with the given id.
+    pub fn new(id: impl Into<String>) -> Self {
+        Timeline {
+            id: id.into(),
+            duration: 10.0,
+            snap: 0.0,
+            label_width: 120.0,
+            track_height: None,
+            pixels_per_second: 100.0,
+            scroll_time: 0.0,
+            selected_keyframes: HashSet::new(),
+            selected_clips: HashSet::new(),
+            interaction: Interaction::None,
+        }
+    }
+
+    /// Sets the length of the timeline, in seconds. Times are kept between 0 and the duration.
+    ///
+    /// Defaults to 10 seconds.
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.duration = duration.max(0.0);
+        self
+    }
+
+    /// Sets the interval times snap to, in seconds. Zero disables snapping.
+    ///
+    /// Disabled by default.
+    pub fn snap(mut self, interval: f64) -> Self {
+        self.snap = interval.max(0.0);
+        self
+    }
+
+    /// Sets the width of the column of track labels.
+    pub fn label_width(mut self, label_width: f32) -> Self {
+        self.label_width = label_width;
+        self
+    }
+
+    /// Sets the height of the tracks. Defaults to the frame height.
+    pub fn track_height(mut self, track_height: f32) -> Self {
+        self.track_height = Some(track_height);
+        self
+    }
+
+    /// Returns the zoom of the time axis, in pixels per second.
+    pub fn pixels_per_second(&self) -> f32 {
+        self.pixels_per_second
+    }
+
+    /// Sets the zoom of the time axis, in pixels per second.
+    pub fn set_pixels_per_second(&mut self, pixels_per_second: f32) {
+        self.pixels_per_second =
+            pixels_per_second.clamp(MIN_PIXELS_PER_SECOND, MAX_PIXELS_PER_SECOND);
+    }
+
+    /// Returns the time at the left edge of the tracks.
+    pub fn scroll_time(&self) -> f64 {
+        self.scroll_time
+    }
+
+    /// Sets the time at the left edge of the tracks.
+    pub fn set_scroll_time(&mut self, scroll_time: f64) {
+        self.scroll_time = scroll_time.clamp(0.0, self.duration);
+    }
+
+    /// Returns the selected keyframes.
+    pub fn selected_keyframes(&self) -> &HashSet<KeyframeId> {
+        &self.selected_keyframes
+    }
+
+    /// Returns the selected clips.
+    pub fn selected_clips(&self) -> &HashSet<ClipId> {
+        &self.selected_clips
+    }
+
+    /// Adds a keyframe to the selection.
+    pub fn select_keyframe(&mut self, key: KeyframeId) {
+        self.selected_keyframes.insert(key);
+    }
+
+    /// Adds a clip to the selection.
+    pub fn select_clip(&mut self, clip: ClipId) {
+        self.selected_clips.insert(clip);
+    }
+
+    /// Clears the selection of keyframes and clips.
+    pub fn clear_selection(&mut self) {
+        self.selected_keyframes.clear();
+        self.selected_clips.clear();
+    }
+
+    /// Builds the timeline, filling `size` like a child window: zero fills the available
+    /// space, negative values leave that much space.
+    ///
+    /// `playhead` is the current time, and `f` submits the tracks for this frame. Returns what
+    /// the user did.
+    pub fn build(
+        &mut self,
+        ui: &Ui,
+        size: impl Into<MintVec2>,
+        playhead: f64,
+        f: impl FnOnce(&mut TimelineItems),
+    ) -> Vec<TimelineEvent> {
+        let mut items = TimelineItems::default();
+        f(&mut items);
+        let mut events = Vec::new();
+        let _id = ui.push_id(&self.id);
+        ui.child_window("##timeline")
+            .size(size)
+            .scroll_bar(false)
+            .scrollable(false)
+            .build(|| self.build_timeline(ui, &items, playhead, &mut events));
+        events
+    }
+
+    fn build_timeline(
+        &mut self,
+        ui: &Ui,
+        items: &TimelineItems,
+        playhead: f64,
+        events: &mut Vec<TimelineEvent>,
+    ) {
+        let io = ui.io();
+        let mouse = io.mouse_pos;
+        let origin = ui.cursor_screen_pos();
+        let avail = ui.content_region_avail();
+        let font_size = ui.current_font_size();
+        let ruler_height = ui.frame_height();
+        let track_height = self.track_height.unwrap_or_else(|| ui.frame_height());
+        let lane_x = origin[0] + self.label_width;
+        let lane_width = (avail[0] - self.label_width).max(1.0);
+        let lane_max_x = lane_x + lane_width;
+        let duration = self.duration;
+        let (snap, snap_enabled) = (self.snap, self.snap > 0.0 && !io.key_alt);
+        let snap_time = |time: f64| {
+            let time = if snap_enabled {
+                (time / snap).round() * snap
+            } else {
+                time
+            };
+            time.clamp(0.0, duration)
+        };
+
+        // Zoom and scroll first, so that everything below uses this frame's time axis
+        let hovered = ui.is_window_hovered_with_flags(WindowHoveredFlags::CHILD_WINDOWS);
+        let over_lanes = hovered && mouse[0] >= lane_x && mouse[0] < lane_max_x;
+        if over_lanes && io.key_ctrl && io.mouse_wheel != 0.0 {
+            let anchor = self.axis(lane_x).to_time(mouse[0]);
+            self.set_pixels_per_second(self.pixels_per_second * 1.1f32.powf(io.mouse_wheel));
+            self.set_scroll_time(anchor - ((mouse[0] - lane_x) / self.pixels_per_second) as f64);
+        }
+        let wheel_scroll = if io.key_shift {
+            io.mouse_wheel + io.mouse_wheel_h
+        } else {
+            io.mouse_wheel_h
+        };
+        if over_lanes && !io.key_ctrl && wheel_scroll != 0.0 {
+            let pixels = wheel_scroll * font_size * 5.0;
+            self.set_scroll_time(self.scroll_time - (pixels / self.pixels_per_second) as f64);
+        }
+        if over_lanes && ui.is_mouse_clicked(MouseButton::Middle) {
+            self.interaction = Interaction::Pan;
+        }
+        if matches!(self.interaction, Interaction::Pan) {
+            if ui.is_mouse_down(MouseButton::Middle) {
+                self.set_scroll_time(
+                    self.scroll_time - (io.mouse_delta[0] / self.pixels_per_second) as f64,
+                );
+            } else {
+                self.interaction = Interaction::None;
+            }
+        }
+        let axis = self.axis(lane_x);
+
+        // Ruler
+        ui.set_cursor_screen_pos([lane_x, origin[1]]);
+        ui.invisible_button("##ruler", [lane_width, ruler_height]);
+        if ui.is_item_activated() {
+            self.interaction = Interaction::Scrub;
+        }
+        if matches!(self.interaction, Interaction::Scrub) {
+            if ui.is_mouse_down(MouseButton::Left) {
+                let time = snap_time(axis.to_time(mouse[0]));
+                if time != playhead {
+                    events.push(TimelineEvent::PlayheadMoved(time));
+                }
+            } else {
+                self.interaction = Interaction::None;
+            }
+        }
+        self.draw_ruler(ui, axis, [origin[0], origin[1]], lane_max_x, playhead);
+
+        // Apply drags, so that keyframes and clips are drawn where they are going
+        let keyframe_times: HashMap<KeyframeId, f64> = items
+            .tracks
+            .iter()
+            .flat_map(|track| track.keyframes.iter().copied())
+            .collect();
+        let mut moved_keyframes = HashMap::new();
+        let mut changed_clip = None;
+        match &self.interaction {
+            Interaction::DragKeyframes {
+                grab,
+                grab_time,
+                origin,
+            } if ui.is_mouse_down(MouseButton::Left) => {
+                // Snap the grabbed keyframe, and move the others by as much
+                let delta = snap_time(grab_time + axis.to_time(mouse[0]) - grab) - grab_time;
+                for (&key, &time) in origin {
+                    let time = (time + delta).clamp(0.0, duration);
+                    if keyframe_times.get(&key).is_some_and(|&t| t != time) {
+                        events.push(TimelineEvent::KeyframeMoved { key, time });
+                    }
+                    moved_keyframes.insert(key, time);
+                }
+            }
+            &Interaction::DragClip {
+                clip,
+                mode,
+                grab,
+                start,
+                end,
+            } if ui.is_mouse_down(MouseButton::Left) => {
+                let delta = axis.to_time(mouse[0]) - grab;
+                let min_length = if snap > 0.0 {
+                    snap
+                } else {
+                    (4.0 / self.pixels_per_second) as f64
+                };
+                let (new_start, new_end) = match mode {
+                    ClipDrag::Move => {
+                        let length = end - start;
+                        let new_start = snap_time(start + delta).min(duration - length).max(0.0);
+                        (new_start, new_start + length)
+                    }
+                    ClipDrag::ResizeStart => (snap_time(start + delta).min(end - min_length), end),
+                    ClipDrag::ResizeEnd => (start, snap_time(end + delta).max(start + min_length)),
+                };
+                changed_clip = Some((clip, new_start, new_end));
+            }
+            Interaction::DragKeyframes { .. } | Interaction::DragClip { .. } => {
+                self.interaction = Interaction::None;
+            }
+            _ => {}
+        }
+        if let Some((clip, start, end)) = changed_clip {
+            let current = items
+                .tracks
+                .iter()
+                .flat_map(|track| &track.clips)
+                .find(|c| c.id == clip);
+            if current.is_some_and(|c| (c.start, c.end) != (start, end)) {
+                events.push(TimelineEvent::ClipChanged { clip, start, end });
+            }
+        }
+
+        // Tracks
+        ui.set_cursor_screen_pos([origin[0], origin[1] + ruler_height]);
+        let flags = if io.key_ctrl || io.key_shift {
+            WindowFlags::NO_SCROLL_WITH_MOUSE
+        } else {
+            WindowFlags::empty()
+        };
+        ui.child_window("##tracks").flags(flags).build(|| {
+            let _spacing = ui.push_style_var(StyleVar::ItemSpacing([0.0, 0.0]));
+            let tracks_origin = ui.cursor_screen_pos();
+            let to_track_y = |y: f32| (y - tracks_origin[1]) as f64;
+
+            // Finish a box selection
+            if let Interaction::BoxSelect(start) = self.interaction {
+                if !ui.is_mouse_down(MouseButton::Left) {
+                    let end = [axis.to_time(mouse[0]), to_track_y(mouse[1])];
+                    let (t0, t1) = (start[0].min(end[0]), start[0].max(end[0]));
+                    let (y0, y1) = (start[1].min(end[1]), start[1].max(end[1]));
+                    for (index, track) in items.tracks.iter().enumerate() {
+                        let center = (index as f64 + 0.5) * track_height as f64;
+                        if center < y0 || center > y1 {
+                            continue;
+                        }
+                        self.selected_keyframes.extend(
+                            track
+                                .keyframes
+                                .iter()
+                                .filter(|(_, time)| (t0..=t1).contains(time))
+                                .map(|&(id, _)| id),
+                        );
+                    }
+                    self.interaction = Interaction::None;
+                }
+            }
+
+            let clipper = ListClipper::new(items.tracks.len() as i32)
+                .items_height(track_height)
+                .begin(ui);
+            for index in clipper.iter() {
+                let track = &items.tracks[index as usize];
+                let row_min = ui.cursor_screen_pos();
+                self.build_track(
+                    ui,
+                    track,
+                    index as usize,
+                    axis,
+                    [row_min[1], track_height],
+                    [lane_x, lane_max_x],
+                    &keyframe_times,
+                    &moved_keyframes,
+                    changed_clip,
+                );
+                ui.set_cursor_screen_pos(row_min);
+                ui.dummy([avail[0], track_height]);
+            }
+
+            let window_pos = ui.window_pos();
+            let window_max = [
+                window_pos[0] + ui.window_size()[0],
+                window_pos[1] + ui.window_size()[1],
+            ];
+            let draw_list = ui.get_window_draw_list();
+            draw_list.with_clip_rect_intersect([lane_x, window_pos[1]], window_max, || {
+                if duration < axis.to_time(lane_max_x) {
+                    draw_list
+                        .add_rect(
+                            [axis.to_x(duration), window_pos[1]],
+                            window_max,
+                            [0.0, 0.0, 0.0, 0.25],
+                        )
+                        .filled(true)
+                        .build();
+                }
+                if let Interaction::BoxSelect(start) = self.interaction {
+                    let start = [axis.to_x(start[0]), tracks_origin[1] + start[1] as f32];
+                    let mut color = ui.style_color(StyleColor::NavHighlight);
+                    draw_list.add_rect(start, mouse, color).build();
+                    color[3] *= 0.25;
+                    draw_list.add_rect(start, mouse, color).filled(true).build();
+                }
+                let x = axis.to_x(playhead).round();
+                draw_list
+                    .add_line(
+                        [x, window_pos[1]],
+                        [x, window_max[1]],
+                        ui.style_color(StyleColor::PlotLinesHovered),
+                    )
+                    .build();
+            });
+        });
+    }
+
+    fn axis(&self, lane_x: f32) -> TimeAxis {
+        TimeAxis {
+            x: lane_x,
+            scroll_time: self.scroll_time,
+            pixels_per_second: self.pixels_per_second,
+        }
+    }
+
+    fn draw_ruler(
+        &self,
+        ui: &Ui,
+        axis: TimeAxis,
+        origin: [f32; 2],
+        lane_max_x: f32,
+        playhead: f64,
+    ) {
+        let height = ui.frame_height();
+        let font_size = ui.current_font_size();
+        let text_color = ui.style_color(StyleColor::Text);
+        let mut minor_color = text_color;
+        minor_color[3] *= 0.4;
+        let playhead_color = ui.style_color(StyleColor::PlotLinesHovered);
+        let draw_list = ui.get_window_draw_list();
+        draw_list
+            .add_rect(
+                origin,
+                [lane_max_x, origin[1] + height],
+                ui.style_color(StyleColor::FrameBg),
+            )
+            .filled(true)
+            .build();
+        draw_list.add_text(
+            [
+                origin[0] + font_size * 0.5,
+                origin[1] + (height - font_size) * 0.5,
+            ],
+            text_color,
+            format!("{:.2}", playhead),
+        );
+
+        let (start, end) = (axis.to_time(axis.x), axis.to_time(lane_max_x));
+        let max_labels = ((lane_max_x - axis.x) / (font_size * 5.0)).max(1.0) as f64;
+        let step = nice_step(end - start, max_labels);
+        // Subdivides 1s and 5s in fifths, and 2s in quarters
+        let mantissa = (step / 10f64.powf(step.log10().floor())).round();
+        let minor_step = step / if mantissa == 2.0 { 4.0 } else { 5.0 };
+        draw_list.with_clip_rect_intersect(
+            [axis.x, origin[1]],
+            [lane_max_x, origin[1] + height],
+            || {
+                let first = (start / minor_step).floor() as i64;
+                let last = (end / minor_step).ceil() as i64;
+                for i in first.max(0)..=last {
+                    let time = i as f64 * minor_step;
+                    let x = axis.to_x(time).round();
+                    let major = ((time / step).round() * step - time).abs() < minor_step * 0.5;
+                    if major {
+                        draw_list
+                            .add_line([x, origin[1]], [x, origin[1] + height], text_color)
+                            .build();
+                        draw_list.add_text(
+                            [x + 3.0, origin[1]],
+                            text_color,
+                            format_number(time, step),
+                        );
+                    } else {
+                        draw_list
+                            .add_line(
+                                [x, origin[1] + height * 0.7],
+                                [x, origin[1] + height],
+                                minor_color,
+                            )
+                            .build();
+                    }
+                }
+                let x = axis.to_x(playhead).round();
+                let size = height * 0.3;
+                draw_list
+                    .add_triangle(
+                        [x - size, origin[1] + height - size],
+                        [x + size, origin[1] + height - size],
+                        [x, origin[1] + height],
+                        playhead_color,
+                    )
+                    .filled(true)
+                    .build();
+                draw_list
+                    .add_line([x, origin[1]], [x, origin[1] + height], playhead_color)
+                    .build();
+            },
+        );
+    }
+
+    /// Submits and draws one track. `row` is the top y and height of the track, and `lanes`
+    /// the horizontal range of keyframes and clips.
+    #[allow(clippy::too_many_arguments)]
+    fn build_track(
+        &mut self,
+        ui: &Ui,
+        track: &TrackData,
+        index: usize,
+        axis: TimeAxis,
+        row: [f32; 2],
+        lanes: [f32; 2],
+        keyframe_times: &HashMap<KeyframeId, f64>,
+        moved_keyframes: &HashMap<KeyframeId, f64>,
+        changed_clip: Option<(ClipId, f64, f64)>,
+    ) {
+        let io = ui.io();
+        let mouse = io.mouse_pos;
+        let [y, height] = row;
+        let font_size = ui.current_font_size();
+        let _id = ui.push_id_usize(track.id.0 as usize);
+
+        // The lane goes first, so that clips and keyframes on top of it take the mouse
+        ui.set_cursor_screen_pos([lanes[0], y]);
+        ui.set_next_item_allow_overlap();
+        ui.invisible_button("##lane", [lanes[1] - lanes[0], height]);
+        if ui.is_item_activated() {
+            if !io.key_ctrl {
+                self.clear_selection();
+            }
+            self.interaction = Interaction::BoxSelect([
+                axis.to_time(mouse[0]),
+                (index as f32 * height + mouse[1] - y) as f64,
+            ]);
+        }
+
+        let inset = (height * 0.1).round().max(1.0);
+        let mut hovered_clip = None;
+        let clips: Vec<(ClipId, f64, f64)> = track
+            .clips
+            .iter()
+            .map(|clip| match changed_clip {
+                Some(changed) if changed.0 == clip.id => changed,
+                _ => (clip.id, clip.start, clip.end),
+            })
+            .collect();
+        for &(id, start, end) in &clips {
+            let _id = ui.push_id_usize(id.0 as usize);
+            let (x0, x1) = (axis.to_x(start), axis.to_x(end));
+            let width = (x1 - x0).max(1.0);
+            let handle_width = (width / 3.0).min(6.0);
+            let parts = [
+                ("##body", x0, width, ClipDrag::Move),
+                ("##start", x0, handle_width, ClipDrag::ResizeStart),
+                (
+                    "##end",
+                    x0 + width - handle_width,
+                    handle_width,
+                    ClipDrag::ResizeEnd,
+                ),
+            ];
+            for (label, x, width, mode) in parts {
+                ui.set_cursor_screen_pos([x, y + inset]);
+                ui.set_next_item_allow_overlap();
+                ui.invisible_button(label, [width, height - inset * 2.0]);
+                if ui.is_item_hovered() {
+                    hovered_clip = Some(id);
+                    if mode != ClipDrag::Move {
+                        ui.set_mouse_cursor(Some(MouseCursor::ResizeEW));
+                    }
+                }
+                if ui.is_item_activated() {
+                    select(&mut self.selected_clips, id, io.key_ctrl);
+                    if !io.key_ctrl {
+                        self.selected_keyframes.clear();
+                    }
+                    self.interaction = Interaction::DragClip {
+                        clip: id,
+                        mode,
+                        grab: axis.to_time(mouse[0]),
+                        start,
+                        end,
+                    };
+                }
+            }
+        }
+
+        let key_size = (height * 0.6).round();
+        let mut hovered_key = None;
+        let keyframes: Vec<(KeyframeId, f64)> = track
+            .keyframes
+            .iter()
+            .map(|&(id, time)| (id, moved_keyframes.get(&id).copied().unwrap_or(time)))
+            .collect();
+        for &(id, time) in &keyframes {
+            let _id = ui.push_id_usize(id.0 as usize);
+            let x = axis.to_x(time);
+            ui.set_cursor_screen_pos([x - key_size * 0.5, y + (height - key_size) * 0.5]);
+            ui.invisible_button("##key", [key_size, key_size]);
+            if ui.is_item_hovered() {
+                hovered_key = Some(id);
+            }
+            if ui.is_item_activated() {
+                select(&mut self.selected_keyframes, id, io.key_ctrl);
+                if !io.key_ctrl {
+                    self.selected_clips.clear();
+                }
+                if self.selected_keyframes.contains(&id) {
+                    let origin = self
+                        .selected_keyframes
+                        .iter()
+                        .filter_map(|key| Some((*key, *keyframe_times.get(key)?)))
+                        .collect();
+                    self.interaction = Interaction::DragKeyframes {
+                        grab: axis.to_time(mouse[0]),
+                        grab_time: time,
+                        origin,
+                    };
+                }
+            }
+        }
+
+        // Draw
+        let text_color = ui.style_color(StyleColor::Text);
+        let selected_color = ui.style_color(StyleColor::CheckMark);
+        let track_color = track.color;
+        let draw_list = ui.get_window_draw_list();
+        let row_max = y + height;
+        if index % 2 == 1 {
+            let mut color = ui.style_color(StyleColor::TableRowBgAlt);
+            color[3] = color[3].max(0.03);
+            draw_list
+                .add_rect([lanes[0], y], [lanes[1], row_max], color)
+                .filled(true)
+                .build();
+        }
+        draw_list.with_clip_rect_intersect(
+            [ui.window_pos()[0], y],
+            [lanes[0] - font_size * 0.25, row_max],
+            || {
+                draw_list.add_text(
+                    [
+                        ui.window_pos()[0] + font_size * 0.5,
+                        y + (height - font_size) * 0.5,
+                    ],
+                    text_color,
+                    &track.label,
+                );
+            },
+        );
+        draw_list.with_clip_rect_intersect([lanes[0], y], [lanes[1], row_max], || {
+            for (clip, &(id, start, end)) in track.clips.iter().zip(&clips) {
+                let min = [axis.to_x(start), y + inset];
+                let max = [axis.to_x(end).max(min[0] + 1.0), row_max - inset];
+                let color = if hovered_clip == Some(id) {
+                    ui.style_color(StyleColor::ButtonHovered)
+                } else {
+                    track_color.unwrap_or_else(|| ui.style_color(StyleColor::Button))
+                };
+                draw_list
+                    .add_rect(min, max, color)
+                    .rounding(inset)
+                    .filled(true)
+                    .build();
+                if self.selected_clips.contains(&id) {
+                    draw_list
+                        .add_rect(min, max, selected_color)
+                        .rounding(inset)
+                        .thickness(2.0)
+                        .build();
+                }
+                draw_list.with_clip_rect_intersect(min, max, || {
+                    draw_list.add_text(
+                        [min[0] + font_size * 0.25, y + (height - font_size) * 0.5],
+                        text_color,
+                        &clip.label,
+                    );
+                });
+            }
+
+            let half = key_size * 0.5;
+            for &(id, time) in &keyframes {
+                let center = [axis.to_x(time), y + height * 0.5];
+                let color = if self.selected_keyframes.contains(&id) {
+                    selected_color
+                } else if hovered_key == Some(id) {
+                    text_color
+                } else {
+                    track_color.unwrap_or(text_color)
+                };
+                let diamond = vec![
+                    [center[0], center[1] - half],
+                    [center[0] + half, center[1]],
+                    [center[0], center[1] + half],
+                    [center[0] - half, center[1]],
+                ];
+                draw_list.add_polyline(diamond, color).filled(true).build();
+            }
+        });
+    }
+}
+
+/// Clicking an item replaces the selection with it, unless `toggle` is held, in which case it
+/// is added or removed. Clicking an already selected item keeps the selection, to drag it.
+fn select<T: Copy + Eq + std::hash::Hash>(selection: &mut HashSet<T>, item: T, toggle: bool) {
+    if toggle {
+        if !selection.remove(&item) {
+            selection.insert(item);
+        }
+    } else if !selection.contains(&item) {
+        selection.clear();
+        selection.insert(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Condition;
+
+    #[test]
+    fn test_select() {
+        let mut selection = HashSet::new();
+        select(&mut selection, 1, false);
+        select(&mut selection, 2, true);
+        assert_eq!(selection, HashSet::from([1, 2]));
+        // Clicking a selected item keeps the selection, so that it can be dragged
+        select(&mut selection, 2, false);
+        assert_eq!(selection, HashSet::from([1, 2]));
+        select(&mut selection, 3, false);
+        assert_eq!(selection, HashSet::from([3]));
+        select(&mut selection, 3, true);
+        assert!(selection.is_empty());
+    }
+
+    #[test]
+    fn test_scrub_and_drag_keyframe() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut timeline = Timeline::new("timeline").snap(0.5).label_width(100.0);
+        // The playhead and the time of the keyframe
+        let mut state = (0.0, 1.0);
+        let mut run = |ctx: &mut crate::Context, state: &mut (f64, f64), mouse, down| {
+            ctx.io_mut().add_mouse_pos_event(mouse);
+            ctx.io_mut().add_mouse_button_event(MouseButton::Left, down);
+            let ui = ctx.frame();
+            let mut origin = [0.0, 0.0];
+            let mut frame_height = 0.0;
+            ui.window("Timeline")
+                .position([0.0, 0.0], Condition::Always)
+                .size([800.0, 300.0], Condition::Always)
+                .build(|| {
+                    origin = ui.cursor_screen_pos();
+                    frame_height = ui.frame_height();
+                    let events = timeline.build(ui, [0.0, 0.0], state.0, |items| {
+                        items
+                            .track(TrackId(0), "Track")
+                            .keyframe(KeyframeId(0), state.1)
+                            .build();
+                    });
+                    for event in events {
+                        match event {
+                            TimelineEvent::PlayheadMoved(time) => state.0 = time,
+                            TimelineEvent::KeyframeMoved { key, time } => {
+                                assert_eq!(key, KeyframeId(0));
+                                state.1 = time;
+                            }
+                            TimelineEvent::ClipChanged { .. } => unreachable!(),
+                        }
+                    }
+                });
+            ctx.render();
+            (origin, frame_height)
+        };
+
+        // The ruler and the tracks are both one frame high
+        let (origin, frame_height) = run(&mut ctx, &mut state, [0.0, 0.0], false);
+        let lane_x = origin[0] + 100.0;
+        let ruler_y = origin[1] + frame_height * 0.5;
+        let track_y = origin[1] + frame_height * 1.5;
+        // 100 pixels per second, so 2.4s snaps to 2.5s
+        run(&mut ctx, &mut state, [lane_x + 240.0, ruler_y], false);
+        run(&mut ctx, &mut state, [lane_x + 240.0, ruler_y], true);
+        run(&mut ctx, &mut state, [lane_x + 240.0, ruler_y], false);
+        assert_eq!(state.0, 2.5);
+
+        run(&mut ctx, &mut state, [lane_x + 100.0, track_y], false);
+        run(&mut ctx, &mut state, [lane_x + 100.0, track_y], true);
+        run(&mut ctx, &mut state, [lane_x + 170.0, track_y], true);
+        run(&mut ctx, &mut state, [lane_x + 170.0, track_y], false);
+        assert_eq!(state.1, 1.5);
+        assert!(timeline.selected_keyframes().contains(&KeyframeId(0)));
+    }
+}
@@ -0,0 +1,568 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{sys, Condition, ProgressBar, StyleVar, Ui, WindowFlags};
+
+/// The severity of a [Toast], which sets its accent color.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ToastKind {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn color(self) -> [f32; 4] {
+        match self {
+            ToastKind::Info => [0.4, 0.7, 1.0, 1.0],
+            ToastKind::Success => [0.4, 0.85, 0.4, 1.0],
+            ToastKind::Warning => [1.0, 0.8, 0.3, 1.0],
+            ToastKind::Error => [1.0, 0.4, 0.4, 1.0],
+        }
+    }
+}
+
+/// The corner of the viewport [Toasts] are stacked in.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ToastCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Identifies a toast pushed to [Toasts], to update or dismiss it later.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct ToastId(pub u64);
+
+/// A notification shown by [Toasts], built with [Toast::new].
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct Toast {
+    kind: ToastKind,
+    title: String,
+    body: String,
+    duration: Option<f32>,
+    progress: Option<f32>,
+    actions: Vec<String>,
+}
+
+impl Toast {
+    /// Creates a toast with the given title, which is dismissed after four seconds.
+    pub fn new(kind: ToastKind, title: impl Into<String>) -> Self {
+        Toast {
+            kind,
+            title: title.into(),
+            body: String::new(),
+            duration: Some(4.0),
+            progress: None,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Sets the text shown below the title, wrapped to the width of the toast.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Sets how long the toast is shown, in seconds. The time doesn't run while the toast is
+    /// hovered.
+    pub fn duration(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+
+    /// Shows the toast until it is dismissed by the user or with [Toasts::dismiss].
+    pub fn sticky(mut self) -> Self {
+        self.duration = None;
+        self
+    }
+
+    /// Shows a progress bar, from `0.0` to `1.0`, which can be updated with
+    /// [Toasts::set_progress].
+    pub fn progress(mut self, fraction: f32) -> Self {
+        self.progress = Some(fraction);
+        self
+    }
+
+    /// Adds a button, which dismisses the toast and is reported as a [ToastEvent::Action].
+    pub fn action(mut self, label: impl Into<String>) -> Self {
+        self.actions.push(label.into());
+        self
+    }
+}
+
+/// Something that happened to a toast, as returned by [Toasts::build].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ToastEvent {
+    /// The action button with this index, in the order they were added, was clicked.
+    Action { toast: ToastId, action: usize },
+    /// The toast was closed by the user.
+    Dismissed(ToastId),
+    /// The duration of the toast ran out.
+    Expired(ToastId),
+}
+
+#[derive(Clone, Debug)]
+struct ActiveToast {
+    id: ToastId,
+    toast: Toast,
+    /// Time the toast was shown, for fading in.
+    age: f32,
+    /// Time left before the toast expires, which doesn't run while it is hovered.
+    remaining: Option<f32>,
+    /// Time since the toast started fading out.
+    closing: Option<f32>,
+    hovered: bool,
+}
+
+/// Tells the windows of several managers apart.
+static NEXT_MANAGER: AtomicU64 = AtomicU64::new(1);
+
+/// A manager of toast notifications, which stacks them in a corner of the viewport and
+/// dismisses them after a while.
+///
+/// Toasts are shown on the main viewport, or with the `docking` feature on the viewport of the
+/// current window, so calling [build](Self::build) outside of any window uses the main viewport.
+///
+/// Toasts are queued with [push](Self::push), and drawn as small borderless windows which fade
+/// in and out, by calling [build](Self::build) once per frame, typically at the end of the frame
+/// so they are drawn over the other windows. Only a few toasts are shown at once, the others
+/// wait for them to go away.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut toasts = Toasts::new();
+/// toasts.push(Toast::new(ToastKind::Success, "Saved").body("scene.json"));
+/// let export = toasts.push(
+///     Toast::new(ToastKind::Info, "Exporting")
+///         .progress(0.0)
+///         .sticky()
+///         .action("Cancel"),
+/// );
+/// // Every frame:
+/// toasts.set_progress(export, 0.5);
+/// for event in toasts.build(ui) {
+///     if event == (ToastEvent::Action { toast: export, action: 0 }) {
+///         // Cancel the export
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Toasts {
+    id: u64,
+    corner: ToastCorner,
+    width: f32,
+    max_visible: usize,
+    fade_duration: f32,
+    next_id: u64,
+    last_time: Option<f64>,
+    active: Vec<ActiveToast>,
+}
+
+impl Clone for Toasts {
+    /// Clones the manager and its toasts, which are drawn in windows of their own.
+    fn clone(&self) -> Self {
+        Toasts {
+            id: NEXT_MANAGER.fetch_add(1, Ordering::Relaxed),
+            corner: self.corner,
+            width: self.width,
+            max_visible: self.max_visible,
+            fade_duration: self.fade_duration,
+            next_id: self.next_id,
+            last_time: self.last_time,
+            active: self.active.clone(),
+        }
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toasts {
+    /// Creates an empty manager.
+    pub fn new() -> Self {
+        Toasts {
+            id: NEXT_MANAGER.fetch_add(1, Ordering::Relaxed),
+            corner: ToastCorner::BottomRight,
+            width: 300.0,
+            max_visible: 5,
+            fade_duration: 0.25,
+            next_id: 0,
+            last_time: None,
+            active: Vec::new(),
+        }
+    }
+
+    /// Sets the corner of the viewport the toasts are stacked in, the newest one furthest
+    /// from the corner.
+    pub fn corner(mut self, corner: ToastCorner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Sets the width of the toasts, in pixels.
+    ///
+    /// Defaults to 300.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets how many toasts are shown at once.
+    ///
+    /// Defaults to 5.
+    pub fn max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible.max(1);
+        self
+    }
+
+    /// Sets how long toasts take to fade in and out, in seconds.
+    ///
+    /// Defaults to 0.25.
+    pub fn fade_duration(mut self, seconds: f32) -> Self {
+        self.fade_duration = seconds.max(0.0);
+        self
+    }
+
+    /// Queues a toast, and returns its id.
+    pub fn push(&mut self, toast: Toast) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.active.push(ActiveToast {
+            id,
+            remaining: toast.duration,
+            toast,
+            age: 0.0,
+            closing: None,
+            hovered: false,
+        });
+        id
+    }
+
+    /// Updates the progress bar of a toast. Returns `false` if the toast is gone.
+    pub fn set_progress(&mut self, id: ToastId, fraction: f32) -> bool {
+        match self.active.iter_mut().find(|active| active.id == id) {
+            Some(active) => {
+                active.toast.progress = Some(fraction);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fades out a toast. Returns `false` if the toast is gone.
+    pub fn dismiss(&mut self, id: ToastId) -> bool {
+        match self.active.iter_mut().find(|active| active.id == id) {
+            Some(active) => {
+                active.closing.get_or_insert(0.0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all toasts at once.
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+
+    /// Returns `true` if the toast is shown or waiting to be shown.
+    pub fn contains(&self, id: ToastId) -> bool {
+        self.active.iter().any(|active| active.id == id)
+    }
+
+    /// Returns the number of toasts shown or waiting to be shown.
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns `true` if there are no toasts.
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Draws the toasts, and returns what happened to them this frame.
+    pub fn build(&mut self, ui: &Ui) -> Vec<ToastEvent> {
+        let mut events = Vec::new();
+        let now = ui.time();
+        let delta = self
+            .last_time
+            .map_or(0.0, |last| (now - last).max(0.0) as f32);
+        self.last_time = Some(now);
+
+        let visible = self.active.len().min(self.max_visible);
+        for active in &mut self.active[..visible] {
+            active.age += delta;
+            if let Some(closing) = &mut active.closing {
+                *closing += delta;
+            } else if let Some(remaining) = &mut active.remaining {
+                if !active.hovered {
+                    *remaining -= delta;
+                }
+                if *remaining <= 0.0 {
+                    active.closing = Some(0.0);
+                    events.push(ToastEvent::Expired(active.id));
+                }
+            }
+        }
+        let fade = self.fade_duration;
+        self.active
+            .retain(|active| active.closing.is_none_or(|closing| closing < fade));
+
+        let (work_pos, work_size, viewport_id) = unsafe {
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "docking")] {
+                    let viewport = &*sys::igGetWindowViewport();
+                } else {
+                    let viewport = &*sys::igGetMainViewport();
+                }
+            }
+            (viewport.WorkPos, viewport.WorkSize, viewport.ID)
+        };
+        let padding = ui.current_font_size();
+        let spacing = unsafe { ui.style() }.item_spacing[1];
+        let (right, bottom) = match self.corner {
+            ToastCorner::TopLeft => (false, false),
+            ToastCorner::TopRight => (true, false),
+            ToastCorner::BottomLeft => (false, true),
+            ToastCorner::BottomRight => (true, true),
+        };
+        let x = if right {
+            work_pos.x + work_size.x - padding
+        } else {
+            work_pos.x + padding
+        };
+        let mut y = if bottom {
+            work_pos.y + work_size.y - padding
+        } else {
+            work_pos.y + padding
+        };
+        let pivot = [f32::from(u8::from(right)), f32::from(u8::from(bottom))];
+
+        #[allow(unused_mut)]
+        let mut flags = WindowFlags::NO_DECORATION
+            | WindowFlags::ALWAYS_AUTO_RESIZE
+            | WindowFlags::NO_SAVED_SETTINGS
+            | WindowFlags::NO_FOCUS_ON_APPEARING
+            | WindowFlags::NO_NAV;
+        #[cfg(feature = "docking")]
+        {
+            flags |= WindowFlags::NO_DOCKING;
+        }
+
+        let visible = self.active.len().min(self.max_visible);
+        for active in &mut self.active[..visible] {
+            let fade_in = if fade > 0.0 { active.age / fade } else { 1.0 };
+            let fade_out = match active.closing {
+                Some(closing) if fade > 0.0 => 1.0 - closing / fade,
+                _ => 1.0,
+            };
+            let alpha = fade_in.min(1.0) * fade_out.clamp(0.0, 1.0);
+            let _alpha = ui.push_style_var(StyleVar::Alpha(alpha));
+            #[cfg(feature = "docking")]
+            unsafe {
+                sys::igSetNextWindowViewport(viewport_id);
+            }
+            #[cfg(not(feature = "docking"))]
+            let _ = viewport_id;
+            let height = ui
+                .window(Self::window_name(self.id, active.id))
+                .position([x, y], Condition::Always)
+                .position_pivot(pivot)
+                .size_constraints([self.width, 0.0], [self.width, f32::MAX])
+                .flags(flags)
+                .build(|| {
+                    // Focusing another window draws it above the toasts, so move them back up
+                    unsafe { sys::imgui_rs_BringCurrentWindowToDisplayFront() };
+                    Self::build_toast(ui, active, &mut events);
+                    ui.window_size()[1]
+                })
+                .unwrap_or(0.0);
+            let offset = height + spacing;
+            if bottom {
+                y -= offset;
+            } else {
+                y += offset;
+            }
+        }
+        events
+    }
+
+    /// Returns the name of the window of a toast, unique across managers.
+    fn window_name(manager: u64, toast: ToastId) -> String {
+        format!("##toast{}_{}", manager, toast.0)
+    }
+
+    fn build_toast(ui: &Ui, active: &mut ActiveToast, events: &mut Vec<ToastEvent>) {
+        active.hovered = ui.is_window_hovered();
+        let toast = &active.toast;
+        let color = toast.kind.color();
+
+        // An accent bar along the left edge
+        let pos = ui.window_pos();
+        let size = ui.window_size();
+        ui.get_window_draw_list()
+            .add_rect(pos, [pos[0] + 4.0, pos[1] + size[1]], color)
+            .filled(true)
+            .build();
+
+        ui.text_colored(color, &toast.title);
+        ui.same_line();
+        let close_width = ui.calc_text_size("x")[0] + unsafe { ui.style() }.frame_padding[0] * 2.0;
+        let cursor = ui.cursor_pos();
+        ui.set_cursor_pos([
+            cursor[0] + ui.content_region_avail()[0] - close_width,
+            cursor[1],
+        ]);
+        if ui.small_button("x") && active.closing.is_none() {
+            active.closing = Some(0.0);
+            events.push(ToastEvent::Dismissed(active.id));
+        }
+
+        if !toast.body.is_empty() {
+            ui.text_wrapped(&toast.body);
+        }
+        if let Some(progress) = toast.progress {
+            ProgressBar::new(progress.clamp(0.0, 1.0))
+                .size([-f32::MIN_POSITIVE, ui.text_line_height()])
+                .build(ui);
+        }
+        for (index, label) in toast.actions.iter().enumerate() {
+            if index > 0 {
+                ui.same_line();
+            }
+            let _id = ui.push_id_usize(index);
+            if ui.button(label) && active.closing.is_none() {
+                active.closing = Some(0.0);
+                events.push(ToastEvent::Action {
+                    toast: active.id,
+                    action: index,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(ctx: &mut crate::Context, toasts: &mut Toasts, delta_time: f32) -> Vec<ToastEvent> {
+        ctx.io_mut().delta_time = delta_time;
+        let ui = ctx.frame();
+        let events = toasts.build(ui);
+        ctx.render();
+        events
+    }
+
+    #[test]
+    fn test_expire_and_queue() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut toasts = Toasts::new().max_visible(1);
+        let first = toasts.push(Toast::new(ToastKind::Info, "First").duration(1.0));
+        let second = toasts.push(Toast::new(ToastKind::Error, "Second").sticky());
+        assert_eq!(toasts.len(), 2);
+
+        assert!(run(&mut ctx, &mut toasts, 0.1).is_empty());
+        assert!(run(&mut ctx, &mut toasts, 0.5).is_empty());
+        assert_eq!(
+            run(&mut ctx, &mut toasts, 0.6),
+            [ToastEvent::Expired(first)]
+        );
+        // Still fading out
+        assert!(toasts.contains(first));
+        run(&mut ctx, &mut toasts, 0.3);
+        assert!(!toasts.contains(first));
+
+        // The queued toast is shown, and stays until it is dismissed
+        for _ in 0..5 {
+            assert!(run(&mut ctx, &mut toasts, 1.0).is_empty());
+        }
+        assert!(toasts.set_progress(second, 0.5));
+        assert!(toasts.dismiss(second));
+        run(&mut ctx, &mut toasts, 0.3);
+        assert!(toasts.is_empty());
+        assert!(!toasts.set_progress(second, 1.0));
+    }
+
+    #[test]
+    fn test_stacked_in_corner() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut toasts = Toasts::new().corner(ToastCorner::TopLeft).width(200.0);
+        toasts.push(Toast::new(ToastKind::Info, "One"));
+        toasts.push(Toast::new(ToastKind::Warning, "Two").body("Body"));
+        let mut rects = Vec::new();
+        for _ in 0..3 {
+            run(&mut ctx, &mut toasts, 0.1);
+        }
+        ctx.io_mut().delta_time = 0.1;
+        let ui = ctx.frame();
+        toasts.build(ui);
+        for id in 0..2 {
+            ui.window(Toasts::window_name(toasts.id, ToastId(id)))
+                .build(|| rects.push((ui.window_pos(), ui.window_size())));
+        }
+        ctx.render();
+
+        let padding = 13.0;
+        let spacing = ctx.style().item_spacing[1];
+        assert_eq!(rects[0].0, [padding, padding]);
+        assert_eq!(rects[0].1[0], 200.0);
+        // The second toast is below the first one
+        assert_eq!(rects[1].0[1], padding + rects[0].1[1] + spacing);
+    }
+
+    #[test]
+    fn test_managers_have_own_windows() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut top = Toasts::new().corner(ToastCorner::TopLeft);
+        let mut bottom = Toasts::new().corner(ToastCorner::BottomRight);
+        top.push(Toast::new(ToastKind::Info, "Top"));
+        bottom.push(Toast::new(ToastKind::Info, "Bottom"));
+        let mut positions = Vec::new();
+        for _ in 0..3 {
+            let ui = ctx.frame();
+            top.build(ui);
+            bottom.build(ui);
+            positions.clear();
+            for toasts in [&top, &bottom] {
+                ui.window(Toasts::window_name(toasts.id, ToastId(0)))
+                    .build(|| positions.push(ui.window_pos()));
+            }
+            ctx.render();
+        }
+        assert_eq!(positions[0], [13.0, 13.0]);
+        assert!(positions[1][1] > 384.0);
+    }
+
+    #[test]
+    fn test_stay_above_focused_window() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut toasts = Toasts::new().corner(ToastCorner::TopLeft);
+        toasts.push(Toast::new(ToastKind::Info, "On top").sticky());
+        ctx.io_mut().add_mouse_pos_event([20.0, 20.0]);
+        for frame in 0..3 {
+            let ui = ctx.frame();
+            toasts.build(ui);
+            // A fullscreen window, focused after the toasts were shown
+            ui.window("app")
+                .position([0.0, 0.0], Condition::Always)
+                .size([1024.0, 768.0], Condition::Always)
+                .focused(frame == 0)
+                .build(|| {});
+            ctx.render();
+        }
+        assert!(toasts.active[0].hovered);
+    }
+}
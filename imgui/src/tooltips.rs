@@ -0,0 +1,272 @@
+use crate::{sys, Condition, HoveredFlags, TooltipToken, Ui};
+
+/// How long the mouse must hover an item before its [TooltipConfig] tooltip is shown.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TooltipDelay {
+    /// Show the tooltip right away.
+    None,
+    /// Wait for [`Style::hover_delay_short`](crate::Style::hover_delay_short), 0.15 seconds by
+    /// default.
+    Short,
+    /// Wait for [`Style::hover_delay_normal`](crate::Style::hover_delay_normal), 0.4 seconds by
+    /// default.
+    Normal,
+}
+
+impl TooltipDelay {
+    fn flag(self) -> HoveredFlags {
+        match self {
+            TooltipDelay::None => HoveredFlags::DELAY_NONE,
+            TooltipDelay::Short => HoveredFlags::DELAY_SHORT,
+            TooltipDelay::Normal => HoveredFlags::DELAY_NORMAL,
+        }
+    }
+}
+
+/// Where a [TooltipConfig] tooltip is shown.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TooltipPlacement {
+    /// Next to the mouse cursor, following it.
+    #[default]
+    FollowMouse,
+    /// Below the item, where it stays while the mouse moves over the item.
+    BelowItem,
+}
+
+/// Builder for a tooltip shown when the last item is hovered, created with
+/// [`Ui::item_tooltip_config`].
+///
+/// By default the tooltip follows [`Style::hover_flags_for_tooltip_mouse`](crate::Style::hover_flags_for_tooltip_mouse),
+/// or [`Style::hover_flags_for_tooltip_nav`](crate::Style::hover_flags_for_tooltip_nav) while
+/// navigating with the keyboard or a gamepad, and their delays, like [`Ui::item_tooltip`].
+/// Setting a delay or the stationary requirement overrides that part of the style for this
+/// tooltip.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// ui.button("Export");
+/// ui.item_tooltip_config()
+///     .delay(TooltipDelay::Short)
+///     .max_width(300.0)
+///     .placement(TooltipPlacement::BelowItem)
+///     .build(|| {
+///         ui.text("Export the scene");
+///         ui.text_disabled("Writes every layer to its own file in the export folder.");
+///     });
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct TooltipConfig<'ui> {
+    ui: &'ui Ui,
+    delay: Option<TooltipDelay>,
+    stationary: Option<bool>,
+    max_width: Option<f32>,
+    placement: TooltipPlacement,
+}
+
+impl<'ui> TooltipConfig<'ui> {
+    /// Sets how long the item must be hovered before the tooltip is shown.
+    ///
+    /// Default: the delay of the style's tooltip flags
+    pub fn delay(mut self, delay: TooltipDelay) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Sets whether the mouse must stop over the item for
+    /// [`Style::hover_stationary_delay`](crate::Style::hover_stationary_delay) before the
+    /// delay starts, so the tooltip doesn't pop up while the mouse passes over the item.
+    ///
+    /// Default: as set in the style's tooltip flags
+    pub fn stationary(mut self, stationary: bool) -> Self {
+        self.stationary = Some(stationary);
+        self
+    }
+
+    /// Sets the width text is wrapped at, in pixels, so long tooltips don't span the screen.
+    ///
+    /// Default: no wrapping
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Sets where the tooltip is shown.
+    ///
+    /// Default: [`TooltipPlacement::FollowMouse`]
+    pub fn placement(mut self, placement: TooltipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Returns the flags the last item is checked with.
+    fn hovered_flags(&self) -> HoveredFlags {
+        if self.delay.is_none() && self.stationary.is_none() {
+            return HoveredFlags::FOR_TOOLTIP;
+        }
+        // The mouse or the navigation flags of the style, whichever `FOR_TOOLTIP` would use
+        let style_flags = unsafe { sys::imgui_rs_GetHoverFlagsForTooltip() };
+        let mut flags = HoveredFlags::from_bits_truncate(style_flags as u32);
+        if let Some(delay) = self.delay {
+            flags.remove(HoveredFlags::DELAY_NONE | HoveredFlags::DELAY_SHORT);
+            flags.remove(HoveredFlags::DELAY_NORMAL);
+            flags.insert(delay.flag());
+        }
+        if let Some(stationary) = self.stationary {
+            flags.set(HoveredFlags::STATIONARY, stationary);
+        }
+        flags
+    }
+
+    /// Shows the tooltip if the last item is hovered, and runs a closure to construct its
+    /// contents.
+    ///
+    /// Returns the result of the closure, if it is called.
+    #[doc(alias = "BeginItemTooltip")]
+    pub fn build<R, F: FnOnce() -> R>(self, f: F) -> Option<R> {
+        let ui = self.ui;
+        if !ui.is_item_hovered_with_flags(self.hovered_flags()) {
+            return None;
+        }
+        if self.placement == TooltipPlacement::BelowItem {
+            let min = ui.item_rect_min();
+            let max = ui.item_rect_max();
+            let spacing = ui.clone_style().item_spacing[1];
+            unsafe {
+                sys::igSetNextWindowPos(
+                    sys::ImVec2::new(min[0], max[1] + spacing),
+                    Condition::Always as i32,
+                    sys::ImVec2::zero(),
+                );
+            }
+        }
+        let _tooltip = ui.begin_tooltip()?;
+        let _wrap = self.max_width.map(|max_width| {
+            let padding = ui.clone_style().window_padding[0];
+            ui.push_text_wrap_pos_with_pos((max_width - padding).max(1.0))
+        });
+        Some(f())
+    }
+
+    /// Shows the tooltip with simple text content if the last item is hovered.
+    #[doc(alias = "SetItemTooltip")]
+    pub fn text(self, text: impl AsRef<str>) {
+        let ui = self.ui;
+        let wrapped = self.max_width.is_some();
+        self.build(|| {
+            if wrapped {
+                ui.text_wrapped(text);
+            } else {
+                ui.text(text);
+            }
+        });
+    }
+}
+
+/// # Tooltips: item tooltips
+impl Ui {
+    /// Construct a tooltip for the last item, shown when it is hovered according to
+    /// [`Style::hover_flags_for_tooltip_mouse`](crate::Style::hover_flags_for_tooltip_mouse),
+    /// which waits for the mouse to stop over the item by default, or
+    /// [`Style::hover_flags_for_tooltip_nav`](crate::Style::hover_flags_for_tooltip_nav) while
+    /// navigating with the keyboard or a gamepad.
+    ///
+    /// This is the same as checking [`HoveredFlags::FOR_TOOLTIP`] before
+    /// [`tooltip`](Self::tooltip). Use [`item_tooltip_config`](Self::item_tooltip_config) to
+    /// change the delay, wrapping or placement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use imgui::*;
+    /// fn user_interface(ui: &Ui) {
+    ///     ui.button("Hover over me");
+    ///     ui.item_tooltip(|| {
+    ///         ui.text_colored([1.0, 0.0, 0.0, 1.0], "I'm red!");
+    ///     });
+    /// }
+    /// ```
+    #[doc(alias = "BeginItemTooltip", alias = "EndTooltip")]
+    pub fn item_tooltip<F: FnOnce()>(&self, f: F) {
+        if let Some(_tooltip) = self.begin_item_tooltip() {
+            f();
+        }
+    }
+
+    /// Construct a tooltip for the last item, like [`item_tooltip`](Self::item_tooltip).
+    ///
+    /// Can return a `TooltipToken` that must be ended by calling `.end()`
+    #[doc(alias = "BeginItemTooltip")]
+    pub fn begin_item_tooltip(&self) -> Option<TooltipToken<'_>> {
+        if unsafe { sys::igBeginItemTooltip() } {
+            Some(TooltipToken::new(self))
+        } else {
+            None
+        }
+    }
+
+    /// Shortcut to call [`Self::item_tooltip`] with simple text content.
+    #[doc(alias = "SetItemTooltip")]
+    pub fn item_tooltip_text<T: AsRef<str>>(&self, text: T) {
+        self.item_tooltip(|| self.text(text));
+    }
+
+    /// Creates a [TooltipConfig] builder for a tooltip of the last item.
+    pub fn item_tooltip_config(&self) -> TooltipConfig<'_> {
+        TooltipConfig {
+            ui: self,
+            delay: None,
+            stationary: None,
+            max_width: None,
+            placement: TooltipPlacement::FollowMouse,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tooltip_delays() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        ctx.io_mut().add_mouse_pos_event([20.0, 30.0]);
+
+        // Returns which of the tooltips were shown
+        let mut frame = || {
+            let ui = ctx.frame();
+            let mut shown = [false; 3];
+            ui.window("Tooltips")
+                .position([0.0, 0.0], Condition::Always)
+                .build(|| {
+                    ui.button_with_size("Hover", [200.0, 100.0]);
+                    ui.item_tooltip(|| shown[0] = true);
+                    ui.item_tooltip_config()
+                        .delay(TooltipDelay::None)
+                        .stationary(false)
+                        .build(|| shown[1] = true);
+                    ui.item_tooltip_config()
+                        .delay(TooltipDelay::Normal)
+                        .stationary(false)
+                        .placement(TooltipPlacement::BelowItem)
+                        .max_width(100.0)
+                        .build(|| shown[2] = true);
+                });
+            ctx.render();
+            shown
+        };
+
+        let shown: Vec<_> = (0..40).map(|_| frame()).collect();
+        let first_shown = |tooltip: usize| shown.iter().position(|s| s[tooltip]).unwrap();
+        // At 60 frames per second, the style's default waits for the mouse to stop for 0.15
+        // seconds, and the normal delay is 0.4 seconds
+        let hovered = first_shown(1);
+        assert!((8..=10).contains(&(first_shown(0) - hovered)));
+        assert!((23..=25).contains(&(first_shown(2) - hovered)));
+        assert!(shown[hovered..].iter().all(|s| s[1]));
+    }
+}
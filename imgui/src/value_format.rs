@@ -0,0 +1,93 @@
+//! Number and date formatting shared by the plot, timeline and file browser widgets.
+
+/// Returns the smallest step among 1, 2 and 5 times a power of ten which cuts `span` in at
+/// most `max_count` parts.
+pub(crate) fn nice_step(span: f64, max_count: f64) -> f64 {
+    let raw = span / max_count;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|&step| step >= raw)
+        .unwrap_or(magnitude * 10.0)
+}
+
+/// Formats a number with the precision needed for values `step` apart.
+pub(crate) fn format_number(value: f64, step: f64) -> String {
+    // Avoids showing "-0" for values which are only off by rounding errors
+    let value = if value.abs() < step * 1e-9 {
+        0.0
+    } else {
+        value
+    };
+    if !(1e-4..1e6).contains(&step) {
+        format!("{:.2e}", value)
+    } else {
+        let decimals = (-step.log10().floor()).max(0.0) as usize;
+        format!("{:.*}", decimals, value)
+    }
+}
+
+/// Splits seconds since the Unix epoch into a UTC `YYYY-MM-DD` date and `HH:MM:SS.mmm` time.
+pub(crate) fn utc_date_time(seconds: f64) -> (String, String) {
+    let millis = (seconds * 1000.0).round() as i64;
+    let days = millis.div_euclid(86_400_000);
+    let millis_of_day = millis.rem_euclid(86_400_000);
+
+    // Days to civil date, after Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let date = format!("{:04}-{:02}-{:02}", year, month, day);
+    let time = format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis_of_day / 3_600_000,
+        millis_of_day / 60_000 % 60,
+        millis_of_day / 1000 % 60,
+        millis_of_day % 1000
+    );
+    (date, time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_step() {
+        assert_eq!(nice_step(1.0, 5.0), 0.2);
+        assert_eq!(nice_step(6912.0, 8.0), 1000.0);
+        assert_eq!(nice_step(30.0, 4.0), 10.0);
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(0.6000000000000001, 0.2), "0.6");
+        assert_eq!(format_number(-1e-13, 0.1), "0.0");
+        assert_eq!(format_number(1500.0, 500.0), "1500");
+        assert_eq!(format_number(2e7, 1e6), "2.00e7");
+    }
+
+    #[test]
+    fn test_utc_date_time() {
+        assert_eq!(
+            utc_date_time(0.0),
+            ("1970-01-01".to_string(), "00:00:00.000".to_string())
+        );
+        assert_eq!(
+            utc_date_time(951_827_696.25),
+            ("2000-02-29".to_string(), "12:34:56.250".to_string())
+        );
+        assert_eq!(
+            utc_date_time(-1.0),
+            ("1969-12-31".to_string(), "23:59:59.000".to_string())
+        );
+    }
+}
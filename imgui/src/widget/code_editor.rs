@@ -0,0 +1,1774 @@
+use std::cmp::{max, min};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+use crate::math::MintVec2;
+use crate::sys;
+use crate::text_match::chars_eq_ignore_case;
+use crate::{Key, ListClipper, MouseButton, MouseCursor, StyleColor, StyleVar, Ui};
+
+/// A position in the text of a [CodeEditor]. Columns are counted in characters.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl TextPosition {
+    /// Creates a new position.
+    pub const fn new(line: usize, column: usize) -> Self {
+        TextPosition { line, column }
+    }
+}
+
+/// The kind of a [CodeToken], which picks its color from the [CodeEditorPalette].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Default,
+    Keyword,
+    Type,
+    Identifier,
+    Number,
+    String,
+    Comment,
+    Punctuation,
+    Preprocessor,
+}
+
+/// A highlighted span of a line, as produced by a [CodeTokenizer].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeToken {
+    /// Byte range of the token in the line.
+    pub range: Range<usize>,
+    pub kind: TokenKind,
+}
+
+/// Splits lines into [CodeToken]s for syntax highlighting in a [CodeEditor].
+///
+/// Lines are tokenized one after the other, and the state returned for a line is passed to
+/// the next one, which allows constructs spanning lines such as block comments. Tokens must be
+/// in order and must not overlap. Text which isn't covered by a token is drawn with
+/// [TokenKind::Default].
+pub trait CodeTokenizer {
+    /// Appends the tokens of `line` to `out`, starting in `state`, and returns the state at the
+    /// end of the line. The first line starts in state `0`.
+    fn tokenize_line(&self, line: &str, state: u32, out: &mut Vec<CodeToken>) -> u32;
+}
+
+/// A [CodeTokenizer] which doesn't highlight anything.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PlainTokenizer;
+
+impl CodeTokenizer for PlainTokenizer {
+    fn tokenize_line(&self, _line: &str, state: u32, _out: &mut Vec<CodeToken>) -> u32 {
+        state
+    }
+}
+
+/// A configurable [CodeTokenizer] for the usual shape of programming languages: keywords and
+/// type names, line and block comments, quoted strings, numbers and punctuation.
+///
+/// # Example
+///
+/// ```
+/// let lua = imgui::LanguageTokenizer::new()
+///     .keywords(["local", "function", "end", "if", "then", "return"])
+///     .line_comment("--")
+///     .block_comment("--[[", "]]")
+///     .string_delimiters(['"', '\'']);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LanguageTokenizer {
+    keywords: HashSet<String>,
+    types: HashSet<String>,
+    line_comment: Option<String>,
+    block_comment: Option<(String, String)>,
+    string_delimiters: Vec<char>,
+    preprocessor: Option<char>,
+}
+
+const STATE_NORMAL: u32 = 0;
+const STATE_BLOCK_COMMENT: u32 = 1;
+
+impl LanguageTokenizer {
+    /// Creates a tokenizer which only recognizes identifiers, numbers and punctuation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds words highlighted as [TokenKind::Keyword].
+    pub fn keywords<S: Into<String>>(mut self, keywords: impl IntoIterator<Item = S>) -> Self {
+        self.keywords.extend(keywords.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds words highlighted as [TokenKind::Type].
+    pub fn types<S: Into<String>>(mut self, types: impl IntoIterator<Item = S>) -> Self {
+        self.types.extend(types.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the prefix of comments which run to the end of the line, e.g. `//`.
+    pub fn line_comment(mut self, prefix: impl Into<String>) -> Self {
+        self.line_comment = Some(prefix.into());
+        self
+    }
+
+    /// Sets the delimiters of comments which can span lines, e.g. `/*` and `*/`.
+    pub fn block_comment(mut self, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.block_comment = Some((start.into(), end.into()));
+        self
+    }
+
+    /// Sets the characters which start and end strings. A backslash escapes the next character.
+    pub fn string_delimiters(mut self, delimiters: impl IntoIterator<Item = char>) -> Self {
+        self.string_delimiters = delimiters.into_iter().collect();
+        self
+    }
+
+    /// Sets the character which starts preprocessor lines, e.g. `#`.
+    pub fn preprocessor(mut self, prefix: char) -> Self {
+        self.preprocessor = Some(prefix);
+        self
+    }
+
+    fn word_kind(&self, word: &str) -> TokenKind {
+        if self.keywords.contains(word) {
+            TokenKind::Keyword
+        } else if self.types.contains(word) {
+            TokenKind::Type
+        } else {
+            TokenKind::Identifier
+        }
+    }
+}
+
+impl CodeTokenizer for LanguageTokenizer {
+    fn tokenize_line(&self, line: &str, state: u32, out: &mut Vec<CodeToken>) -> u32 {
+        let mut i = 0;
+        if state == STATE_BLOCK_COMMENT {
+            let Some((_, end)) = &self.block_comment else {
+                return STATE_NORMAL;
+            };
+            match line.find(end.as_str()) {
+                Some(found) => {
+                    i = found + end.len();
+                    out.push(CodeToken {
+                        range: 0..i,
+                        kind: TokenKind::Comment,
+                    });
+                }
+                None => {
+                    out.push(CodeToken {
+                        range: 0..line.len(),
+                        kind: TokenKind::Comment,
+                    });
+                    return STATE_BLOCK_COMMENT;
+                }
+            }
+        } else if let Some(prefix) = self.preprocessor {
+            if line.trim_start().starts_with(prefix) {
+                out.push(CodeToken {
+                    range: 0..line.len(),
+                    kind: TokenKind::Preprocessor,
+                });
+                return STATE_NORMAL;
+            }
+        }
+
+        while let Some(c) = line[i..].chars().next() {
+            let rest = &line[i..];
+            let start = i;
+            // Block comments go first, as their start may begin with the line comment prefix
+            let kind = if let Some((block_start, block_end)) = self
+                .block_comment
+                .as_ref()
+                .filter(|(block_start, _)| rest.starts_with(block_start.as_str()))
+            {
+                let body = start + block_start.len();
+                match line[body..].find(block_end.as_str()) {
+                    Some(found) => i = body + found + block_end.len(),
+                    None => {
+                        out.push(CodeToken {
+                            range: start..line.len(),
+                            kind: TokenKind::Comment,
+                        });
+                        return STATE_BLOCK_COMMENT;
+                    }
+                }
+                TokenKind::Comment
+            } else if self
+                .line_comment
+                .as_ref()
+                .is_some_and(|prefix| rest.starts_with(prefix.as_str()))
+            {
+                out.push(CodeToken {
+                    range: start..line.len(),
+                    kind: TokenKind::Comment,
+                });
+                break;
+            } else if c.is_whitespace() {
+                i += c.len_utf8();
+                continue;
+            } else if self.string_delimiters.contains(&c) {
+                let mut chars = rest.char_indices().skip(1);
+                i = line.len();
+                while let Some((offset, next)) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        i = start + offset + next.len_utf8();
+                        break;
+                    }
+                }
+                TokenKind::String
+            } else if c.is_ascii_digit() {
+                i += rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '.' || c == '_'))
+                    .unwrap_or(rest.len());
+                TokenKind::Number
+            } else if c.is_alphabetic() || c == '_' {
+                i += rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                self.word_kind(&line[start..i])
+            } else {
+                i += c.len_utf8();
+                TokenKind::Punctuation
+            };
+            out.push(CodeToken {
+                range: start..i,
+                kind,
+            });
+        }
+        STATE_NORMAL
+    }
+}
+
+/// The colors of a [CodeEditor].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CodeEditorPalette {
+    pub default: [f32; 4],
+    pub keyword: [f32; 4],
+    pub type_name: [f32; 4],
+    pub identifier: [f32; 4],
+    pub number: [f32; 4],
+    pub string: [f32; 4],
+    pub comment: [f32; 4],
+    pub punctuation: [f32; 4],
+    pub preprocessor: [f32; 4],
+    pub background: [f32; 4],
+    pub gutter: [f32; 4],
+    pub line_number: [f32; 4],
+    pub current_line_number: [f32; 4],
+    pub current_line: [f32; 4],
+    pub selection: [f32; 4],
+    pub cursor: [f32; 4],
+    pub breakpoint: [f32; 4],
+}
+
+impl CodeEditorPalette {
+    /// A palette for dark styles.
+    pub const fn dark() -> Self {
+        CodeEditorPalette {
+            default: [0.86, 0.86, 0.86, 1.0],
+            keyword: [0.34, 0.61, 0.84, 1.0],
+            type_name: [0.31, 0.79, 0.69, 1.0],
+            identifier: [0.86, 0.86, 0.86, 1.0],
+            number: [0.71, 0.81, 0.66, 1.0],
+            string: [0.81, 0.57, 0.47, 1.0],
+            comment: [0.42, 0.60, 0.33, 1.0],
+            punctuation: [0.80, 0.80, 0.80, 1.0],
+            preprocessor: [0.77, 0.53, 0.75, 1.0],
+            background: [0.12, 0.12, 0.12, 1.0],
+            gutter: [0.12, 0.12, 0.12, 1.0],
+            line_number: [0.52, 0.52, 0.52, 1.0],
+            current_line_number: [0.78, 0.78, 0.78, 1.0],
+            current_line: [1.0, 1.0, 1.0, 0.06],
+            selection: [0.15, 0.31, 0.47, 1.0],
+            cursor: [0.90, 0.90, 0.90, 1.0],
+            breakpoint: [0.89, 0.27, 0.27, 1.0],
+        }
+    }
+
+    /// A palette for light styles.
+    pub const fn light() -> Self {
+        CodeEditorPalette {
+            default: [0.0, 0.0, 0.0, 1.0],
+            keyword: [0.0, 0.0, 1.0, 1.0],
+            type_name: [0.15, 0.5, 0.6, 1.0],
+            identifier: [0.0, 0.0, 0.0, 1.0],
+            number: [0.04, 0.53, 0.35, 1.0],
+            string: [0.64, 0.08, 0.08, 1.0],
+            comment: [0.0, 0.5, 0.0, 1.0],
+            punctuation: [0.2, 0.2, 0.2, 1.0],
+            preprocessor: [0.5, 0.2, 0.6, 1.0],
+            background: [1.0, 1.0, 1.0, 1.0],
+            gutter: [0.96, 0.96, 0.96, 1.0],
+            line_number: [0.55, 0.55, 0.55, 1.0],
+            current_line_number: [0.1, 0.1, 0.1, 1.0],
+            current_line: [0.0, 0.0, 0.0, 0.05],
+            selection: [0.68, 0.84, 1.0, 1.0],
+            cursor: [0.0, 0.0, 0.0, 1.0],
+            breakpoint: [0.89, 0.27, 0.27, 1.0],
+        }
+    }
+
+    /// Returns the color of tokens of the given kind.
+    pub fn token_color(&self, kind: TokenKind) -> [f32; 4] {
+        match kind {
+            TokenKind::Default => self.default,
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Type => self.type_name,
+            TokenKind::Identifier => self.identifier,
+            TokenKind::Number => self.number,
+            TokenKind::String => self.string,
+            TokenKind::Comment => self.comment,
+            TokenKind::Punctuation => self.punctuation,
+            TokenKind::Preprocessor => self.preprocessor,
+        }
+    }
+}
+
+impl Default for CodeEditorPalette {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// A colored mark in the marker lane of a [CodeEditor], e.g. for an error or a search result.
+/// The message is shown as a tooltip when the mark is hovered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodeMarker {
+    pub color: [f32; 4],
+    pub message: String,
+}
+
+impl CodeMarker {
+    /// Creates a new marker.
+    pub fn new(color: impl Into<[f32; 4]>, message: impl Into<String>) -> Self {
+        CodeMarker {
+            color: color.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// What happened in a [CodeEditor] this frame, as returned by [CodeEditor::build].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CodeEditorResponse {
+    /// The text was edited by the user.
+    pub changed: bool,
+    /// The breakpoint of this line was toggled by the user.
+    pub breakpoint_toggled: Option<usize>,
+}
+
+/// An undoable replacement of `removed` by `inserted` at `start`, with the selections as
+/// `(anchor, cursor)` before and after.
+#[derive(Clone, Debug)]
+struct Edit {
+    start: TextPosition,
+    removed: String,
+    inserted: String,
+    before: (TextPosition, TextPosition),
+    after: (TextPosition, TextPosition),
+}
+
+#[derive(Clone, Debug, Default)]
+struct FindBar {
+    open: bool,
+    replace: bool,
+    focus: bool,
+    query: String,
+    replacement: String,
+    case_sensitive: bool,
+}
+
+/// A multi-line text editor for source code, with syntax highlighting, line numbers, a
+/// breakpoint and a marker lane, selection, undo/redo and find/replace.
+///
+/// Highlighting is done by a [CodeTokenizer], and only the visible lines are tokenized and
+/// drawn, so large files stay fast. The editor keeps its text and state between frames, so it
+/// should be stored alongside the rest of the UI state rather than recreated every frame.
+///
+/// Besides the usual cursor movement and editing keys, the editor handles Ctrl+A/C/X/V,
+/// Ctrl+Z and Ctrl+Y (or Ctrl+Shift+Z) for undo and redo, Tab and Shift+Tab to indent, and
+/// Ctrl+F, Ctrl+H and F3 for the find bar. On macOS the Cmd key replaces Ctrl.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut ctx = imgui::Context::create();
+/// # let ui = ctx.frame();
+/// let mut editor = imgui::CodeEditor::new("script").tokenizer(
+///     imgui::LanguageTokenizer::new()
+///         .keywords(["fn", "let", "if", "else"])
+///         .line_comment("//"),
+/// );
+/// editor.set_text("fn main() {\n    let x = 1;\n}");
+/// // Every frame:
+/// if editor.build(ui, [0.0, 300.0]).changed {
+///     println!("{}", editor.text());
+/// }
+/// ```
+pub struct CodeEditor {
+    id: String,
+    tokenizer: Box<dyn CodeTokenizer>,
+    palette: CodeEditorPalette,
+    read_only: bool,
+    show_line_numbers: bool,
+    tab_size: usize,
+
+    lines: Vec<String>,
+    anchor: TextPosition,
+    cursor: TextPosition,
+    /// Column kept when moving up and down through shorter lines.
+    preferred_column: Option<usize>,
+    breakpoints: BTreeSet<usize>,
+    markers: BTreeMap<usize, CodeMarker>,
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    /// Whether typing may be merged into the last undo step.
+    merge_undo: bool,
+    /// Incremented on every change of the text.
+    version: u64,
+
+    /// Tokens of the first `tokens.len()` lines, and the tokenizer state at the end of each.
+    tokens: Vec<Vec<CodeToken>>,
+    end_states: Vec<u32>,
+    max_line_width: f32,
+    scroll_to_cursor: bool,
+    reset_blink: bool,
+    blink_start: f64,
+    dragging: bool,
+    find: FindBar,
+}
+
+impl fmt::Debug for CodeEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeEditor")
+            .field("id", &self.id)
+            .field("lines", &self.lines.len())
+            .field("anchor", &self.anchor)
+            .field("cursor", &self.cursor)
+            .field("read_only", &self.read_only)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CodeEditor {
+    /// Creates a new, empty code editor with the given id.
+    pub fn new(id: impl Into<String>) -> Self {
+        CodeEditor {
+            id: id.into(),
+            tokenizer: Box::new(PlainTokenizer),
+            palette: CodeEditorPalette::default(),
+            read_only: false,
+            show_line_numbers: true,
+            tab_size: 4,
+            lines: vec![String::new()],
+            anchor: TextPosition::default(),
+            cursor: TextPosition::default(),
+            preferred_column: None,
+            breakpoints: BTreeSet::new(),
+            markers: BTreeMap::new(),
+            undo: Vec::new(),
+            redo: Vec::new(),
+            merge_undo: false,
+            version: 0,
+            tokens: Vec::new(),
+            end_states: Vec::new(),
+            max_line_width: 0.0,
+            scroll_to_cursor: false,
+            reset_blink: true,
+            blink_start: 0.0,
+            dragging: false,
+            find: FindBar::default(),
+        }
+    }
+
+    /// Sets the tokenizer used for syntax highlighting.
+    ///
+    /// Default: [PlainTokenizer]
+    pub fn tokenizer(mut self, tokenizer: impl CodeTokenizer + 'static) -> Self {
+        self.tokenizer = Box::new(tokenizer);
+        self.invalidate_tokens(0);
+        self
+    }
+
+    /// Sets the colors.
+    ///
+    /// Default: `CodeEditorPalette::dark()`
+    pub fn palette(mut self, palette: CodeEditorPalette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Enables/disables editing by the user. The text can still be changed programmatically.
+    ///
+    /// Disabled by default.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Enables/disables the line numbers in the gutter.
+    ///
+    /// Enabled by default.
+    pub fn show_line_numbers(mut self, show_line_numbers: bool) -> Self {
+        self.show_line_numbers = show_line_numbers;
+        self
+    }
+
+    /// Sets the number of spaces inserted by Tab.
+    ///
+    /// Default: 4
+    pub fn tab_size(mut self, tab_size: usize) -> Self {
+        self.tab_size = tab_size.max(1);
+        self
+    }
+
+    /// Returns the whole text, with lines separated by `\n`.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Replaces the whole text, and clears the undo history and the selection. `\r\n` line
+    /// endings are converted to `\n`.
+    pub fn set_text(&mut self, text: &str) {
+        self.lines = text
+            .replace("\r\n", "\n")
+            .split('\n')
+            .map(String::from)
+            .collect();
+        self.anchor = TextPosition::default();
+        self.cursor = TextPosition::default();
+        self.preferred_column = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.merge_undo = false;
+        self.version += 1;
+        self.max_line_width = 0.0;
+        self.breakpoints.retain(|&line| line < self.lines.len());
+        self.markers.retain(|&line, _| line < self.lines.len());
+        self.invalidate_tokens(0);
+    }
+
+    /// Returns the lines of the text.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns the cursor position.
+    pub fn cursor(&self) -> TextPosition {
+        self.cursor
+    }
+
+    /// Moves the cursor, clearing the selection, and scrolls it into view.
+    pub fn set_cursor(&mut self, position: TextPosition) {
+        let position = self.clamp(position);
+        self.set_selection(position..position);
+    }
+
+    /// Returns the selected range, if any, ordered from start to end.
+    pub fn selection(&self) -> Option<Range<TextPosition>> {
+        if self.anchor == self.cursor {
+            None
+        } else {
+            let (start, end) = self.ordered_selection();
+            Some(start..end)
+        }
+    }
+
+    /// Selects the given range, with the cursor at its end, and scrolls it into view.
+    pub fn set_selection(&mut self, range: Range<TextPosition>) {
+        self.anchor = self.clamp(range.start);
+        self.cursor = self.clamp(range.end);
+        self.cursor_moved();
+    }
+
+    /// Selects the whole text.
+    pub fn select_all(&mut self) {
+        self.anchor = TextPosition::default();
+        self.cursor = self.end();
+        self.cursor_moved();
+    }
+
+    /// Returns the selected text, which is empty without a selection.
+    pub fn selected_text(&self) -> String {
+        let (start, end) = self.ordered_selection();
+        self.text_range(start, end)
+    }
+
+    /// Replaces the selection, or inserts at the cursor, with the given text. This can be
+    /// undone.
+    pub fn insert_text(&mut self, text: &str) {
+        let (start, end) = self.ordered_selection();
+        self.edit(start, end, &text.replace("\r\n", "\n"), false);
+    }
+
+    /// Deletes the selected text. This can be undone.
+    pub fn delete_selection(&mut self) {
+        let (start, end) = self.ordered_selection();
+        if start != end {
+            self.edit(start, end, "", false);
+        }
+    }
+
+    /// Returns `true` if there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undoes the last edit. Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo.pop() else {
+            return false;
+        };
+        let end = end_of(edit.start, &edit.inserted);
+        self.replace(edit.start, end, &edit.removed);
+        (self.anchor, self.cursor) = edit.before;
+        self.redo.push(edit);
+        self.cursor_moved();
+        true
+    }
+
+    /// Redoes the last undone edit. Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+        let end = end_of(edit.start, &edit.removed);
+        self.replace(edit.start, end, &edit.inserted);
+        (self.anchor, self.cursor) = edit.after;
+        self.undo.push(edit);
+        self.cursor_moved();
+        true
+    }
+
+    /// Returns the lines with a breakpoint.
+    pub fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    /// Adds or removes the breakpoint of a line. Breakpoints move with their line as the text
+    /// is edited.
+    pub fn set_breakpoint(&mut self, line: usize, enabled: bool) {
+        if enabled {
+            self.breakpoints.insert(line);
+        } else {
+            self.breakpoints.remove(&line);
+        }
+    }
+
+    /// Toggles the breakpoint of a line, and returns whether it is now set.
+    pub fn toggle_breakpoint(&mut self, line: usize) -> bool {
+        let enabled = !self.breakpoints.contains(&line);
+        self.set_breakpoint(line, enabled);
+        enabled
+    }
+
+    /// Returns the markers, by line.
+    pub fn markers(&self) -> &BTreeMap<usize, CodeMarker> {
+        &self.markers
+    }
+
+    /// Sets the marker of a line, replacing any previous one. Markers move with their line as
+    /// the text is edited.
+    pub fn set_marker(&mut self, line: usize, marker: CodeMarker) {
+        self.markers.insert(line, marker);
+    }
+
+    /// Removes the marker of a line.
+    pub fn remove_marker(&mut self, line: usize) {
+        self.markers.remove(&line);
+    }
+
+    /// Removes all markers.
+    pub fn clear_markers(&mut self) {
+        self.markers.clear();
+    }
+
+    /// Opens the find bar, with the replace field if `replace` is `true`, and focuses it.
+    pub fn open_find(&mut self, replace: bool) {
+        self.find.open = true;
+        self.find.replace = replace && !self.read_only;
+        self.find.focus = true;
+        let selected = self.selected_text();
+        if !selected.is_empty() && !selected.contains('\n') {
+            self.find.query = selected;
+        }
+    }
+
+    /// Closes the find bar.
+    pub fn close_find(&mut self) {
+        self.find.open = false;
+    }
+
+    /// Selects the next occurrence of `query` after the selection, wrapping around at the end.
+    /// The query is matched within single lines. Returns `false` if it wasn't found.
+    pub fn find_next(&mut self, query: &str, case_sensitive: bool) -> bool {
+        let (_, from) = self.ordered_selection();
+        self.find(query, case_sensitive, from, true)
+    }
+
+    /// Selects the previous occurrence of `query` before the selection, wrapping around at the
+    /// start. The query is matched within single lines. Returns `false` if it wasn't found.
+    pub fn find_previous(&mut self, query: &str, case_sensitive: bool) -> bool {
+        let (from, _) = self.ordered_selection();
+        self.find(query, case_sensitive, from, false)
+    }
+
+    /// Replaces the selection by `replacement` if it matches `query`, then selects the next
+    /// occurrence. Returns `true` if something was replaced.
+    pub fn replace_next(&mut self, query: &str, replacement: &str, case_sensitive: bool) -> bool {
+        let selected: Vec<char> = self.selected_text().chars().collect();
+        let query_chars: Vec<char> = query.chars().collect();
+        let replaced = !self.read_only
+            && !query.is_empty()
+            && text_matches(&selected, &query_chars, case_sensitive);
+        if replaced {
+            let (start, end) = self.ordered_selection();
+            self.edit(start, end, replacement, false);
+        }
+        self.find_next(query, case_sensitive);
+        replaced
+    }
+
+    /// Replaces every occurrence of `query` by `replacement` as a single undo step, and returns
+    /// the number of replacements.
+    pub fn replace_all(&mut self, query: &str, replacement: &str, case_sensitive: bool) -> usize {
+        let query: Vec<char> = query.chars().collect();
+        if self.read_only || query.is_empty() || query.contains(&'\n') {
+            return 0;
+        }
+        let mut count = 0;
+        let mut text = String::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                text.push('\n');
+            }
+            let chars: Vec<char> = line.chars().collect();
+            let mut column = 0;
+            for start in matches_in_line(&chars, &query, case_sensitive) {
+                if start < column {
+                    continue;
+                }
+                text.extend(&chars[column..start]);
+                text.push_str(replacement);
+                column = start + query.len();
+                count += 1;
+            }
+            text.extend(&chars[column..]);
+        }
+        if count > 0 {
+            let cursor = self.cursor;
+            self.edit(TextPosition::default(), self.end(), &text, false);
+            self.set_cursor(cursor);
+        }
+        count
+    }
+
+    /// Builds the editor, filling `size` like a child window: zero fills the available space,
+    /// negative values leave that much space.
+    pub fn build(&mut self, ui: &Ui, size: impl Into<MintVec2>) -> CodeEditorResponse {
+        let mut response = CodeEditorResponse::default();
+        let version = self.version;
+        let _id = ui.push_id(&self.id);
+        if self.find.open {
+            self.build_find_bar(ui);
+        }
+
+        let _spacing = ui.push_style_var(StyleVar::ItemSpacing([0.0, 0.0]));
+        let _background = ui.push_style_color(StyleColor::ChildBg, self.palette.background);
+        ui.child_window("##text")
+            .size(size)
+            .horizontal_scrollbar(true)
+            .build(|| self.build_text_area(ui, &mut response));
+
+        response.changed = self.version != version;
+        response
+    }
+
+    fn build_find_bar(&mut self, ui: &Ui) {
+        let _id = ui.push_id("##find");
+        let field_width = ui.current_font_size() * 16.0;
+        if self.find.focus {
+            ui.set_keyboard_focus_here();
+            self.find.focus = false;
+        }
+        ui.set_next_item_width(field_width);
+        if ui
+            .input_text("##query", &mut self.find.query)
+            .hint("Find")
+            .enter_returns_true(true)
+            .build()
+        {
+            let query = self.find.query.clone();
+            self.find_next(&query, self.find.case_sensitive);
+            self.find.focus = true;
+        }
+        if ui.is_item_active() && ui.is_key_pressed(Key::Escape) {
+            self.find.open = false;
+        }
+        let query = self.find.query.clone();
+        ui.same_line();
+        ui.checkbox("Aa", &mut self.find.case_sensitive);
+        if ui.is_item_hovered() {
+            ui.tooltip_text("Match case");
+        }
+        ui.same_line();
+        if ui.button("Previous") {
+            self.find_previous(&query, self.find.case_sensitive);
+        }
+        ui.same_line();
+        if ui.button("Next") {
+            self.find_next(&query, self.find.case_sensitive);
+        }
+        ui.same_line();
+        if ui.button("Close") {
+            self.find.open = false;
+        }
+
+        if self.find.replace {
+            ui.set_next_item_width(field_width);
+            ui.input_text("##replacement", &mut self.find.replacement)
+                .hint("Replace")
+                .build();
+            let replacement = self.find.replacement.clone();
+            ui.same_line();
+            if ui.button("Replace") {
+                self.replace_next(&query, &replacement, self.find.case_sensitive);
+            }
+            ui.same_line();
+            if ui.button("Replace all") {
+                self.replace_all(&query, &replacement, self.find.case_sensitive);
+            }
+        }
+    }
+
+    fn build_text_area(&mut self, ui: &Ui, response: &mut CodeEditorResponse) {
+        let line_height = ui.text_line_height();
+        let space_width = ui.calc_text_size(" ")[0];
+        let number_width = if self.show_line_numbers {
+            ui.calc_text_size(self.lines.len().to_string())[0] + space_width * 2.0
+        } else {
+            0.0
+        };
+        let lane_width = line_height;
+        let marker_width = (space_width * 0.5).max(2.0);
+        let gutter_width = lane_width + number_width + marker_width + space_width;
+
+        let origin = ui.cursor_screen_pos();
+        let window_pos = ui.window_pos();
+        let window_size = ui.window_size();
+        let scrollbar_size = unsafe { ui.style() }.scrollbar_size;
+        let inner_size = [
+            window_size[0]
+                - if ui.scroll_max_y() > 0.0 {
+                    scrollbar_size
+                } else {
+                    0.0
+                },
+            window_size[1]
+                - if ui.scroll_max_x() > 0.0 {
+                    scrollbar_size
+                } else {
+                    0.0
+                },
+        ];
+        let page_lines = ((inner_size[1] / line_height) as usize).max(1);
+        let text_x = origin[0] + gutter_width;
+
+        if ui.is_window_focused() {
+            self.handle_keyboard(ui, page_lines);
+            unsafe { sys::igSetNextFrameWantCaptureKeyboard(true) };
+        }
+
+        // Mouse
+        let mouse = ui.io().mouse_pos;
+        let hovered = ui.is_window_hovered()
+            && mouse[0] < window_pos[0] + inner_size[0]
+            && mouse[1] < window_pos[1] + inner_size[1];
+        let in_gutter = mouse[0] < window_pos[0] + gutter_width;
+        let mouse_line = ((mouse[1] - origin[1]) / line_height).max(0.0) as usize;
+        if hovered && in_gutter {
+            if let Some(marker) = self.markers.get(&mouse_line) {
+                ui.tooltip_text(&marker.message);
+            }
+        } else if hovered {
+            ui.set_mouse_cursor(Some(MouseCursor::TextInput));
+        }
+        if hovered && ui.is_mouse_clicked(MouseButton::Left) {
+            if in_gutter {
+                if mouse_line < self.lines.len() && mouse[0] < window_pos[0] + lane_width {
+                    self.toggle_breakpoint(mouse_line);
+                    response.breakpoint_toggled = Some(mouse_line);
+                }
+            } else {
+                let position = self.position_at(ui, mouse_line, mouse[0] - text_x);
+                if ui.is_mouse_double_clicked(MouseButton::Left) {
+                    let (start, end) = self.word_at(position);
+                    self.anchor = start;
+                    self.cursor = end;
+                } else {
+                    if !ui.io().key_shift {
+                        self.anchor = position;
+                    }
+                    self.cursor = position;
+                    self.dragging = true;
+                }
+                self.cursor_moved();
+                self.scroll_to_cursor = false;
+            }
+        }
+        if self.dragging {
+            if ui.is_mouse_down(MouseButton::Left) {
+                self.cursor = self.position_at(ui, mouse_line, mouse[0] - text_x);
+            } else {
+                self.dragging = false;
+            }
+        }
+
+        let cursor_line_width = ui.calc_text_size(&self.lines[self.cursor.line])[0];
+        self.max_line_width = self.max_line_width.max(cursor_line_width);
+        if self.scroll_to_cursor {
+            self.scroll_to_cursor = false;
+            let y = self.cursor.line as f32 * line_height;
+            if y < ui.scroll_y() {
+                ui.set_scroll_y(y);
+            } else if y + line_height > ui.scroll_y() + inner_size[1] {
+                ui.set_scroll_y(y + line_height - inner_size[1]);
+            }
+            let x = self.column_x(ui, self.cursor);
+            let text_width = inner_size[0] - gutter_width - space_width;
+            if x < ui.scroll_x() {
+                ui.set_scroll_x(x);
+            } else if x > ui.scroll_x() + text_width {
+                ui.set_scroll_x(x - text_width);
+            }
+        }
+        if self.reset_blink {
+            self.reset_blink = false;
+            self.blink_start = ui.time();
+        }
+        let show_cursor = ui.is_window_focused() && (ui.time() - self.blink_start) % 1.2 < 0.8;
+
+        let (selection_start, selection_end) = self.ordered_selection();
+        let mut clipper = ListClipper::new(self.lines.len() as i32)
+            .items_height(line_height)
+            .begin(ui);
+        clipper.include_item_by_index(self.cursor.line as i32);
+        for line in clipper.iter() {
+            let line = line as usize;
+            self.ensure_tokens(line);
+            let y = ui.cursor_screen_pos()[1];
+            let text = &self.lines[line];
+            let draw_list = ui.get_window_draw_list();
+
+            if line == self.cursor.line && selection_start == selection_end {
+                draw_list
+                    .add_rect(
+                        [window_pos[0], y],
+                        [window_pos[0] + inner_size[0], y + line_height],
+                        self.palette.current_line,
+                    )
+                    .filled(true)
+                    .build();
+            }
+            if selection_start.line <= line && line <= selection_end.line {
+                let x0 = if line == selection_start.line {
+                    self.column_x(ui, selection_start)
+                } else {
+                    0.0
+                };
+                let x1 = if line == selection_end.line {
+                    self.column_x(ui, selection_end)
+                } else {
+                    ui.calc_text_size(text)[0] + space_width
+                };
+                if x1 > x0 {
+                    draw_list
+                        .add_rect(
+                            [text_x + x0, y],
+                            [text_x + x1, y + line_height],
+                            self.palette.selection,
+                        )
+                        .filled(true)
+                        .build();
+                }
+            }
+
+            let mut x = text_x;
+            let mut last = 0;
+            let mut segment = |range: Range<usize>, kind: TokenKind| {
+                if let Some(part) = text.get(range).filter(|part| !part.is_empty()) {
+                    draw_list.add_text([x, y], self.palette.token_color(kind), part);
+                    x += ui.calc_text_size(part)[0];
+                }
+            };
+            for token in &self.tokens[line] {
+                if token.range.start < last || token.range.end > text.len() {
+                    continue;
+                }
+                segment(last..token.range.start, TokenKind::Default);
+                segment(token.range.clone(), token.kind);
+                last = token.range.end;
+            }
+            segment(last..text.len(), TokenKind::Default);
+            self.max_line_width = self.max_line_width.max(x - text_x);
+
+            if show_cursor && line == self.cursor.line {
+                let x = text_x + self.column_x(ui, self.cursor);
+                draw_list
+                    .add_rect([x, y], [x + 1.0, y + line_height], self.palette.cursor)
+                    .filled(true)
+                    .build();
+            }
+
+            // The gutter stays in place when scrolling horizontally, so it is drawn over the text
+            let gutter_x = window_pos[0];
+            draw_list
+                .add_rect(
+                    [gutter_x, y],
+                    [gutter_x + gutter_width, y + line_height],
+                    self.palette.gutter,
+                )
+                .filled(true)
+                .build();
+            if self.breakpoints.contains(&line) {
+                draw_list
+                    .add_circle(
+                        [gutter_x + lane_width * 0.5, y + line_height * 0.5],
+                        line_height * 0.3,
+                        self.palette.breakpoint,
+                    )
+                    .filled(true)
+                    .build();
+            }
+            if self.show_line_numbers {
+                let number = (line + 1).to_string();
+                let color = if line == self.cursor.line {
+                    self.palette.current_line_number
+                } else {
+                    self.palette.line_number
+                };
+                let number_x = gutter_x + lane_width + number_width
+                    - space_width
+                    - ui.calc_text_size(&number)[0];
+                draw_list.add_text([number_x, y], color, number);
+            }
+            if let Some(marker) = self.markers.get(&line) {
+                let marker_x = gutter_x + lane_width + number_width;
+                draw_list
+                    .add_rect(
+                        [marker_x, y],
+                        [marker_x + marker_width, y + line_height],
+                        marker.color,
+                    )
+                    .filled(true)
+                    .build();
+            }
+            drop(draw_list);
+
+            ui.dummy([
+                gutter_width + self.max_line_width + space_width,
+                line_height,
+            ]);
+        }
+    }
+
+    fn handle_keyboard(&mut self, ui: &Ui, page_lines: usize) {
+        let io = ui.io();
+        let shift = io.key_shift;
+        let (ctrl, word) = if io.config_mac_os_behaviors {
+            (io.key_super, io.key_alt)
+        } else {
+            (io.key_ctrl, io.key_ctrl)
+        };
+        let editable = !self.read_only;
+        let pressed = |key| ui.is_key_pressed(key);
+
+        let vertical = if pressed(Key::UpArrow) {
+            Some(-1)
+        } else if pressed(Key::DownArrow) {
+            Some(1)
+        } else if pressed(Key::PageUp) {
+            Some(-(page_lines as isize))
+        } else if pressed(Key::PageDown) {
+            Some(page_lines as isize)
+        } else {
+            None
+        };
+        if let Some(delta) = vertical {
+            let column = self.preferred_column.unwrap_or(self.cursor.column);
+            let line = self
+                .cursor
+                .line
+                .saturating_add_signed(delta)
+                .min(self.lines.len() - 1);
+            self.move_to(TextPosition::new(line, column), shift);
+            self.preferred_column = Some(column);
+        } else if pressed(Key::LeftArrow) {
+            let (start, _) = self.ordered_selection();
+            let target = match (shift, self.selection()) {
+                (false, Some(_)) => start,
+                _ if word => self.word_left(self.cursor),
+                _ => self.left(self.cursor),
+            };
+            self.move_to(target, shift);
+        } else if pressed(Key::RightArrow) {
+            let (_, end) = self.ordered_selection();
+            let target = match (shift, self.selection()) {
+                (false, Some(_)) => end,
+                _ if word => self.word_right(self.cursor),
+                _ => self.right(self.cursor),
+            };
+            self.move_to(target, shift);
+        } else if pressed(Key::Home) {
+            let target = if ctrl {
+                TextPosition::default()
+            } else {
+                // Go to the first non-blank character, or the start if already there
+                let indent = leading_whitespace(&self.lines[self.cursor.line])
+                    .chars()
+                    .count();
+                let column = if self.cursor.column == indent {
+                    0
+                } else {
+                    indent
+                };
+                TextPosition::new(self.cursor.line, column)
+            };
+            self.move_to(target, shift);
+        } else if pressed(Key::End) {
+            let target = if ctrl {
+                self.end()
+            } else {
+                TextPosition::new(self.cursor.line, self.line_len(self.cursor.line))
+            };
+            self.move_to(target, shift);
+        } else if ctrl && pressed(Key::A) {
+            self.select_all();
+        } else if ctrl && pressed(Key::C) {
+            self.copy(ui);
+        } else if ctrl && pressed(Key::F) {
+            self.open_find(false);
+        } else if ctrl && pressed(Key::H) {
+            self.open_find(true);
+        } else if pressed(Key::F3) {
+            let query = self.find.query.clone();
+            if shift {
+                self.find_previous(&query, self.find.case_sensitive);
+            } else {
+                self.find_next(&query, self.find.case_sensitive);
+            }
+        } else if pressed(Key::Escape) {
+            self.find.open = false;
+        } else if !editable {
+            // Everything below edits the text
+        } else if ctrl && (pressed(Key::Y) || (shift && pressed(Key::Z))) {
+            self.redo();
+        } else if ctrl && pressed(Key::Z) {
+            self.undo();
+        } else if ctrl && pressed(Key::X) {
+            self.copy(ui);
+            self.delete_selection();
+        } else if ctrl && pressed(Key::V) {
+            if let Some(text) = ui.clipboard_text() {
+                self.insert_text(&text);
+            }
+        } else if pressed(Key::Backspace) {
+            if self.selection().is_none() {
+                self.anchor = if word {
+                    self.word_left(self.cursor)
+                } else {
+                    self.left(self.cursor)
+                };
+            }
+            self.delete_selection();
+        } else if pressed(Key::Delete) {
+            if self.selection().is_none() {
+                self.anchor = if word {
+                    self.word_right(self.cursor)
+                } else {
+                    self.right(self.cursor)
+                };
+            }
+            self.delete_selection();
+        } else if pressed(Key::Enter) || pressed(Key::KeypadEnter) {
+            let indent = leading_whitespace(&self.lines[self.cursor.line]).to_owned();
+            self.insert_text(&format!("\n{}", indent));
+        } else if pressed(Key::Tab) {
+            let (start, end) = self.ordered_selection();
+            if shift || start.line != end.line {
+                self.indent_lines(!shift);
+            } else {
+                let spaces = self.tab_size - self.cursor.column % self.tab_size;
+                self.insert_text(&" ".repeat(spaces));
+            }
+        } else if !ctrl || io.key_alt {
+            let typed: String = io
+                .input_queue_characters()
+                .filter(|&c| c >= ' ' && c != '\u{7f}')
+                .collect();
+            if !typed.is_empty() {
+                let (start, end) = self.ordered_selection();
+                self.edit(start, end, &typed, true);
+            }
+        }
+    }
+
+    fn copy(&self, ui: &Ui) {
+        let text = self.selected_text();
+        if !text.is_empty() {
+            ui.set_clipboard_text(text);
+        }
+    }
+
+    /// Indents or unindents the lines of the selection by one tab stop.
+    fn indent_lines(&mut self, indent: bool) {
+        let (start, end) = self.ordered_selection();
+        let last = if end.line > start.line && end.column == 0 {
+            end.line - 1
+        } else {
+            end.line
+        };
+        let mut text = String::new();
+        for line in start.line..=last {
+            if line > start.line {
+                text.push('\n');
+            }
+            let content = &self.lines[line];
+            if indent {
+                text.push_str(&" ".repeat(self.tab_size));
+                text.push_str(content);
+            } else if let Some(rest) = content.strip_prefix('\t') {
+                text.push_str(rest);
+            } else {
+                let spaces = content.len() - content.trim_start_matches(' ').len();
+                text.push_str(&content[spaces.min(self.tab_size)..]);
+            }
+        }
+        let line_end = TextPosition::new(last, self.line_len(last));
+        self.edit(TextPosition::new(start.line, 0), line_end, &text, false);
+        self.anchor = TextPosition::new(start.line, 0);
+        self.cursor = TextPosition::new(last, self.line_len(last));
+    }
+
+    fn find(
+        &mut self,
+        query: &str,
+        case_sensitive: bool,
+        from: TextPosition,
+        forward: bool,
+    ) -> bool {
+        let query: Vec<char> = query.chars().collect();
+        if query.is_empty() {
+            return false;
+        }
+        let count = self.lines.len();
+        // Visit every line once, and the starting line a second time to wrap around
+        for step in 0..=count {
+            let line = if forward {
+                (from.line + step) % count
+            } else {
+                (from.line + count * 2 - step) % count
+            };
+            let chars: Vec<char> = self.lines[line].chars().collect();
+            let matches = matches_in_line(&chars, &query, case_sensitive);
+            let accept = |column: usize| match step {
+                0 if forward => column >= from.column,
+                0 => column + query.len() <= from.column,
+                _ if step == count && forward => column < from.column,
+                _ if step == count => column + query.len() > from.column,
+                _ => true,
+            };
+            let found = if forward {
+                matches.into_iter().find(|&column| accept(column))
+            } else {
+                matches.into_iter().rev().find(|&column| accept(column))
+            };
+            if let Some(column) = found {
+                self.set_selection(
+                    TextPosition::new(line, column)..TextPosition::new(line, column + query.len()),
+                );
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Replaces `start..end` by `text` and records it for undo. Typing can be merged with the
+    /// previous edit into a single undo step.
+    fn edit(&mut self, start: TextPosition, end: TextPosition, text: &str, merge: bool) {
+        let removed = self.text_range(start, end);
+        let before = (self.anchor, self.cursor);
+        let new_end = self.replace(start, end, text);
+        let after = (new_end, new_end);
+        self.anchor = new_end;
+        self.cursor = new_end;
+        self.redo.clear();
+
+        let merged = merge && self.merge_undo && removed.is_empty() && {
+            match self.undo.last_mut() {
+                Some(last)
+                    if last.removed.is_empty()
+                        && end_of(last.start, &last.inserted) == start
+                        // Start a new step at word boundaries
+                        && (!text.starts_with(char::is_whitespace)
+                            || last.inserted.ends_with(char::is_whitespace)) =>
+                {
+                    last.inserted.push_str(text);
+                    last.after = after;
+                    true
+                }
+                _ => false,
+            }
+        };
+        if !merged {
+            self.undo.push(Edit {
+                start,
+                removed,
+                inserted: text.to_owned(),
+                before,
+                after,
+            });
+        }
+        self.cursor_moved();
+        self.merge_undo = merge;
+    }
+
+    /// Replaces `start..end` by `text`, without recording it for undo, and returns the end of
+    /// the inserted text.
+    fn replace(&mut self, start: TextPosition, end: TextPosition, text: &str) -> TextPosition {
+        let start = self.clamp(start);
+        let end = self.clamp(end);
+        let head = &self.lines[start.line][..byte_index(&self.lines[start.line], start.column)];
+        let tail = &self.lines[end.line][byte_index(&self.lines[end.line], end.column)..];
+        let mut new_lines: Vec<String> = text.split('\n').map(String::from).collect();
+        let inserted_end = end_of(start, text);
+        new_lines[0].insert_str(0, head);
+        new_lines.last_mut().unwrap().push_str(tail);
+
+        let removed_lines = end.line - start.line;
+        let added_lines = new_lines.len() - 1;
+        self.lines.splice(start.line..=end.line, new_lines);
+
+        if removed_lines != added_lines {
+            // Lines merged into the first one lose their breakpoints and markers
+            let shift = |line: usize| {
+                if line <= start.line {
+                    Some(line)
+                } else if line <= end.line {
+                    None
+                } else {
+                    Some(line - removed_lines + added_lines)
+                }
+            };
+            self.breakpoints = self.breakpoints.iter().filter_map(|&l| shift(l)).collect();
+            self.markers = std::mem::take(&mut self.markers)
+                .into_iter()
+                .filter_map(|(l, marker)| Some((shift(l)?, marker)))
+                .collect();
+        }
+        self.version += 1;
+        self.invalidate_tokens(start.line);
+        inserted_end
+    }
+
+    fn invalidate_tokens(&mut self, line: usize) {
+        self.tokens.truncate(line);
+        self.end_states.truncate(line);
+    }
+
+    fn ensure_tokens(&mut self, line: usize) {
+        while self.tokens.len() <= line {
+            let index = self.tokens.len();
+            let state = index
+                .checked_sub(1)
+                .map_or(STATE_NORMAL, |previous| self.end_states[previous]);
+            let mut tokens = Vec::new();
+            let end_state = self
+                .tokenizer
+                .tokenize_line(&self.lines[index], state, &mut tokens);
+            self.tokens.push(tokens);
+            self.end_states.push(end_state);
+        }
+    }
+
+    fn move_to(&mut self, position: TextPosition, extend_selection: bool) {
+        self.cursor = self.clamp(position);
+        if !extend_selection {
+            self.anchor = self.cursor;
+        }
+        self.cursor_moved();
+    }
+
+    fn cursor_moved(&mut self) {
+        self.preferred_column = None;
+        self.merge_undo = false;
+        self.scroll_to_cursor = true;
+        self.reset_blink = true;
+    }
+
+    fn ordered_selection(&self) -> (TextPosition, TextPosition) {
+        (min(self.anchor, self.cursor), max(self.anchor, self.cursor))
+    }
+
+    fn line_len(&self, line: usize) -> usize {
+        self.lines[line].chars().count()
+    }
+
+    fn end(&self) -> TextPosition {
+        let line = self.lines.len() - 1;
+        TextPosition::new(line, self.line_len(line))
+    }
+
+    fn clamp(&self, position: TextPosition) -> TextPosition {
+        let line = position.line.min(self.lines.len() - 1);
+        TextPosition::new(line, position.column.min(self.line_len(line)))
+    }
+
+    fn text_range(&self, start: TextPosition, end: TextPosition) -> String {
+        let (start, end) = (self.clamp(start), self.clamp(end));
+        if start >= end {
+            return String::new();
+        }
+        let first = &self.lines[start.line];
+        let start_byte = byte_index(first, start.column);
+        if start.line == end.line {
+            return first[start_byte..byte_index(first, end.column)].to_owned();
+        }
+        let mut text = first[start_byte..].to_owned();
+        for line in &self.lines[start.line + 1..end.line] {
+            text.push('\n');
+            text.push_str(line);
+        }
+        let last = &self.lines[end.line];
+        text.push('\n');
+        text.push_str(&last[..byte_index(last, end.column)]);
+        text
+    }
+
+    fn left(&self, position: TextPosition) -> TextPosition {
+        if position.column > 0 {
+            TextPosition::new(position.line, position.column - 1)
+        } else if position.line > 0 {
+            TextPosition::new(position.line - 1, self.line_len(position.line - 1))
+        } else {
+            position
+        }
+    }
+
+    fn right(&self, position: TextPosition) -> TextPosition {
+        if position.column < self.line_len(position.line) {
+            TextPosition::new(position.line, position.column + 1)
+        } else if position.line + 1 < self.lines.len() {
+            TextPosition::new(position.line + 1, 0)
+        } else {
+            position
+        }
+    }
+
+    fn word_left(&self, position: TextPosition) -> TextPosition {
+        if position.column == 0 {
+            return self.left(position);
+        }
+        let chars: Vec<char> = self.lines[position.line].chars().collect();
+        let mut column = position.column;
+        while column > 0 && chars[column - 1].is_whitespace() {
+            column -= 1;
+        }
+        if let Some(class) = column.checked_sub(1).map(|c| char_class(chars[c])) {
+            while column > 0 && char_class(chars[column - 1]) == class {
+                column -= 1;
+            }
+        }
+        TextPosition::new(position.line, column)
+    }
+
+    fn word_right(&self, position: TextPosition) -> TextPosition {
+        let chars: Vec<char> = self.lines[position.line].chars().collect();
+        if position.column >= chars.len() {
+            return self.right(position);
+        }
+        let mut column = position.column;
+        let class = char_class(chars[column]);
+        while column < chars.len() && char_class(chars[column]) == class {
+            column += 1;
+        }
+        while column < chars.len() && chars[column].is_whitespace() {
+            column += 1;
+        }
+        TextPosition::new(position.line, column)
+    }
+
+    /// Returns the word, or run of whitespace or punctuation, around a position.
+    fn word_at(&self, position: TextPosition) -> (TextPosition, TextPosition) {
+        let chars: Vec<char> = self.lines[position.line].chars().collect();
+        let Some(class) = chars
+            .get(position.column)
+            .or_else(|| chars.get(position.column.checked_sub(1)?))
+            .map(|&c| char_class(c))
+        else {
+            return (position, position);
+        };
+        let mut start = position.column.min(chars.len());
+        while start > 0 && char_class(chars[start - 1]) == class {
+            start -= 1;
+        }
+        let mut end = start;
+        while end < chars.len() && char_class(chars[end]) == class {
+            end += 1;
+        }
+        (
+            TextPosition::new(position.line, start),
+            TextPosition::new(position.line, end),
+        )
+    }
+
+    /// Returns the horizontal offset of a position from the start of its line.
+    fn column_x(&self, ui: &Ui, position: TextPosition) -> f32 {
+        let line = &self.lines[position.line];
+        ui.calc_text_size(&line[..byte_index(line, position.column)])[0]
+    }
+
+    /// Returns the position closest to the horizontal offset `x` in a line.
+    fn position_at(&self, ui: &Ui, line: usize, x: f32) -> TextPosition {
+        let line = line.min(self.lines.len() - 1);
+        let mut offset = 0.0;
+        let mut buf = [0; 4];
+        for (column, c) in self.lines[line].chars().enumerate() {
+            let width = ui.calc_text_size(c.encode_utf8(&mut buf))[0];
+            if x < offset + width * 0.5 {
+                return TextPosition::new(line, column);
+            }
+            offset += width;
+        }
+        TextPosition::new(line, self.line_len(line))
+    }
+}
+
+fn byte_index(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column)
+        .map_or(line.len(), |(index, _)| index)
+}
+
+/// Returns the position after `text` when it is inserted at `start`.
+fn end_of(start: TextPosition, text: &str) -> TextPosition {
+    match text.rfind('\n') {
+        Some(last_newline) => TextPosition::new(
+            start.line + text.matches('\n').count(),
+            text[last_newline + 1..].chars().count(),
+        ),
+        None => TextPosition::new(start.line, start.column + text.chars().count()),
+    }
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+fn text_matches(text: &[char], query: &[char], case_sensitive: bool) -> bool {
+    text.len() == query.len()
+        && text.iter().zip(query).all(|(&a, &b)| {
+            if case_sensitive {
+                a == b
+            } else {
+                chars_eq_ignore_case(a, b)
+            }
+        })
+}
+
+/// Returns the start columns of every occurrence of `query` in `line`, including overlapping
+/// ones.
+fn matches_in_line(line: &[char], query: &[char], case_sensitive: bool) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    line.windows(query.len())
+        .enumerate()
+        .filter(|(_, window)| text_matches(window, query, case_sensitive))
+        .map(|(column, _)| column)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: usize, column: usize) -> TextPosition {
+        TextPosition::new(line, column)
+    }
+
+    #[test]
+    fn edit_and_undo() {
+        let mut editor = CodeEditor::new("editor");
+        editor.set_text("fn main() {\r\n}");
+        editor.set_cursor(pos(0, 11));
+        editor.insert_text("\n    let x = 1;");
+        assert_eq!(editor.text(), "fn main() {\n    let x = 1;\n}");
+        assert_eq!(editor.cursor(), pos(1, 14));
+
+        editor.set_selection(pos(0, 3)..pos(0, 7));
+        assert_eq!(editor.selected_text(), "main");
+        editor.insert_text("start");
+        assert_eq!(editor.lines()[0], "fn start() {");
+
+        assert!(editor.undo());
+        assert_eq!(editor.lines()[0], "fn main() {");
+        assert_eq!(editor.selection(), Some(pos(0, 3)..pos(0, 7)));
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "fn main() {\n}");
+        assert!(!editor.undo());
+        assert!(editor.redo());
+        assert!(editor.redo());
+        assert_eq!(editor.text(), "fn start() {\n    let x = 1;\n}");
+        assert!(!editor.redo());
+    }
+
+    #[test]
+    fn typing_merges_words() {
+        let mut editor = CodeEditor::new("editor");
+        for c in ["l", "e", "t", " ", "x"] {
+            let (start, end) = editor.ordered_selection();
+            editor.edit(start, end, c, true);
+        }
+        assert_eq!(editor.text(), "let x");
+        editor.undo();
+        assert_eq!(editor.text(), "let");
+        editor.undo();
+        assert_eq!(editor.text(), "");
+    }
+
+    #[test]
+    fn find_and_replace() {
+        let mut editor = CodeEditor::new("editor");
+        editor.set_text("Foo foo\nbar FOO");
+        assert!(editor.find_next("foo", true));
+        assert_eq!(editor.selection(), Some(pos(0, 4)..pos(0, 7)));
+        assert!(editor.find_next("foo", false));
+        assert_eq!(editor.selection(), Some(pos(1, 4)..pos(1, 7)));
+        assert!(editor.find_next("foo", false));
+        assert_eq!(editor.selection(), Some(pos(0, 0)..pos(0, 3)));
+        assert!(editor.find_previous("foo", false));
+        assert_eq!(editor.selection(), Some(pos(1, 4)..pos(1, 7)));
+        assert!(!editor.find_next("baz", false));
+
+        assert!(editor.replace_next("foo", "baz", false));
+        assert_eq!(editor.text(), "Foo foo\nbar baz");
+        assert_eq!(editor.replace_all("foo", "x", false), 2);
+        assert_eq!(editor.text(), "x x\nbar baz");
+        editor.undo();
+        assert_eq!(editor.text(), "Foo foo\nbar baz");
+    }
+
+    #[test]
+    fn breakpoints_follow_lines() {
+        let mut editor = CodeEditor::new("editor");
+        editor.set_text("a\nb\nc\nd");
+        editor.set_breakpoint(1, true);
+        editor.set_breakpoint(3, true);
+        editor.set_marker(2, CodeMarker::new([1.0, 0.0, 0.0, 1.0], "error"));
+        editor.set_cursor(pos(0, 1));
+        editor.insert_text("\nnew");
+        assert_eq!(
+            editor.breakpoints().iter().copied().collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert!(editor.markers().contains_key(&3));
+
+        editor.set_selection(pos(2, 1)..pos(3, 1));
+        editor.delete_selection();
+        assert_eq!(editor.text(), "a\nnew\nb\nd");
+        assert_eq!(
+            editor.breakpoints().iter().copied().collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!(editor.markers().is_empty());
+    }
+
+    #[test]
+    fn language_tokenizer() {
+        let tokenizer = LanguageTokenizer::new()
+            .keywords(["let"])
+            .types(["u32"])
+            .line_comment("//")
+            .block_comment("/*", "*/")
+            .string_delimiters(['"']);
+        fn kinds<'a>(
+            tokenizer: &LanguageTokenizer,
+            line: &'a str,
+            state: u32,
+        ) -> (Vec<(&'a str, TokenKind)>, u32) {
+            let mut out = Vec::new();
+            let state = tokenizer.tokenize_line(line, state, &mut out);
+            let kinds: Vec<_> = out
+                .iter()
+                .map(|t| (&line[t.range.clone()], t.kind))
+                .collect();
+            (kinds, state)
+        }
+        let (tokens, state) = kinds(&tokenizer, r#"let x: u32 = "a\"b"; // hi"#, 0);
+        assert_eq!(state, 0);
+        assert_eq!(
+            tokens,
+            vec![
+                ("let", TokenKind::Keyword),
+                ("x", TokenKind::Identifier),
+                (":", TokenKind::Punctuation),
+                ("u32", TokenKind::Type),
+                ("=", TokenKind::Punctuation),
+                (r#""a\"b""#, TokenKind::String),
+                (";", TokenKind::Punctuation),
+                ("// hi", TokenKind::Comment),
+            ]
+        );
+        let (tokens, state) = kinds(&tokenizer, "1.5 /* open", 0);
+        assert_eq!(state, 1);
+        assert_eq!(tokens[0], ("1.5", TokenKind::Number));
+        let (tokens, state) = kinds(&tokenizer, "close */ x", state);
+        assert_eq!(state, 0);
+        assert_eq!(tokens[0], ("close */", TokenKind::Comment));
+    }
+
+    #[test]
+    fn build() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut editor = CodeEditor::new("editor")
+            .tokenizer(LanguageTokenizer::new().keywords(["fn"]).line_comment("//"));
+        let text: Vec<String> = (0..1000)
+            .map(|i| format!("fn f{}() {{}} // {}", i, i))
+            .collect();
+        editor.set_text(&text.join("\n"));
+        editor.set_breakpoint(3, true);
+        editor.open_find(true);
+        for _ in 0..2 {
+            let ui = ctx.frame();
+            ui.window("Editor").build(|| {
+                assert!(!editor.build(ui, [400.0, 300.0]).changed);
+            });
+            ctx.render();
+        }
+        // Only the visible lines were tokenized
+        assert!(editor.tokens.len() < 100);
+    }
+}
@@ -0,0 +1,578 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::ops::Range;
+
+use super::searchable_combo::draw_highlighted;
+use crate::text_match::fuzzy_match;
+use crate::{sys, Condition, Key, ListClipper, StyleColor, Ui};
+
+/// A command which can be run from a [CommandPalette].
+pub struct Command {
+    /// Identifies the command, returned by [CommandPalette::build] when it's run.
+    pub id: String,
+    /// The name shown in the palette.
+    pub name: String,
+    /// An optional category shown before the name, e.g. `File` in `File: Open`.
+    pub category: Option<String>,
+    /// An optional shortcut shown after the name, e.g. `Ctrl+O`. The palette only displays
+    /// it, the application handles the shortcut itself.
+    pub shortcut: Option<String>,
+    enabled: Option<Box<dyn Fn() -> bool>>,
+}
+
+impl Command {
+    /// Creates a new command with the given id and name.
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Command {
+            id: id.into(),
+            name: name.into(),
+            category: None,
+            shortcut: None,
+            enabled: None,
+        }
+    }
+
+    /// Sets the category shown before the name.
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Sets the shortcut shown after the name.
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Sets a predicate deciding whether the command can currently be run. Disabled commands
+    /// are still listed, but greyed out.
+    ///
+    /// Default: always enabled
+    pub fn enabled(mut self, enabled: impl Fn() -> bool + 'static) -> Self {
+        self.enabled = Some(Box::new(enabled));
+        self
+    }
+
+    /// Returns `true` if the command can currently be run.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled())
+    }
+
+    /// The text the query is matched against, `category: name`.
+    fn label(&self) -> String {
+        match &self.category {
+            Some(category) => format!("{}: {}", category, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Command")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("category", &self.category)
+            .field("shortcut", &self.shortcut)
+            .field("enabled", &self.enabled.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PaletteMatch {
+    index: usize,
+    label: String,
+    score: i32,
+    /// Byte ranges of the label which matched the query.
+    ranges: Vec<Range<usize>>,
+}
+
+/// Added to the score of the most recently used command, and divided by its position for the
+/// ones used before it.
+const RECENT_BONUS: i32 = 12;
+
+/// A searchable list of the commands of an application in a modal popup, like the Ctrl+P
+/// palette of many editors.
+///
+/// Typing filters the commands with the same fuzzy matching as [SearchableCombo](crate::SearchableCombo),
+/// matching both the category and the name, and highlights the matched characters. The
+/// highlighted command can be moved with the arrow and page keys and run with Enter or a
+/// click. Recently used commands are listed first when the query is empty, and ranked higher
+/// otherwise.
+///
+/// The palette keeps its commands and the recently used ones between frames, so it should be
+/// stored alongside the rest of the UI state.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let mut palette = CommandPalette::new();
+/// palette.add(Command::new("file.open", "Open...").category("File").shortcut("Ctrl+O"));
+/// palette.add(Command::new("file.save", "Save").category("File").shortcut("Ctrl+S"));
+/// // Every frame:
+/// if ui.io().key_ctrl && ui.is_key_pressed(Key::P) {
+///     palette.open();
+/// }
+/// if let Some(id) = palette.build(ui) {
+///     println!("running {}", id);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CommandPalette {
+    id: String,
+    hint: String,
+    width: f32,
+    max_visible_items: usize,
+    max_recent: usize,
+    highlight_color: Option<[f32; 4]>,
+    commands: Vec<Command>,
+    /// Ids of the recently used commands, most recent first.
+    recent: Vec<String>,
+    query: String,
+    matches: Vec<PaletteMatch>,
+    /// Position of the highlighted command in `matches`.
+    highlighted: usize,
+    needs_refilter: bool,
+    open_requested: bool,
+    is_open: bool,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandPalette {
+    /// Creates a new, empty command palette.
+    pub fn new() -> Self {
+        CommandPalette {
+            id: String::from("##CommandPalette"),
+            hint: String::from("Type a command"),
+            width: 500.0,
+            max_visible_items: 12,
+            max_recent: 10,
+            highlight_color: None,
+            commands: Vec::new(),
+            recent: Vec::new(),
+            query: String::new(),
+            matches: Vec::new(),
+            highlighted: 0,
+            needs_refilter: true,
+            open_requested: false,
+            is_open: false,
+        }
+    }
+
+    /// Sets the hint shown in the empty search field.
+    ///
+    /// Default: `"Type a command"`
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+
+    /// Sets the width of the palette.
+    ///
+    /// Default: 500
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets how many commands are visible before the list scrolls.
+    ///
+    /// Default: 12
+    pub fn max_visible_items(mut self, count: usize) -> Self {
+        self.max_visible_items = count.max(1);
+        self
+    }
+
+    /// Sets how many recently used commands are remembered.
+    ///
+    /// Default: 10
+    pub fn max_recent(mut self, count: usize) -> Self {
+        self.max_recent = count;
+        self.recent.truncate(count);
+        self
+    }
+
+    /// Sets the color of the matched parts of the commands.
+    ///
+    /// Default: the `CheckMark` style color.
+    pub fn highlight_color(mut self, color: impl Into<[f32; 4]>) -> Self {
+        self.highlight_color = Some(color.into());
+        self
+    }
+
+    /// Adds a command, replacing the one with the same id if there is one.
+    pub fn add(&mut self, command: Command) {
+        match self.commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+        self.needs_refilter = true;
+    }
+
+    /// Removes the command with the given id, and returns it if it existed.
+    pub fn remove(&mut self, id: &str) -> Option<Command> {
+        let index = self.commands.iter().position(|c| c.id == id)?;
+        self.recent.retain(|recent| recent != id);
+        self.needs_refilter = true;
+        Some(self.commands.remove(index))
+    }
+
+    /// Returns the command with the given id.
+    pub fn command(&self, id: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.id == id)
+    }
+
+    /// Returns all commands, in the order they were added.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Returns the ids of the recently used commands, most recent first.
+    pub fn recent(&self) -> &[String] {
+        &self.recent
+    }
+
+    /// Marks a command as just used, e.g. when it was run with its shortcut rather than from
+    /// the palette. Commands run from the palette are marked automatically.
+    pub fn mark_used(&mut self, id: &str) {
+        self.recent.retain(|recent| recent != id);
+        if self.max_recent > 0 {
+            self.recent.insert(0, id.to_owned());
+            self.recent.truncate(self.max_recent);
+        }
+        self.needs_refilter = true;
+    }
+
+    /// Returns the current search query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Opens the palette with an empty query the next time it's built.
+    pub fn open(&mut self) {
+        self.open_requested = true;
+    }
+
+    /// Returns `true` if the palette is open.
+    pub fn is_open(&self) -> bool {
+        self.is_open || self.open_requested
+    }
+
+    /// Builds the palette, which must be done every frame, whether it's open or not.
+    ///
+    /// Returns the id of the command the user ran, which is then closed.
+    #[doc(alias = "BeginPopupModal")]
+    pub fn build(&mut self, ui: &Ui) -> Option<String> {
+        if self.open_requested {
+            self.open_requested = false;
+            self.is_open = true;
+            self.query.clear();
+            self.needs_refilter = true;
+            ui.open_popup(&self.id);
+        }
+        if !self.is_open {
+            return None;
+        }
+
+        let (work_pos, work_size) = unsafe {
+            let viewport = &*sys::igGetMainViewport();
+            (viewport.WorkPos, viewport.WorkSize)
+        };
+        let pos = sys::ImVec2::new(
+            work_pos.x + work_size.x * 0.5,
+            work_pos.y + work_size.y * 0.15,
+        );
+        unsafe {
+            sys::igSetNextWindowPos(pos, Condition::Always as i32, sys::ImVec2::new(0.5, 0.0));
+            // A zero height fits the window to its contents
+            sys::igSetNextWindowSize(sys::ImVec2::new(self.width, 0.0), Condition::Always as i32);
+        }
+        let id = self.id.clone();
+        let result = ui
+            .modal_popup_config(&id)
+            .title_bar(false)
+            .resizable(false)
+            .movable(false)
+            .save_settings(false)
+            .build(|| {
+                let result = self.build_contents(ui);
+                if result.is_some() {
+                    ui.close_current_popup();
+                }
+                result
+            });
+        match result {
+            Some(None) => None,
+            Some(Some(ran)) => {
+                self.is_open = false;
+                ran.map(|index| {
+                    let id = self.commands[index].id.clone();
+                    self.mark_used(&id);
+                    id
+                })
+            }
+            // The popup is also gone if it was closed from elsewhere
+            None => {
+                self.is_open = false;
+                None
+            }
+        }
+    }
+
+    /// Returns `Some` once the palette should close, with the index of the command to run.
+    fn build_contents(&mut self, ui: &Ui) -> Option<Option<usize>> {
+        let appearing = ui.is_window_appearing();
+        if appearing {
+            ui.set_keyboard_focus_here();
+        }
+        ui.set_next_item_width(-f32::MIN_POSITIVE);
+        let edited = ui
+            .input_text("##query", &mut self.query)
+            .hint(&self.hint)
+            .build();
+        if edited || self.needs_refilter {
+            self.refilter();
+            self.highlighted = 0;
+        }
+
+        let mut moved = appearing;
+        if !self.matches.is_empty() {
+            let last = self.matches.len() - 1;
+            let page = self.max_visible_items;
+            let target = if ui.is_key_pressed(Key::UpArrow) {
+                Some(self.highlighted.saturating_sub(1))
+            } else if ui.is_key_pressed(Key::DownArrow) {
+                Some(self.highlighted + 1)
+            } else if ui.is_key_pressed(Key::PageUp) {
+                Some(self.highlighted.saturating_sub(page))
+            } else if ui.is_key_pressed(Key::PageDown) {
+                Some(self.highlighted + page)
+            } else {
+                None
+            };
+            if let Some(target) = target {
+                self.highlighted = target.min(last);
+                moved = true;
+            }
+        }
+        if ui.is_key_pressed(Key::Escape) {
+            return Some(None);
+        }
+        if ui.is_key_pressed(Key::Enter) || ui.is_key_pressed(Key::KeypadEnter) {
+            match self.matches.get(self.highlighted) {
+                Some(m) if self.commands[m.index].is_enabled() => return Some(Some(m.index)),
+                // Enter deactivates the search field, so keep typing there
+                _ => ui.set_keyboard_focus_here_with_offset(crate::FocusedWidget::Previous),
+            }
+        }
+
+        if self.matches.is_empty() {
+            ui.text_disabled("No matching commands");
+            return None;
+        }
+
+        let visible = self.matches.len().min(self.max_visible_items);
+        let height = visible as f32 * ui.text_line_height_with_spacing();
+        let text_color = ui.style_color(StyleColor::Text);
+        let disabled_color = ui.style_color(StyleColor::TextDisabled);
+        let highlight_color = self
+            .highlight_color
+            .unwrap_or_else(|| ui.style_color(StyleColor::CheckMark));
+        let mouse_moved = ui.io().mouse_delta != [0.0, 0.0];
+
+        let mut ran = None;
+        ui.child_window("##commands").size([0.0, height]).build(|| {
+            let mut clipper = ListClipper::new(self.matches.len() as i32).begin(ui);
+            if moved {
+                clipper.include_item_by_index(self.highlighted as i32);
+            }
+            for row in clipper.iter() {
+                let row = row as usize;
+                let m = &self.matches[row];
+                let command = &self.commands[m.index];
+                let enabled = command.is_enabled();
+                let _id = ui.push_id_usize(m.index);
+                let pos = ui.cursor_screen_pos();
+                if ui
+                    .selectable_config("##command")
+                    .selected(row == self.highlighted)
+                    .disabled(!enabled)
+                    .close_popups(false)
+                    .build()
+                {
+                    ran = Some(m.index);
+                }
+                if mouse_moved && ui.is_item_hovered() {
+                    self.highlighted = row;
+                }
+                if moved && row == self.highlighted && !ui.is_item_visible() {
+                    ui.set_scroll_here_y_with_ratio(0.5);
+                }
+
+                let (text_color, highlight_color) = if enabled {
+                    (text_color, highlight_color)
+                } else {
+                    (disabled_color, disabled_color)
+                };
+                draw_highlighted(ui, pos, &m.label, &m.ranges, text_color, highlight_color);
+                if let Some(shortcut) = &command.shortcut {
+                    let x = ui.item_rect_max()[0] - ui.calc_text_size(shortcut)[0];
+                    ui.get_window_draw_list()
+                        .add_text([x, pos[1]], disabled_color, shortcut);
+                }
+            }
+        });
+        ran.map(Some)
+    }
+
+    fn refilter(&mut self) {
+        self.needs_refilter = false;
+        self.matches.clear();
+        let recent_rank = |id: &str| self.recent.iter().position(|recent| recent == id);
+        for (index, command) in self.commands.iter().enumerate() {
+            let label = command.label();
+            let Some((score, ranges)) = fuzzy_match(&label, &self.query) else {
+                continue;
+            };
+            let bonus = recent_rank(&command.id).map_or(0, |rank| RECENT_BONUS / (rank as i32 + 1));
+            self.matches.push(PaletteMatch {
+                index,
+                label,
+                score: score + bonus,
+                ranges,
+            });
+        }
+        if self.query.is_empty() {
+            // Recently used ones first, the rest in the order they were added
+            self.matches
+                .sort_by_key(|m| recent_rank(&self.commands[m.index].id).unwrap_or(usize::MAX));
+        } else {
+            // Stable, so equally good matches stay in the order they were added
+            self.matches.sort_by_key(|m| Reverse(m.score));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    fn palette() -> CommandPalette {
+        let mut palette = CommandPalette::new();
+        palette.add(Command::new("file.open", "Open").category("File"));
+        palette.add(Command::new("file.save", "Save").category("File"));
+        palette.add(Command::new("view.sidebar", "Toggle Sidebar").category("View"));
+        palette.add(Command::new("edit.select_word", "Select Word").category("Edit"));
+        palette
+    }
+
+    fn order(palette: &CommandPalette) -> Vec<&str> {
+        palette
+            .matches
+            .iter()
+            .map(|m| palette.commands[m.index].id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_refilter_ranks_recent_commands() {
+        let mut palette = palette();
+        palette.refilter();
+        assert_eq!(
+            order(&palette),
+            ["file.open", "file.save", "view.sidebar", "edit.select_word"]
+        );
+
+        palette.mark_used("file.save");
+        palette.mark_used("view.sidebar");
+        palette.refilter();
+        assert_eq!(
+            order(&palette),
+            ["view.sidebar", "file.save", "file.open", "edit.select_word"]
+        );
+
+        // "Select Word" is the best match, but the sidebar was used most recently
+        palette.query = String::from("se");
+        palette.refilter();
+        assert_eq!(
+            order(&palette),
+            ["view.sidebar", "edit.select_word", "file.save"]
+        );
+        // Which leaves saving as the most recent one
+        palette.remove("view.sidebar");
+        palette.refilter();
+        assert_eq!(order(&palette), ["file.save", "edit.select_word"]);
+        assert_eq!(palette.recent(), ["file.save"]);
+
+        // Categories are matched too
+        palette.query = String::from("file:o");
+        palette.refilter();
+        assert_eq!(order(&palette), ["file.open"]);
+        assert_eq!(palette.matches[0].ranges, vec![0..5, 6..7]);
+    }
+
+    #[test]
+    fn test_build_runs_highlighted_command() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut palette = palette();
+        let can_save = Rc::new(Cell::new(false));
+        let enabled = can_save.clone();
+        palette.add(
+            Command::new("file.save", "Save")
+                .category("File")
+                .enabled(move || enabled.get()),
+        );
+
+        // Presses and releases the key in separate frames, like a real key press
+        let mut frame = |palette: &mut CommandPalette, key: Option<Key>| {
+            let mut ran = None;
+            for down in [true, false] {
+                if let Some(key) = key {
+                    ctx.io_mut().add_key_event(key, down);
+                }
+                let ui = ctx.frame();
+                ran = ran.or(palette.build(ui));
+                ctx.render();
+            }
+            ran
+        };
+
+        assert_eq!(frame(&mut palette, None), None);
+        assert!(!palette.is_open());
+        palette.open();
+        assert_eq!(frame(&mut palette, None), None);
+        assert!(palette.is_open());
+
+        // The disabled command can be highlighted but not run
+        assert_eq!(frame(&mut palette, Some(Key::DownArrow)), None);
+        assert_eq!(palette.highlighted, 1);
+        assert_eq!(frame(&mut palette, Some(Key::Enter)), None);
+        assert!(palette.is_open());
+
+        can_save.set(true);
+        assert_eq!(
+            frame(&mut palette, Some(Key::Enter)).as_deref(),
+            Some("file.save")
+        );
+        assert!(!palette.is_open());
+        assert_eq!(palette.recent(), ["file.save"]);
+
+        // Reopening lists the command just run first, and Escape closes without running
+        palette.open();
+        assert_eq!(frame(&mut palette, None), None);
+        assert_eq!(order(&palette)[0], "file.save");
+        assert_eq!(frame(&mut palette, Some(Key::Escape)), None);
+        assert!(!palette.is_open());
+    }
+}
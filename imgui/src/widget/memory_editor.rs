@@ -0,0 +1,899 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::math::MintVec2;
+use crate::{Key, ListClipper, MouseButton, MouseCursor, StyleColor, StyleVar, Ui};
+
+/// The memory shown by a [MemoryEditor].
+///
+/// It is implemented for byte slices, and can be implemented for address spaces which are too
+/// large to hold in memory, e.g. a file read in pages or the memory of another process. Only
+/// the visible rows are read every frame.
+pub trait MemorySource {
+    /// Returns the size of the address space.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the address space is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads the bytes starting at `address` into `buf`, and returns how many were read. Bytes
+    /// after these are shown as unreadable.
+    fn read(&mut self, address: usize, buf: &mut [u8]) -> usize;
+
+    /// Writes `data` at `address`, and returns `true` if it was written.
+    fn write(&mut self, _address: usize, _data: &[u8]) -> bool {
+        false
+    }
+
+    /// Returns `true` if the memory can be edited with [write](Self::write).
+    fn is_writable(&self) -> bool {
+        false
+    }
+}
+
+impl MemorySource for [u8] {
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn read(&mut self, address: usize, buf: &mut [u8]) -> usize {
+        ReadOnly(self).read(address, buf)
+    }
+
+    fn write(&mut self, address: usize, data: &[u8]) -> bool {
+        let end = address.saturating_add(data.len());
+        match self.get_mut(address..end) {
+            Some(target) => {
+                target.copy_from_slice(data);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn is_writable(&self) -> bool {
+        true
+    }
+}
+
+struct ReadOnly<'a>(&'a [u8]);
+
+impl MemorySource for ReadOnly<'_> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn read(&mut self, address: usize, buf: &mut [u8]) -> usize {
+        let available = self.0.get(address..).unwrap_or_default();
+        let count = buf.len().min(available.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        count
+    }
+}
+
+/// A colored range of a [MemoryEditor], e.g. a field of a file header. The label is shown as a
+/// tooltip when the range is hovered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryHighlight {
+    pub range: Range<usize>,
+    pub color: [f32; 4],
+    pub label: String,
+}
+
+impl MemoryHighlight {
+    /// Creates a new highlight.
+    pub fn new(range: Range<usize>, color: impl Into<[f32; 4]>, label: impl Into<String>) -> Self {
+        MemoryHighlight {
+            range,
+            color: color.into(),
+            label: label.into(),
+        }
+    }
+}
+
+/// What happened in a [MemoryEditor] this frame, as returned by [MemoryEditor::build].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryEditorResponse {
+    /// Bytes were written by the user.
+    pub changed: bool,
+    /// The address of the byte under the mouse.
+    pub hovered: Option<usize>,
+}
+
+/// A hex editor, which shows memory as rows of hexadecimal bytes and their ASCII characters,
+/// with selection, inline editing, a go-to-address field, a preview of the bytes at the cursor
+/// as numbers, and highlighted ranges.
+///
+/// Only the visible rows are read and drawn, so huge address spaces can be browsed through a
+/// [MemorySource], up to `i32::MAX` rows, the most a [ListClipper] can step through. The editor
+/// keeps its selection and scrolling between frames, so it should be stored alongside the rest of
+/// the UI state rather than recreated every frame.
+///
+/// Bytes are edited by typing hexadecimal digits over the cursor. The arrow keys, Page Up/Down
+/// and Home/End move the cursor (with Shift to select), Ctrl+C copies the selection as hex, up to
+/// its first MiB, and Ctrl+V writes hex from the clipboard at the cursor.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut ctx = imgui::Context::create();
+/// # let ui = ctx.frame();
+/// let mut packet = vec![0u8; 1500];
+/// let mut editor = imgui::MemoryEditor::new("packet");
+/// editor.add_highlight(imgui::MemoryHighlight::new(0..14, [0.2, 0.4, 0.8, 0.4], "Ethernet"));
+/// // Every frame:
+/// if editor.build(ui, [0.0, 0.0], &mut packet).changed {
+///     println!("edited");
+/// }
+/// ```
+#[derive(Clone)]
+pub struct MemoryEditor {
+    id: String,
+    columns: usize,
+    base_address: usize,
+    read_only: bool,
+    show_ascii: bool,
+    show_preview: bool,
+    highlights: Vec<MemoryHighlight>,
+
+    anchor: usize,
+    cursor: usize,
+    /// The digit typed over the cursor, waiting for the second one.
+    pending_digit: Option<u8>,
+    big_endian: bool,
+    goto_text: String,
+    scroll_to_cursor: bool,
+    dragging: bool,
+}
+
+impl fmt::Debug for MemoryEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryEditor")
+            .field("id", &self.id)
+            .field("columns", &self.columns)
+            .field("base_address", &self.base_address)
+            .field("anchor", &self.anchor)
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MemoryEditor {
+    /// Creates a new memory editor with the given id.
+    pub fn new(id: impl Into<String>) -> Self {
+        MemoryEditor {
+            id: id.into(),
+            columns: 16,
+            base_address: 0,
+            read_only: false,
+            show_ascii: true,
+            show_preview: true,
+            highlights: Vec::new(),
+            anchor: 0,
+            cursor: 0,
+            pending_digit: None,
+            big_endian: false,
+            goto_text: String::new(),
+            scroll_to_cursor: false,
+            dragging: false,
+        }
+    }
+
+    /// Sets the number of bytes per row.
+    ///
+    /// Defaults to 16.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns.max(1);
+        self
+    }
+
+    /// Sets the address shown for the first byte, e.g. where the memory is mapped. Addresses
+    /// passed to and returned by the editor are always offsets from the first byte.
+    pub fn base_address(mut self, base_address: usize) -> Self {
+        self.base_address = base_address;
+        self
+    }
+
+    /// Prevents editing, even if the memory is writable.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Shows the ASCII column. Enabled by default.
+    pub fn show_ascii(mut self, show_ascii: bool) -> Self {
+        self.show_ascii = show_ascii;
+        self
+    }
+
+    /// Shows the bytes at the cursor as integers and floats below the rows. Enabled by default.
+    pub fn show_preview(mut self, show_preview: bool) -> Self {
+        self.show_preview = show_preview;
+        self
+    }
+
+    /// Returns the address of the cursor.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the selected addresses, which always include the cursor.
+    pub fn selection(&self) -> Range<usize> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor) + 1
+    }
+
+    /// Selects `range`, with the cursor on its first byte, and scrolls to it.
+    pub fn set_selection(&mut self, range: Range<usize>) {
+        self.cursor = range.start;
+        self.anchor = range.end.max(range.start.saturating_add(1)) - 1;
+        self.pending_digit = None;
+        self.scroll_to_cursor = true;
+    }
+
+    /// Moves the cursor to `address`, and scrolls to it.
+    pub fn goto(&mut self, address: usize) {
+        self.set_selection(address..address.saturating_add(1));
+    }
+
+    /// Returns the highlighted ranges.
+    pub fn highlights(&self) -> &[MemoryHighlight] {
+        &self.highlights
+    }
+
+    /// Highlights a range. Earlier highlights are drawn over later ones.
+    pub fn add_highlight(&mut self, highlight: MemoryHighlight) {
+        self.highlights.push(highlight);
+    }
+
+    /// Removes all highlights.
+    pub fn clear_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    /// Builds the editor for a mutable byte slice, filling `size` like a child window: zero
+    /// fills the available space, negative values leave that much space.
+    pub fn build(
+        &mut self,
+        ui: &Ui,
+        size: impl Into<MintVec2>,
+        data: &mut [u8],
+    ) -> MemoryEditorResponse {
+        self.build_with_source(ui, size, data)
+    }
+
+    /// Builds the editor for a byte slice, which can't be edited.
+    pub fn build_read_only(
+        &mut self,
+        ui: &Ui,
+        size: impl Into<MintVec2>,
+        data: &[u8],
+    ) -> MemoryEditorResponse {
+        self.build_with_source(ui, size, &mut ReadOnly(data))
+    }
+
+    /// Builds the editor for any [MemorySource].
+    pub fn build_with_source<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &Ui,
+        size: impl Into<MintVec2>,
+        source: &mut S,
+    ) -> MemoryEditorResponse {
+        let mut response = MemoryEditorResponse::default();
+        let _id = ui.push_id(&self.id);
+        let len = self.shown_len(source);
+        self.cursor = self.cursor.min(len.saturating_sub(1));
+        self.anchor = self.anchor.min(len.saturating_sub(1));
+        self.build_goto_bar(ui, len);
+
+        let mut size: [f32; 2] = size.into().into();
+        if self.show_preview && size[1] <= 0.0 {
+            // Leave room for the preview below the rows
+            size[1] -= ui.text_line_height_with_spacing() * 5.0 + ui.frame_height_with_spacing();
+        }
+        {
+            let _spacing = ui.push_style_var(StyleVar::ItemSpacing([0.0, 0.0]));
+            ui.child_window("##rows")
+                .size(size)
+                .horizontal_scrollbar(true)
+                .build(|| self.build_rows(ui, source, &mut response));
+        }
+
+        if self.show_preview {
+            self.build_preview(ui, source);
+        }
+        response
+    }
+
+    fn build_goto_bar(&mut self, ui: &Ui, len: usize) {
+        ui.set_next_item_width(ui.calc_text_size("0000000000000000")[0]);
+        if ui
+            .input_text("##goto", &mut self.goto_text)
+            .hint("Go to address")
+            .chars_hexadecimal(true)
+            .enter_returns_true(true)
+            .build()
+        {
+            let address = parse_address(&self.goto_text)
+                .and_then(|address| address.checked_sub(self.base_address))
+                .filter(|&address| address < len);
+            if let Some(address) = address {
+                self.goto(address);
+                self.goto_text.clear();
+            }
+        }
+        ui.same_line();
+        let selection = self.selection();
+        if len > 0 {
+            ui.text_disabled(format!(
+                "{:X}..{:X} ({} bytes)",
+                self.base_address.saturating_add(selection.start),
+                self.base_address.saturating_add(selection.end),
+                selection.len()
+            ));
+        }
+    }
+
+    fn build_rows<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &Ui,
+        source: &mut S,
+        response: &mut MemoryEditorResponse,
+    ) {
+        let len = self.shown_len(source);
+        let rows = len.div_ceil(self.columns);
+        let line_height = ui.text_line_height();
+        let layout = RowLayout::new(
+            ui.calc_text_size("F")[0],
+            self.columns,
+            self.base_address.saturating_add(len),
+            self.show_ascii,
+        );
+        let origin = ui.cursor_screen_pos();
+        let page_rows = ((ui.window_size()[1] / line_height) as usize).max(1);
+        let writable = source.is_writable() && !self.read_only;
+
+        if ui.is_window_focused() && len > 0 {
+            self.handle_keyboard(ui, source, page_rows, writable, response);
+            unsafe { sys::igSetNextFrameWantCaptureKeyboard(true) };
+        }
+
+        // Mouse
+        let mouse = ui.io().mouse_pos;
+        let row = ((mouse[1] - origin[1]) / line_height).max(0.0) as usize;
+        let cell = layout.cell_at(mouse[0] - origin[0]);
+        let hovered = (ui.is_window_hovered() && mouse[1] >= origin[1])
+            .then(|| cell.map(|(column, _)| row * self.columns + column))
+            .flatten()
+            .filter(|&address| address < len);
+        response.hovered = hovered;
+        if let Some(address) = hovered {
+            ui.set_mouse_cursor(Some(MouseCursor::TextInput));
+            if let Some(highlight) = self.highlights.iter().find(|h| h.range.contains(&address)) {
+                if !highlight.label.is_empty() {
+                    ui.tooltip_text(&highlight.label);
+                }
+            }
+            if ui.is_mouse_clicked(MouseButton::Left) {
+                if !ui.io().key_shift {
+                    self.anchor = address;
+                }
+                self.cursor = address;
+                self.pending_digit = None;
+                self.dragging = true;
+            }
+        }
+        if self.dragging {
+            if !ui.is_mouse_down(MouseButton::Left) {
+                self.dragging = false;
+            } else if let Some((column, _)) = layout.cell_at(mouse[0] - origin[0]) {
+                self.cursor = (row * self.columns + column).min(len.saturating_sub(1));
+            }
+        }
+
+        if self.scroll_to_cursor {
+            self.scroll_to_cursor = false;
+            let y = (self.cursor / self.columns) as f32 * line_height;
+            let height = ui.window_size()[1] - line_height;
+            if y < ui.scroll_y() {
+                ui.set_scroll_y(y);
+            } else if y > ui.scroll_y() + height {
+                ui.set_scroll_y(y - height);
+            }
+        }
+
+        let text_color = ui.style_color(StyleColor::Text);
+        let dim_color = ui.style_color(StyleColor::TextDisabled);
+        let selection_color = ui.style_color(StyleColor::TextSelectedBg);
+        let selection = self.selection();
+        let mut bytes = vec![0; self.columns];
+        // `shown_len` keeps the row count in range
+        let mut clipper = ListClipper::new(rows as i32)
+            .items_height(line_height)
+            .begin(ui);
+        clipper.include_item_by_index((self.cursor / self.columns) as i32);
+        for row in clipper.iter() {
+            let row = row as usize;
+            let start = row * self.columns;
+            let count = self.columns.min(len - start);
+            let read = source.read(start, &mut bytes[..count]);
+            let y = ui.cursor_screen_pos()[1];
+            let x = origin[0];
+            let draw_list = ui.get_window_draw_list();
+
+            draw_list.add_text(
+                [x, y],
+                dim_color,
+                format!(
+                    "{:0width$X}",
+                    self.base_address.saturating_add(start),
+                    width = layout.address_digits
+                ),
+            );
+            for (column, &byte) in bytes[..count].iter().enumerate() {
+                let address = start + column;
+                let hex_x = x + layout.hex_x(column);
+                let ascii_x = x + layout.ascii_x(column);
+                let mut background = self
+                    .highlights
+                    .iter()
+                    .find(|h| h.range.contains(&address))
+                    .map(|h| h.color);
+                if selection.contains(&address) {
+                    background = Some(selection_color);
+                }
+                if let Some(color) = background {
+                    // Fill the gap up to the next selected or highlighted byte of the row
+                    let joined = column + 1 < count
+                        && (selection.contains(&(address + 1)) && selection.contains(&address)
+                            || self
+                                .highlights
+                                .iter()
+                                .find(|h| h.range.contains(&(address + 1)))
+                                .is_some_and(|h| h.color == color));
+                    let hex_end = if joined {
+                        x + layout.hex_x(column + 1)
+                    } else {
+                        hex_x + layout.glyph_width * 2.0
+                    };
+                    draw_list
+                        .add_rect([hex_x, y], [hex_end, y + line_height], color)
+                        .filled(true)
+                        .build();
+                    if self.show_ascii {
+                        draw_list
+                            .add_rect(
+                                [ascii_x, y],
+                                [ascii_x + layout.glyph_width, y + line_height],
+                                color,
+                            )
+                            .filled(true)
+                            .build();
+                    }
+                }
+
+                let (hex, color) = if column >= read {
+                    ("??".to_string(), dim_color)
+                } else if address == self.cursor && self.pending_digit.is_some() {
+                    (
+                        format!("{:X}_", self.pending_digit.unwrap_or(0)),
+                        text_color,
+                    )
+                } else if byte == 0 {
+                    ("00".to_string(), dim_color)
+                } else {
+                    (format!("{:02X}", byte), text_color)
+                };
+                draw_list.add_text([hex_x, y], color, hex);
+                if self.show_ascii {
+                    let c = if column < read && (0x20..0x7f).contains(&byte) {
+                        byte as char
+                    } else {
+                        '.'
+                    };
+                    let color = if c == '.' { dim_color } else { text_color };
+                    draw_list.add_text([ascii_x, y], color, c.to_string());
+                }
+
+                if address == self.cursor {
+                    draw_list
+                        .add_rect(
+                            [hex_x - 1.0, y],
+                            [hex_x + layout.glyph_width * 2.0 + 1.0, y + line_height],
+                            text_color,
+                        )
+                        .build();
+                    if self.show_ascii {
+                        draw_list
+                            .add_rect(
+                                [ascii_x, y],
+                                [ascii_x + layout.glyph_width, y + line_height],
+                                text_color,
+                            )
+                            .build();
+                    }
+                }
+            }
+            drop(draw_list);
+
+            ui.dummy([layout.width(), line_height]);
+        }
+    }
+
+    /// Returns the length of the part of `source` which is shown, which is limited to the
+    /// `i32::MAX` rows a [ListClipper] can step through.
+    fn shown_len<S: MemorySource + ?Sized>(&self, source: &S) -> usize {
+        source
+            .len()
+            .min((i32::MAX as usize).saturating_mul(self.columns))
+    }
+
+    fn handle_keyboard<S: MemorySource + ?Sized>(
+        &mut self,
+        ui: &Ui,
+        source: &mut S,
+        page_rows: usize,
+        writable: bool,
+        response: &mut MemoryEditorResponse,
+    ) {
+        let io = ui.io();
+        let shift = io.key_shift;
+        let ctrl = if io.config_mac_os_behaviors {
+            io.key_super
+        } else {
+            io.key_ctrl
+        };
+        let last = self.shown_len(source) - 1;
+        let columns = self.columns;
+        let pressed = |key| ui.is_key_pressed(key);
+
+        let target = if pressed(Key::LeftArrow) {
+            Some(self.cursor.saturating_sub(1))
+        } else if pressed(Key::RightArrow) {
+            Some(self.cursor.saturating_add(1))
+        } else if pressed(Key::UpArrow) {
+            Some(self.cursor.saturating_sub(columns))
+        } else if pressed(Key::DownArrow) {
+            Some(self.cursor.saturating_add(columns))
+        } else if pressed(Key::PageUp) {
+            Some(self.cursor.saturating_sub(columns * page_rows))
+        } else if pressed(Key::PageDown) {
+            Some(self.cursor.saturating_add(columns * page_rows))
+        } else if pressed(Key::Home) {
+            Some(if ctrl {
+                0
+            } else {
+                self.cursor - self.cursor % columns
+            })
+        } else if pressed(Key::End) {
+            Some(if ctrl {
+                last
+            } else {
+                (self.cursor - self.cursor % columns).saturating_add(columns - 1)
+            })
+        } else {
+            None
+        };
+        if let Some(target) = target {
+            self.cursor = target.min(last);
+            if !shift {
+                self.anchor = self.cursor;
+            }
+            self.pending_digit = None;
+            self.scroll_to_cursor = true;
+            return;
+        }
+
+        if ctrl && pressed(Key::C) {
+            let selection = self.selection();
+            // A selection of a huge source could be gigabytes
+            let mut bytes = vec![0; selection.len().min(MAX_COPY_LEN)];
+            let read = source.read(selection.start, &mut bytes);
+            ui.set_clipboard_text(format_hex(&bytes[..read]));
+        } else if ctrl && pressed(Key::V) {
+            let bytes = ui.clipboard_text().and_then(|text| parse_hex(&text));
+            if let Some(bytes) = bytes.filter(|_| writable) {
+                let bytes = &bytes[..bytes.len().min(self.shown_len(source) - self.cursor)];
+                if !bytes.is_empty() && source.write(self.cursor, bytes) {
+                    response.changed = true;
+                    self.anchor = self.cursor;
+                    self.cursor += bytes.len() - 1;
+                }
+            }
+        } else if pressed(Key::Escape) {
+            self.pending_digit = None;
+        } else if writable && !ctrl {
+            for c in io.input_queue_characters() {
+                if let Some(digit) = c.to_digit(16) {
+                    response.changed |= self.type_digit(source, digit as u8);
+                }
+            }
+        }
+    }
+
+    /// Types a hexadecimal digit over the cursor, and writes the byte once both of its digits
+    /// were typed. Returns `true` if the byte was written.
+    fn type_digit<S: MemorySource + ?Sized>(&mut self, source: &mut S, digit: u8) -> bool {
+        let Some(high) = self.pending_digit.take() else {
+            self.pending_digit = Some(digit);
+            return false;
+        };
+        let written = source.write(self.cursor, &[high << 4 | digit]);
+        self.cursor = (self.cursor + 1).min(self.shown_len(source).saturating_sub(1));
+        self.anchor = self.cursor;
+        self.scroll_to_cursor = true;
+        written
+    }
+
+    fn build_preview<S: MemorySource + ?Sized>(&mut self, ui: &Ui, source: &mut S) {
+        let mut bytes = [0; 8];
+        let read = source.read(self.cursor, &mut bytes);
+        let values = preview_values(&bytes[..read], self.big_endian);
+        for pair in values.chunks(2) {
+            let text = |(name, value): &(&str, Option<String>)| {
+                format!("{:<4} {}", name, value.as_deref().unwrap_or("-"))
+            };
+            ui.text(text(&pair[0]));
+            ui.same_line_with_pos(ui.calc_text_size("0")[0] * 28.0);
+            ui.text(text(&pair[1]));
+        }
+        ui.radio_button("Little endian", &mut self.big_endian, false);
+        ui.same_line();
+        ui.radio_button("Big endian", &mut self.big_endian, true);
+    }
+}
+
+/// The horizontal layout of a row, relative to its start.
+#[derive(Copy, Clone, Debug)]
+struct RowLayout {
+    glyph_width: f32,
+    columns: usize,
+    address_digits: usize,
+    show_ascii: bool,
+}
+
+impl RowLayout {
+    fn new(glyph_width: f32, columns: usize, max_address: usize, show_ascii: bool) -> Self {
+        let digits = (usize::BITS - max_address.leading_zeros()).div_ceil(4) as usize;
+        RowLayout {
+            glyph_width,
+            columns,
+            address_digits: digits.max(4),
+            show_ascii,
+        }
+    }
+
+    /// Returns the position of the hex digits of a column. Columns are grouped by eight.
+    fn hex_x(&self, column: usize) -> f32 {
+        let cells = (self.address_digits + 2 + column * 3 + column / 8) as f32;
+        cells * self.glyph_width
+    }
+
+    fn ascii_x(&self, column: usize) -> f32 {
+        self.hex_x(self.columns) + self.glyph_width * (1 + column) as f32
+    }
+
+    fn width(&self) -> f32 {
+        if self.show_ascii {
+            self.ascii_x(self.columns) + self.glyph_width
+        } else {
+            self.hex_x(self.columns)
+        }
+    }
+
+    /// Returns the column at `x`, and whether it is in the ASCII column.
+    fn cell_at(&self, x: f32) -> Option<(usize, bool)> {
+        let ascii_start = self.ascii_x(0);
+        if self.show_ascii && x >= ascii_start {
+            let column = ((x - ascii_start) / self.glyph_width) as usize;
+            return (column < self.columns).then_some((column, true));
+        }
+        (0..self.columns)
+            .find(|&column| {
+                // Each column extends over the space after its digits
+                x >= self.hex_x(column) - self.glyph_width * 0.5
+                    && x < self.hex_x(column + 1) - self.glyph_width * 0.5
+            })
+            .map(|column| (column, false))
+    }
+}
+
+/// Parses a hexadecimal address, with or without a `0x` prefix.
+fn parse_address(text: &str) -> Option<usize> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    usize::from_str_radix(text, 16).ok()
+}
+
+/// The most bytes Ctrl+C copies to the clipboard.
+const MAX_COPY_LEN: usize = 1 << 20;
+
+fn format_hex(bytes: &[u8]) -> String {
+    let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+    hex.join(" ")
+}
+
+/// Parses pairs of hexadecimal digits, ignoring whitespace.
+fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+    let pairs = digits.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+    Some(pairs.map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+/// Returns the bytes at the start of `bytes` read as each integer and float type, or `None`
+/// for types larger than `bytes`.
+fn preview_values(bytes: &[u8], big_endian: bool) -> Vec<(&'static str, Option<String>)> {
+    macro_rules! value {
+        ($ty:ty) => {
+            bytes
+                .get(..std::mem::size_of::<$ty>())
+                .map(|bytes| {
+                    let bytes = bytes.try_into().unwrap();
+                    if big_endian {
+                        <$ty>::from_be_bytes(bytes)
+                    } else {
+                        <$ty>::from_le_bytes(bytes)
+                    }
+                })
+                .map(|value| value.to_string())
+        };
+    }
+    vec![
+        ("i8", value!(i8)),
+        ("u8", value!(u8)),
+        ("i16", value!(i16)),
+        ("u16", value!(u16)),
+        ("i32", value!(i32)),
+        ("u32", value!(u32)),
+        ("i64", value!(i64)),
+        ("u64", value!(u64)),
+        ("f32", value!(f32)),
+        ("f64", value!(f64)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Condition;
+
+    #[test]
+    fn test_hex_and_preview() {
+        assert_eq!(parse_address("0x1F"), Some(0x1f));
+        assert_eq!(parse_address(" ff "), Some(0xff));
+        assert_eq!(parse_address("zz"), None);
+        assert_eq!(
+            parse_hex("de ad\nBE EF"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(parse_hex("abc"), None);
+        assert_eq!(format_hex(&[0x01, 0xab]), "01 AB");
+
+        let values = preview_values(&[0x01, 0x02, 0x03], false);
+        assert_eq!(values[3], ("u16", Some("513".to_string())));
+        assert_eq!(values[4], ("i32", None));
+        let values = preview_values(&[0x01, 0x02], true);
+        assert_eq!(values[3], ("u16", Some("258".to_string())));
+
+        let layout = RowLayout::new(10.0, 16, 0x1000, true);
+        assert_eq!(layout.address_digits, 4);
+        assert_eq!(layout.cell_at(layout.hex_x(9) + 5.0), Some((9, false)));
+        assert_eq!(layout.cell_at(layout.ascii_x(3) + 5.0), Some((3, true)));
+    }
+
+    #[test]
+    fn test_click_and_type() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut editor = MemoryEditor::new("memory").show_preview(false);
+        let mut data = vec![0u8; 64];
+        let run =
+            |ctx: &mut crate::Context, editor: &mut MemoryEditor, data: &mut [u8], mouse, down| {
+                ctx.io_mut().add_mouse_pos_event(mouse);
+                ctx.io_mut().add_mouse_button_event(MouseButton::Left, down);
+                let ui = ctx.frame();
+                let mut origin = [0.0, 0.0];
+                let mut response = MemoryEditorResponse::default();
+                ui.window("Memory")
+                    .position([0.0, 0.0], Condition::Always)
+                    .size([800.0, 400.0], Condition::Always)
+                    .build(|| {
+                        // The rows start below the go-to bar
+                        origin = ui.cursor_screen_pos();
+                        origin[1] += ui.frame_height_with_spacing();
+                        response = editor.build(ui, [0.0, 0.0], data);
+                    });
+                ctx.render();
+                (origin, response)
+            };
+
+        let (origin, _) = run(&mut ctx, &mut editor, &mut data, [0.0, 0.0], false);
+        let layout = RowLayout::new(7.0, 16, 64, true);
+        // Click the third byte of the second row
+        let byte = [origin[0] + layout.hex_x(2) + 3.0, origin[1] + 13.0 * 1.5];
+        let (_, response) = run(&mut ctx, &mut editor, &mut data, byte, false);
+        assert_eq!(response.hovered, Some(18));
+        run(&mut ctx, &mut editor, &mut data, byte, true);
+        run(&mut ctx, &mut editor, &mut data, byte, false);
+        assert_eq!(editor.selection(), 18..19);
+
+        // Typing two digits writes the byte and moves to the next one
+        assert!(!editor.type_digit(&mut data[..], 0xa));
+        assert!(editor.type_digit(&mut data[..], 0x7));
+        assert_eq!(data[18], 0xa7);
+        assert_eq!(editor.cursor(), 19);
+    }
+
+    #[test]
+    fn test_huge_address_space() {
+        struct Zeros;
+        impl MemorySource for Zeros {
+            fn len(&self) -> usize {
+                usize::MAX
+            }
+            fn read(&mut self, _address: usize, buf: &mut [u8]) -> usize {
+                buf.fill(0);
+                buf.len()
+            }
+        }
+
+        struct Clipboard(Option<String>);
+        impl crate::ClipboardBackend for Clipboard {
+            fn get(&mut self) -> Option<String> {
+                self.0.clone()
+            }
+            fn set(&mut self, value: &str) {
+                self.0 = Some(value.to_owned());
+            }
+        }
+
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        ctx.set_clipboard_backend(Clipboard(None));
+        let mut editor = MemoryEditor::new("memory").base_address(usize::MAX - 0xff);
+        assert_eq!(
+            editor.shown_len(&Zeros),
+            (i32::MAX as usize).saturating_mul(16)
+        );
+        // Returns the clipboard text
+        let mut frame = |editor: &mut MemoryEditor, click: bool, copy: bool| {
+            ctx.io_mut().add_mouse_pos_event([100.0, 150.0]);
+            ctx.io_mut()
+                .add_mouse_button_event(MouseButton::Left, click);
+            ctx.io_mut().add_key_event(Key::ModCtrl, copy);
+            ctx.io_mut().add_key_event(Key::C, copy);
+            let ui = ctx.frame();
+            ui.window("Memory")
+                .position([0.0, 0.0], Condition::Always)
+                .size([800.0, 400.0], Condition::Always)
+                .build(|| {
+                    editor.build_with_source(ui, [0.0, 0.0], &mut Zeros);
+                });
+            let clipboard = ui.clipboard_text();
+            ctx.render();
+            clipboard
+        };
+        frame(&mut editor, false, false);
+        editor.goto(usize::MAX);
+        frame(&mut editor, false, false);
+        frame(&mut editor, false, false);
+        assert_eq!(editor.cursor(), editor.shown_len(&Zeros) - 1);
+
+        // Copying everything only copies the start
+        frame(&mut editor, true, false);
+        frame(&mut editor, false, false);
+        editor.set_selection(0..editor.shown_len(&Zeros));
+        frame(&mut editor, false, true);
+        let clipboard = frame(&mut editor, false, false).unwrap();
+        assert_eq!(clipboard.len(), MAX_COPY_LEN * 3 - 1);
+    }
+}
@@ -0,0 +1,501 @@
+use std::fmt;
+
+use crate::{KeyChord, PopupFlags, ShortcutFlags, Ui};
+
+/// An action of a [MenuModel], drawn as a menu item and triggered by a click or its shortcut.
+pub struct MenuAction {
+    /// Identifies the action in the [MenuTrigger]s reported when it's triggered.
+    pub id: String,
+    /// The label of the menu item.
+    pub label: String,
+    /// An optional shortcut, shown next to the label and handled by the model.
+    pub shortcut: Option<KeyChord>,
+    checked: Option<Box<dyn Fn() -> bool>>,
+    enabled: Option<Box<dyn Fn() -> bool>>,
+}
+
+impl MenuAction {
+    /// Creates a new action with the given id and label.
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        MenuAction {
+            id: id.into(),
+            label: label.into(),
+            shortcut: None,
+            checked: None,
+            enabled: None,
+        }
+    }
+
+    /// Sets the shortcut which triggers the action, e.g.
+    /// `KeyChord::new(KeyMods::CTRL, Key::S)`.
+    pub fn shortcut(mut self, shortcut: impl Into<KeyChord>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Sets a predicate deciding whether a checkmark is shown next to the label, for actions
+    /// which toggle something.
+    ///
+    /// Default: never checked
+    pub fn checked(mut self, checked: impl Fn() -> bool + 'static) -> Self {
+        self.checked = Some(Box::new(checked));
+        self
+    }
+
+    /// Sets a predicate deciding whether the action can currently be triggered. Disabled
+    /// actions are greyed out, and their shortcut is ignored.
+    ///
+    /// Default: always enabled
+    pub fn enabled(mut self, enabled: impl Fn() -> bool + 'static) -> Self {
+        self.enabled = Some(Box::new(enabled));
+        self
+    }
+
+    /// Returns `true` if a checkmark is shown next to the label.
+    pub fn is_checked(&self) -> bool {
+        self.checked.as_ref().is_some_and(|checked| checked())
+    }
+
+    /// Returns `true` if the action toggles something, and shows a checkmark when it's on.
+    pub fn is_checkable(&self) -> bool {
+        self.checked.is_some()
+    }
+
+    /// Returns `true` if the action can currently be triggered.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled())
+    }
+}
+
+impl fmt::Debug for MenuAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MenuAction")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .field("shortcut", &self.shortcut)
+            .field("checked", &self.checked.as_ref().map(|_| ".."))
+            .field("enabled", &self.enabled.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// A submenu of a [MenuModel], or a menu of the menu bar when it's at the top level.
+pub struct Submenu {
+    /// The label of the menu.
+    pub label: String,
+    /// The entries of the menu, in order.
+    pub entries: Vec<MenuEntry>,
+    enabled: Option<Box<dyn Fn() -> bool>>,
+}
+
+impl Submenu {
+    /// Creates an empty menu with the given label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Submenu {
+            label: label.into(),
+            entries: Vec::new(),
+            enabled: None,
+        }
+    }
+
+    /// Appends an action.
+    pub fn action(mut self, action: MenuAction) -> Self {
+        self.entries.push(MenuEntry::Action(action));
+        self
+    }
+
+    /// Appends a nested submenu.
+    pub fn submenu(mut self, submenu: Submenu) -> Self {
+        self.entries.push(MenuEntry::Submenu(submenu));
+        self
+    }
+
+    /// Appends a separator.
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// Sets a predicate deciding whether the menu can currently be opened. The shortcuts of
+    /// the actions of a disabled menu are ignored too.
+    ///
+    /// Default: always enabled
+    pub fn enabled(mut self, enabled: impl Fn() -> bool + 'static) -> Self {
+        self.enabled = Some(Box::new(enabled));
+        self
+    }
+
+    /// Returns `true` if the menu can currently be opened.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled())
+    }
+}
+
+impl fmt::Debug for Submenu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Submenu")
+            .field("label", &self.label)
+            .field("entries", &self.entries)
+            .field("enabled", &self.enabled.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// An entry of a [MenuModel] or a [Submenu].
+#[derive(Debug)]
+pub enum MenuEntry {
+    Action(MenuAction),
+    Submenu(Submenu),
+    Separator,
+}
+
+/// How a [MenuAction] was triggered.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MenuTriggerSource {
+    /// The menu item was clicked, or activated with keyboard navigation.
+    Menu,
+    /// The shortcut of the action was pressed.
+    Shortcut,
+}
+
+/// An action triggered this frame, as returned by the `build_*` functions of [MenuModel].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MenuTrigger {
+    /// The id of the action.
+    pub id: String,
+    /// Whether the action was triggered from the menu or by its shortcut.
+    pub source: MenuTriggerSource,
+}
+
+/// A description of the menus of an application, drawn as a menu bar or a context menu and
+/// handling the shortcuts of its actions, so menus, toolbars and hotkeys share one definition.
+///
+/// The top-level entries are usually [Submenu]s, which become the menus of the menu bar. The
+/// model can be drawn in several places, and reports the actions triggered in each of them.
+/// Toolbars can look actions up with [find](Self::find) to show the same enabled and checked
+/// state.
+///
+/// # Example
+///
+/// ```no_run
+/// # use imgui::*;
+/// # use std::cell::Cell;
+/// # use std::rc::Rc;
+/// # let mut ctx = Context::create();
+/// # let ui = ctx.frame();
+/// let dirty = Rc::new(Cell::new(false));
+/// let show_grid = Rc::new(Cell::new(true));
+/// let can_save = dirty.clone();
+/// let grid = show_grid.clone();
+/// let menus = MenuModel::new()
+///     .submenu(
+///         Submenu::new("File")
+///             .action(
+///                 MenuAction::new("file.open", "Open...")
+///                     .shortcut(KeyChord::new(KeyMods::CTRL, Key::O)),
+///             )
+///             .action(
+///                 MenuAction::new("file.save", "Save")
+///                     .shortcut(KeyChord::new(KeyMods::CTRL, Key::S))
+///                     .enabled(move || can_save.get()),
+///             )
+///             .separator()
+///             .action(MenuAction::new("file.quit", "Quit")),
+///     )
+///     .submenu(
+///         Submenu::new("View").action(
+///             MenuAction::new("view.grid", "Grid")
+///                 .shortcut(KeyChord::new(KeyMods::CTRL, Key::G))
+///                 .checked(move || grid.get()),
+///         ),
+///     );
+///
+/// // Every frame:
+/// for trigger in menus.build_main_menu_bar(ui) {
+///     match trigger.id.as_str() {
+///         "view.grid" => show_grid.set(!show_grid.get()),
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct MenuModel {
+    /// The top-level entries, in order.
+    pub entries: Vec<MenuEntry>,
+}
+
+impl MenuModel {
+    /// Creates an empty model.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a top-level action, which is drawn directly in a menu bar.
+    pub fn action(mut self, action: MenuAction) -> Self {
+        self.entries.push(MenuEntry::Action(action));
+        self
+    }
+
+    /// Appends a submenu.
+    pub fn submenu(mut self, submenu: Submenu) -> Self {
+        self.entries.push(MenuEntry::Submenu(submenu));
+        self
+    }
+
+    /// Appends a separator.
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// Returns the action with the given id, searching through all submenus.
+    pub fn find(&self, id: &str) -> Option<&MenuAction> {
+        fn find<'a>(entries: &'a [MenuEntry], id: &str) -> Option<&'a MenuAction> {
+            entries.iter().find_map(|entry| match entry {
+                MenuEntry::Action(action) if action.id == id => Some(action),
+                MenuEntry::Submenu(submenu) => find(&submenu.entries, id),
+                _ => None,
+            })
+        }
+        find(&self.entries, id)
+    }
+
+    /// Draws the model as the main menu bar, and handles its shortcuts anywhere in the
+    /// application unless the focused window or the active item uses them.
+    ///
+    /// Returns the actions triggered this frame.
+    #[doc(alias = "BeginMainMenuBar")]
+    pub fn build_main_menu_bar(&self, ui: &Ui) -> Vec<MenuTrigger> {
+        let mut triggers = self.handle_shortcuts(ui, ShortcutFlags::ROUTE_GLOBAL);
+        if let Some(_menu_bar) = ui.begin_main_menu_bar() {
+            build_entries(ui, &self.entries, true, &mut triggers);
+        }
+        triggers
+    }
+
+    /// Draws the model as the menu bar of the current window, which needs
+    /// [`WindowFlags::MENU_BAR`](crate::WindowFlags::MENU_BAR), and handles its shortcuts while
+    /// the window is focused.
+    ///
+    /// Returns the actions triggered this frame.
+    #[doc(alias = "BeginMenuBar")]
+    pub fn build_menu_bar(&self, ui: &Ui) -> Vec<MenuTrigger> {
+        let mut triggers = self.handle_shortcuts(ui, ShortcutFlags::ROUTE_FOCUSED);
+        if let Some(_menu_bar) = ui.begin_menu_bar() {
+            build_entries(ui, &self.entries, true, &mut triggers);
+        }
+        triggers
+    }
+
+    /// Draws the model as a context menu, opened by right clicking the last item. Without a
+    /// label, the popup uses the id of the last item.
+    ///
+    /// This doesn't handle shortcuts, since the menu is only drawn while it's open: use
+    /// [handle_shortcuts](Self::handle_shortcuts) for that.
+    ///
+    /// Returns the actions triggered this frame.
+    #[doc(alias = "BeginPopupContextItem")]
+    pub fn build_context_menu(&self, ui: &Ui, str_id: Option<&str>) -> Vec<MenuTrigger> {
+        let mut triggers = Vec::new();
+        if let Some(_popup) =
+            ui.begin_popup_context_item_with_flags(str_id, PopupFlags::MOUSE_BUTTON_RIGHT)
+        {
+            build_entries(ui, &self.entries, true, &mut triggers);
+        }
+        triggers
+    }
+
+    /// Draws the model in a popup opened with [`Ui::open_popup`], e.g. from a toolbar button.
+    ///
+    /// Like [build_context_menu](Self::build_context_menu), this doesn't handle shortcuts.
+    ///
+    /// Returns the actions triggered this frame.
+    #[doc(alias = "BeginPopup")]
+    pub fn build_popup(&self, ui: &Ui, str_id: impl AsRef<str>) -> Vec<MenuTrigger> {
+        let mut triggers = Vec::new();
+        if let Some(_popup) = ui.begin_popup(str_id) {
+            build_entries(ui, &self.entries, true, &mut triggers);
+        }
+        triggers
+    }
+
+    /// Checks the shortcuts of all enabled actions with [`Ui::shortcut`], using the given
+    /// routing. This should be called every frame, and only once per frame for a model: the
+    /// shortcut of an action which just got enabled works from the next frame on.
+    ///
+    /// Returns the actions whose shortcut was pressed.
+    #[doc(alias = "Shortcut")]
+    pub fn handle_shortcuts(&self, ui: &Ui, flags: ShortcutFlags) -> Vec<MenuTrigger> {
+        fn handle(
+            ui: &Ui,
+            entries: &[MenuEntry],
+            flags: ShortcutFlags,
+            out: &mut Vec<MenuTrigger>,
+        ) {
+            for entry in entries {
+                match entry {
+                    MenuEntry::Action(action) => {
+                        let Some(chord) = action.shortcut else {
+                            continue;
+                        };
+                        if action.is_enabled() && ui.shortcut(chord, flags) {
+                            out.push(MenuTrigger {
+                                id: action.id.clone(),
+                                source: MenuTriggerSource::Shortcut,
+                            });
+                        }
+                    }
+                    MenuEntry::Submenu(submenu) if submenu.is_enabled() => {
+                        handle(ui, &submenu.entries, flags, out);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let mut triggers = Vec::new();
+        handle(ui, &self.entries, flags, &mut triggers);
+        triggers
+    }
+}
+
+/// Draws menu items, submenus and separators, collecting the items which were clicked.
+fn build_entries(ui: &Ui, entries: &[MenuEntry], enabled: bool, out: &mut Vec<MenuTrigger>) {
+    for entry in entries {
+        match entry {
+            MenuEntry::Action(action) => {
+                let shortcut = action
+                    .shortcut
+                    .map(|chord| chord.to_string())
+                    .unwrap_or_default();
+                // The id keeps items with the same label apart
+                let clicked = ui
+                    .menu_item_config(format!("{}##{}", action.label, action.id))
+                    .shortcut(shortcut)
+                    .selected(action.is_checked())
+                    .enabled(enabled && action.is_enabled())
+                    .build();
+                if clicked {
+                    out.push(MenuTrigger {
+                        id: action.id.clone(),
+                        source: MenuTriggerSource::Menu,
+                    });
+                }
+            }
+            MenuEntry::Submenu(submenu) => {
+                let enabled = enabled && submenu.is_enabled();
+                if let Some(_menu) = ui.begin_menu_with_enabled(&submenu.label, enabled) {
+                    build_entries(ui, &submenu.entries, enabled, out);
+                }
+            }
+            MenuEntry::Separator => ui.separator(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Key, KeyMods};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    fn model(can_save: Rc<Cell<bool>>) -> MenuModel {
+        MenuModel::new()
+            .submenu(
+                Submenu::new("File")
+                    .action(
+                        MenuAction::new("file.save", "Save")
+                            .shortcut(KeyChord::new(KeyMods::CTRL, Key::S))
+                            .enabled(move || can_save.get()),
+                    )
+                    .separator()
+                    .submenu(
+                        Submenu::new("Export")
+                            .action(MenuAction::new("file.export.png", "PNG").shortcut(Key::F5)),
+                    ),
+            )
+            .action(MenuAction::new("quit", "Quit").checked(|| true))
+    }
+
+    #[test]
+    fn test_find_and_state() {
+        // No context is current while the test mutex is held, so chords are named in Rust
+        let _guard = crate::test::TEST_MUTEX.lock();
+        let menus = model(Rc::new(Cell::new(false)));
+        let save = menus.find("file.save").unwrap();
+        assert!(!save.is_enabled());
+        assert!(!save.is_checkable());
+        assert_eq!(save.shortcut.unwrap().to_string(), "Ctrl+S");
+        assert_eq!(menus.find("file.export.png").unwrap().label, "PNG");
+        assert!(menus.find("quit").unwrap().is_checked());
+        assert!(menus.find("file").is_none());
+        assert_eq!(
+            KeyChord::new(KeyMods::CTRL | KeyMods::SHIFT, Key::Alpha1).to_string(),
+            "Ctrl+Shift+1"
+        );
+        // With a context, `GetKeyChordName` leaves out the modifier of a modifier key
+        let shift = KeyChord::new(KeyMods::CTRL | KeyMods::SHIFT, Key::LeftShift);
+        assert_eq!(shift.to_string(), "Ctrl+Shift+LeftShift");
+        let (_guard, _ctx) = crate::test::test_ctx();
+        assert_eq!(shift.to_string(), "Ctrl+LeftShift");
+        assert_eq!(save.shortcut.unwrap().to_string(), "Ctrl+S");
+    }
+
+    #[test]
+    fn test_shortcuts_and_clicks_are_reported() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let can_save = Rc::new(Cell::new(false));
+        let menus = model(can_save.clone());
+        let file_width = ctx.frame().calc_text_size("File")[0];
+        ctx.render();
+
+        let mut frame = |keys: &[(Key, bool)], mouse: Option<([f32; 2], bool)>| {
+            for &(key, down) in keys {
+                ctx.io_mut().add_key_event(key, down);
+            }
+            if let Some((pos, down)) = mouse {
+                ctx.io_mut().add_mouse_pos_event(pos);
+                ctx.io_mut()
+                    .add_mouse_button_event(crate::MouseButton::Left, down);
+            }
+            let ui = ctx.frame();
+            let triggers = menus.build_main_menu_bar(ui);
+            ctx.render();
+            triggers
+        };
+        let shortcut = |id: &str| MenuTrigger {
+            id: id.into(),
+            source: MenuTriggerSource::Shortcut,
+        };
+
+        assert!(frame(&[], None).is_empty());
+        assert_eq!(
+            frame(&[(Key::F5, true)], None),
+            [shortcut("file.export.png")]
+        );
+        assert!(frame(&[(Key::F5, false)], None).is_empty());
+
+        // Disabled actions ignore their shortcut, and modifiers must match exactly
+        assert!(frame(&[(Key::ModCtrl, true), (Key::S, true)], None).is_empty());
+        frame(&[(Key::S, false)], None);
+        // Shortcut routes take a frame to register
+        can_save.set(true);
+        frame(&[], None);
+        assert_eq!(frame(&[(Key::S, true)], None), [shortcut("file.save")]);
+        frame(&[(Key::S, false), (Key::ModCtrl, false)], None);
+        assert!(frame(&[(Key::S, true)], None).is_empty());
+        frame(&[(Key::S, false)], None);
+
+        // The top-level action is drawn in the menu bar, after the File menu
+        let quit = [file_width + 30.0, 8.0];
+        frame(&[], Some((quit, false)));
+        frame(&[], Some((quit, true)));
+        assert_eq!(
+            frame(&[], Some((quit, false))),
+            [MenuTrigger {
+                id: "quit".into(),
+                source: MenuTriggerSource::Menu,
+            }]
+        );
+    }
+}
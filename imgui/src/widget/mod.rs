@@ -1,13 +1,19 @@
+pub mod code_editor;
 pub mod color_editors;
 pub mod combo_box;
+pub mod command_palette;
 pub mod drag;
 pub mod image;
 pub mod list_box;
+pub mod memory_editor;
 pub mod menu;
+pub mod menu_model;
 pub mod misc;
 pub mod progress_bar;
+pub mod searchable_combo;
 pub mod selectable;
 pub mod slider;
 pub mod tab;
 pub mod text;
 pub mod tree;
+pub mod tree_view;
@@ -0,0 +1,419 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::ops::Range;
+
+use crate::text_match::{chars_eq_ignore_case, fuzzy_match};
+use crate::{ComboBox, ComboBoxFlags, Key, ListClipper, StyleColor, Ui};
+
+/// How a [SearchableCombo] matches its query against item labels.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ComboSearchMode {
+    /// Items whose label contains the query, ignoring case, in their original order.
+    Substring,
+    /// Items whose label contains the characters of the query in order, ignoring case, best
+    /// matches first. Consecutive characters and characters at the start of a word score higher.
+    #[default]
+    Fuzzy,
+}
+
+#[derive(Clone, Debug)]
+struct ComboMatch {
+    index: usize,
+    score: i32,
+    /// Byte ranges of the label which matched the query.
+    ranges: Vec<Range<usize>>,
+}
+
+/// A combo box with a search field at the top of its popup.
+///
+/// Typing filters the items, the matched parts of the labels are highlighted, and the
+/// highlighted item can be moved with the up/down arrow keys and picked with Enter. Only the
+/// visible items are drawn, so it stays fast with thousands of items.
+///
+/// The search state is kept between frames, so a `SearchableCombo` should be stored alongside
+/// the rest of the UI state rather than recreated every frame.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::borrow::Cow;
+/// # let mut ctx = imgui::Context::create();
+/// # let ui = ctx.frame();
+/// let assets = ["grass.png", "stone.png", "water.png"];
+/// let mut current = 0;
+/// let mut combo = imgui::SearchableCombo::new("Texture");
+/// if combo.build(ui, &mut current, &assets, |s| Cow::Borrowed(s)) {
+///     println!("picked {}", assets[current]);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SearchableCombo {
+    label: String,
+    flags: ComboBoxFlags,
+    mode: ComboSearchMode,
+    hint: String,
+    highlight_color: Option<[f32; 4]>,
+    max_visible_items: usize,
+    query: String,
+    matches: Vec<ComboMatch>,
+    /// Position of the highlighted item in `matches`.
+    highlighted: usize,
+    items_count: usize,
+}
+
+impl SearchableCombo {
+    /// Creates a new searchable combo box with the given label.
+    pub fn new(label: impl Into<String>) -> Self {
+        SearchableCombo {
+            label: label.into(),
+            flags: ComboBoxFlags::empty(),
+            mode: ComboSearchMode::default(),
+            hint: String::from("Search"),
+            highlight_color: None,
+            max_visible_items: 12,
+            query: String::new(),
+            matches: Vec::new(),
+            highlighted: 0,
+            items_count: 0,
+        }
+    }
+
+    /// Replaces all current combo box flags with the given flags. The height flags are ignored,
+    /// use [max_visible_items](Self::max_visible_items) instead.
+    pub fn flags(mut self, flags: ComboBoxFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets how the query is matched against item labels.
+    ///
+    /// Default: `ComboSearchMode::Fuzzy`
+    pub fn mode(mut self, mode: ComboSearchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the hint shown in the empty search field.
+    ///
+    /// Default: `"Search"`
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+
+    /// Sets the color of the matched parts of the labels.
+    ///
+    /// Default: the `CheckMark` style color.
+    pub fn highlight_color(mut self, color: impl Into<[f32; 4]>) -> Self {
+        self.highlight_color = Some(color.into());
+        self
+    }
+
+    /// Sets how many items are visible before the list scrolls.
+    ///
+    /// Default: 12
+    pub fn max_visible_items(mut self, count: usize) -> Self {
+        self.max_visible_items = count.max(1);
+        self
+    }
+
+    /// Returns the current search query.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Builds the combo box for choosing from a slice of values, in the same way as
+    /// [`Ui::combo`].
+    ///
+    /// Returns `true` if `current_item` was changed.
+    #[doc(alias = "BeginCombo")]
+    pub fn build<V, L>(
+        &mut self,
+        ui: &Ui,
+        current_item: &mut usize,
+        items: &[V],
+        label_fn: L,
+    ) -> bool
+    where
+        for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    {
+        // The item list has its own height, so let the popup grow to fit it
+        let height_flags = ComboBoxFlags::HEIGHT_SMALL
+            | ComboBoxFlags::HEIGHT_REGULAR
+            | ComboBoxFlags::HEIGHT_LARGE
+            | ComboBoxFlags::HEIGHT_LARGEST;
+        let label_fn = &label_fn;
+        let preview_value = items.get(*current_item).map(label_fn);
+        let combo = ComboBox {
+            label: &self.label,
+            preview_value,
+            flags: (self.flags - height_flags) | ComboBoxFlags::HEIGHT_LARGEST,
+            ui,
+        }
+        .begin();
+        let Some(_combo) = combo else {
+            return false;
+        };
+
+        let appearing = ui.is_window_appearing();
+        if appearing {
+            self.query.clear();
+            ui.set_keyboard_focus_here();
+        }
+        ui.set_next_item_width(-f32::MIN_POSITIVE);
+        let edited = ui
+            .input_text("##search", &mut self.query)
+            .hint(&self.hint)
+            .build();
+
+        if appearing || edited || items.len() != self.items_count {
+            self.refilter(items, label_fn);
+            self.highlighted = if appearing {
+                self.matches
+                    .iter()
+                    .position(|m| m.index == *current_item)
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+        }
+
+        let mut changed = false;
+        let mut moved = appearing;
+        if !self.matches.is_empty() {
+            if ui.is_key_pressed(Key::UpArrow) {
+                self.highlighted = self.highlighted.saturating_sub(1);
+                moved = true;
+            }
+            if ui.is_key_pressed(Key::DownArrow) {
+                self.highlighted = (self.highlighted + 1).min(self.matches.len() - 1);
+                moved = true;
+            }
+        }
+        if ui.is_key_pressed(Key::Enter) || ui.is_key_pressed(Key::KeypadEnter) {
+            if let Some(m) = self.matches.get(self.highlighted) {
+                *current_item = m.index;
+                changed = true;
+            }
+            ui.close_current_popup();
+        } else if ui.is_key_pressed(Key::Escape) {
+            ui.close_current_popup();
+        }
+
+        if self.matches.is_empty() {
+            ui.text_disabled("No matches");
+            return changed;
+        }
+
+        let visible = self.matches.len().min(self.max_visible_items);
+        let height = visible as f32 * ui.text_line_height_with_spacing();
+        let text_color = ui.style_color(StyleColor::Text);
+        let highlight_color = self
+            .highlight_color
+            .unwrap_or_else(|| ui.style_color(StyleColor::CheckMark));
+        let mouse_moved = ui.io().mouse_delta != [0.0, 0.0];
+
+        ui.child_window("##items").size([0.0, height]).build(|| {
+            let mut clipper = ListClipper::new(self.matches.len() as i32).begin(ui);
+            if moved {
+                clipper.include_item_by_index(self.highlighted as i32);
+            }
+            for row in clipper.iter() {
+                let row = row as usize;
+                let m = &self.matches[row];
+                let _id = ui.push_id_usize(m.index);
+                let pos = ui.cursor_screen_pos();
+                // Selectables only close popups from the popup window itself, not from this child
+                if ui
+                    .selectable_config("##item")
+                    .selected(row == self.highlighted)
+                    .close_popups(false)
+                    .build()
+                {
+                    *current_item = m.index;
+                    changed = true;
+                    ui.close_current_popup();
+                }
+                if mouse_moved && ui.is_item_hovered() {
+                    self.highlighted = row;
+                }
+                if moved && row == self.highlighted && !ui.is_item_visible() {
+                    ui.set_scroll_here_y_with_ratio(0.5);
+                }
+
+                let label = label_fn(&items[m.index]);
+                draw_highlighted(ui, pos, &label, &m.ranges, text_color, highlight_color);
+            }
+        });
+        changed
+    }
+
+    fn refilter<V, L>(&mut self, items: &[V], label_fn: &L)
+    where
+        for<'b> L: Fn(&'b V) -> Cow<'b, str>,
+    {
+        self.items_count = items.len();
+        self.matches.clear();
+        for (index, item) in items.iter().enumerate() {
+            let label = label_fn(item);
+            let found = match self.mode {
+                ComboSearchMode::Substring => substring_match(&label, &self.query)
+                    .map(|range| (0, range.into_iter().collect())),
+                ComboSearchMode::Fuzzy => fuzzy_match(&label, &self.query),
+            };
+            if let Some((score, ranges)) = found {
+                self.matches.push(ComboMatch {
+                    index,
+                    score,
+                    ranges,
+                });
+            }
+        }
+        if self.mode == ComboSearchMode::Fuzzy {
+            // Stable, so equally good matches stay in item order
+            self.matches.sort_by_key(|m| Reverse(m.score));
+        }
+    }
+}
+
+/// Draws `label` at `pos` in the current window, with the byte `ranges` in `highlight_color`.
+pub(super) fn draw_highlighted(
+    ui: &Ui,
+    pos: [f32; 2],
+    label: &str,
+    ranges: &[Range<usize>],
+    text_color: [f32; 4],
+    highlight_color: [f32; 4],
+) {
+    let draw_list = ui.get_window_draw_list();
+    let mut x = pos[0];
+    let mut segment = |range: Range<usize>, color: [f32; 4]| {
+        let text = &label[range];
+        draw_list.add_text([x, pos[1]], color, text);
+        x += ui.calc_text_size(text)[0];
+    };
+    let mut last = 0;
+    for range in ranges {
+        segment(last..range.start, text_color);
+        segment(range.clone(), highlight_color);
+        last = range.end;
+    }
+    segment(last..label.len(), text_color);
+}
+
+/// Returns the byte range of the first occurrence of `needle` in `haystack`, ignoring case, or
+/// `Some(None)` if `needle` is empty.
+fn substring_match(haystack: &str, needle: &str) -> Option<Option<Range<usize>>> {
+    if needle.is_empty() {
+        return Some(None);
+    }
+    let needle: Vec<char> = needle.chars().collect();
+    let haystack: Vec<(usize, char)> = haystack.char_indices().collect();
+    haystack
+        .windows(needle.len())
+        .find(|window| {
+            window
+                .iter()
+                .zip(&needle)
+                .all(|(&(_, h), &n)| chars_eq_ignore_case(h, n))
+        })
+        .map(|window| {
+            let (start, _) = window[0];
+            let (last, c) = window[window.len() - 1];
+            Some(start..last + c.len_utf8())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::Condition;
+
+    #[test]
+    fn substring() {
+        assert_eq!(substring_match("Stone Wall", "wall"), Some(Some(6..10)));
+        assert_eq!(substring_match("Stone Wall", ""), Some(None));
+        assert_eq!(substring_match("Stone Wall", "walls"), None);
+        assert_eq!(substring_match("Größe", "ÖSSE"), None);
+        assert_eq!(substring_match("Größe", "Öß"), Some(Some(2..6)));
+    }
+
+    #[test]
+    fn refilter_orders_fuzzy_matches() {
+        let items = ["grassway", "stone_wall", "sandwich", "water"];
+        let mut combo = SearchableCombo::new("combo");
+        combo.query = String::from("sw");
+        combo.refilter(&items, &|s: &&str| Cow::Borrowed(*s));
+        let order: Vec<usize> = combo.matches.iter().map(|m| m.index).collect();
+        assert_eq!(order, vec![1, 2, 0]);
+
+        let mut combo = SearchableCombo::new("combo").mode(ComboSearchMode::Substring);
+        combo.query = String::from("A");
+        combo.refilter(&items, &|s: &&str| Cow::Borrowed(*s));
+        let order: Vec<usize> = combo.matches.iter().map(|m| m.index).collect();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+    }
+
+    /// Gives a closure the higher-ranked signature `build` expects.
+    fn label_fn<V, L: for<'b> Fn(&'b V) -> Cow<'b, str>>(label_fn: L) -> L {
+        label_fn
+    }
+
+    #[test]
+    fn clicking_an_item_closes_the_popup() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let items = ["apple", "banana", "cherry"];
+        let mut combo = SearchableCombo::new("fruit");
+        let mut current = 2;
+
+        // Returns whether the item changed, whether a popup is open, and the positions of the
+        // combo and of the items that were drawn
+        let mut frame = |ctx: &mut crate::Context, mouse: Option<([f32; 2], bool)>| {
+            if let Some((pos, down)) = mouse {
+                ctx.io_mut().add_mouse_pos_event(pos);
+                ctx.io_mut()
+                    .add_mouse_button_event(crate::MouseButton::Left, down);
+            }
+            let rects = RefCell::new(HashMap::new());
+            let ui = ctx.frame();
+            let mut result = (false, false);
+            ui.window("combo")
+                .position([0.0, 0.0], Condition::Always)
+                .size([300.0, 300.0], Condition::Always)
+                .build(|| {
+                    // The labels of the list are drawn right after their selectables
+                    let label_fn = label_fn(|item: &&'static str| {
+                        rects.borrow_mut().insert(*item, ui.item_rect_min());
+                        Cow::Borrowed(*item)
+                    });
+                    let changed = combo.build(ui, &mut current, &items, label_fn);
+                    result = (changed, ui.is_any_popup_open());
+                    rects.borrow_mut().insert("combo", ui.item_rect_min());
+                });
+            ctx.render();
+            (result.0, result.1, rects.into_inner())
+        };
+        // Clicks at `pos`, and returns whether the item changed, and the state a few frames
+        // later, once the popup settled
+        let mut click = |ctx: &mut crate::Context, pos: [f32; 2]| {
+            let pos = [pos[0] + 4.0, pos[1] + 4.0];
+            frame(ctx, Some((pos, false)));
+            frame(ctx, Some((pos, true)));
+            let (changed, _, _) = frame(ctx, Some((pos, false)));
+            frame(ctx, None);
+            let (_, open, rects) = frame(ctx, None);
+            (changed, open, rects)
+        };
+
+        let (_, _, rects) = click(&mut ctx, [-100.0, -100.0]);
+        let (_, open, rects) = click(&mut ctx, rects["combo"]);
+        assert!(open);
+        let (changed, open, _) = click(&mut ctx, rects["apple"]);
+        assert!(changed);
+        assert!(!open);
+        assert_eq!(current, 0);
+    }
+}
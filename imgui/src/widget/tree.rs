@@ -47,6 +47,12 @@ bitflags!(
         const SPAN_AVAIL_WIDTH = sys::ImGuiTreeNodeFlags_SpanAvailWidth;
         /// Extend hit box to the left-most and right-most edges (bypass the indented area)
         const SPAN_FULL_WIDTH = sys::ImGuiTreeNodeFlags_SpanFullWidth;
+        /// Narrow hit box to the width of the label, for nodes followed by other items on the
+        /// same line
+        const SPAN_TEXT_WIDTH = sys::ImGuiTreeNodeFlags_SpanTextWidth;
+        /// Frame will span all columns of its container table (text will still fit in current
+        /// column)
+        const SPAN_ALL_COLUMNS = sys::ImGuiTreeNodeFlags_SpanAllColumns;
         /// (WIP) Nav: left direction may move to this tree node from any of its child
         const NAV_LEFT_JUMPS_BACK_HERE = sys::ImGuiTreeNodeFlags_NavLeftJumpsBackHere;
     }
@@ -55,7 +61,7 @@ bitflags!(
 static FMT: &[u8] = b"%s\0";
 
 #[inline]
-fn fmt_ptr() -> *const c_char {
+pub(super) fn fmt_ptr() -> *const c_char {
     FMT.as_ptr() as *const c_char
 }
 
@@ -0,0 +1,419 @@
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use super::tree::fmt_ptr;
+use crate::sys;
+use crate::{Condition, ListClipper, MouseButton, TreeNodeFlags, Ui};
+
+/// Provides the nodes of a [TreeView].
+///
+/// Nodes are identified by an [Id](Self::Id) which must stay the same across frames. Children
+/// are only requested for open nodes, and are cached by the tree view until it is refreshed, so
+/// they can be fetched lazily.
+pub trait TreeViewSource {
+    /// Identifies a node.
+    type Id: Copy + Eq + Hash;
+
+    /// Appends the root nodes to `out`.
+    fn roots(&mut self, out: &mut Vec<Self::Id>);
+
+    /// Returns the number of children of `node`. This is only used to tell leaves apart, so it
+    /// may be an estimate as long as it is `0` exactly for leaves.
+    fn child_count(&mut self, node: Self::Id) -> usize;
+
+    /// Appends the children of `node` to `out`. This is only called for open nodes.
+    fn children(&mut self, node: Self::Id, out: &mut Vec<Self::Id>);
+
+    /// Returns the label of `node`.
+    fn label(&self, node: Self::Id) -> Cow<'_, str>;
+
+    /// Returns an icon drawn before the label of `node`, typically a glyph of an icon font.
+    fn icon(&self, _node: Self::Id) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// Returns `true` if `node` is drawn as selected.
+    fn is_selected(&self, _node: Self::Id) -> bool {
+        false
+    }
+
+    /// Returns the parent of `node`, which is needed to open the ancestors of a node in
+    /// [TreeView::scroll_to_node].
+    fn parent(&mut self, _node: Self::Id) -> Option<Self::Id> {
+        None
+    }
+}
+
+/// What happened in a [TreeView] this frame, as returned by [TreeView::build].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TreeViewResponse<Id> {
+    /// The node which was clicked, if any. Clicks which open or close a node aren't included.
+    pub clicked: Option<Id>,
+    /// The node which was double-clicked, if any.
+    pub double_clicked: Option<Id>,
+    /// The node which was opened (`true`) or closed (`false`) by the user, if any.
+    pub toggled: Option<(Id, bool)>,
+    /// The node which is hovered, if any.
+    pub hovered: Option<Id>,
+}
+
+impl<Id> Default for TreeViewResponse<Id> {
+    fn default() -> Self {
+        TreeViewResponse {
+            clicked: None,
+            double_clicked: None,
+            toggled: None,
+            hovered: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Row<Id> {
+    node: Id,
+    depth: usize,
+    has_children: bool,
+}
+
+/// A tree of nodes from a [TreeViewSource], which only draws the visible rows.
+///
+/// The tree is kept between frames, as it keeps the open nodes, the children fetched from the
+/// source, and the flattened list of visible rows, which is only rebuilt when nodes are opened or
+/// closed. Every frame, [build](Self::build) draws it with a [ListClipper], so the cost of a frame
+/// doesn't depend on the size of the tree.
+///
+/// When the children of a node change, call [refresh_node](Self::refresh_node), or
+/// [refresh](Self::refresh) when the whole tree changes.
+///
+/// With the `tables-api` feature, `build_table_rows` draws the tree as the first column of a
+/// table.
+#[derive(Clone, Debug)]
+pub struct TreeView<Id> {
+    flags: TreeNodeFlags,
+    open: HashSet<Id>,
+    roots: Option<Vec<Id>>,
+    children: HashMap<Id, Vec<Id>>,
+    rows: Vec<Row<Id>>,
+    rows_dirty: bool,
+    scroll_to: Option<Id>,
+}
+
+impl<Id: Copy + Eq + Hash> Default for TreeView<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: Copy + Eq + Hash> TreeView<Id> {
+    /// Creates a tree view with all nodes closed.
+    pub fn new() -> Self {
+        TreeView {
+            flags: TreeNodeFlags::OPEN_ON_ARROW
+                | TreeNodeFlags::OPEN_ON_DOUBLE_CLICK
+                | TreeNodeFlags::SPAN_AVAIL_WIDTH,
+            open: HashSet::new(),
+            roots: None,
+            children: HashMap::new(),
+            rows: Vec::new(),
+            rows_dirty: true,
+            scroll_to: None,
+        }
+    }
+
+    /// Replaces the flags of the tree nodes, which default to opening on the arrow or on double
+    /// click and spanning the available width.
+    ///
+    /// [TreeNodeFlags::LEAF], [TreeNodeFlags::SELECTED] and
+    /// [TreeNodeFlags::NO_TREE_PUSH_ON_OPEN] are set by the tree view.
+    pub fn flags(mut self, flags: TreeNodeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Returns `true` if the given node is open.
+    pub fn is_open(&self, node: Id) -> bool {
+        self.open.contains(&node)
+    }
+
+    /// Opens or closes the given node.
+    pub fn set_open(&mut self, node: Id, open: bool) {
+        let changed = if open {
+            self.open.insert(node)
+        } else {
+            self.open.remove(&node)
+        };
+        self.rows_dirty |= changed;
+    }
+
+    /// Opens every node of the tree, fetching all children from the source.
+    pub fn expand_all<S: TreeViewSource<Id = Id>>(&mut self, source: &mut S) {
+        let mut stack = self.roots(source).to_vec();
+        while let Some(node) = stack.pop() {
+            if source.child_count(node) > 0 {
+                self.open.insert(node);
+                stack.extend_from_slice(self.children(source, node));
+            }
+        }
+        self.rows_dirty = true;
+    }
+
+    /// Closes every node of the tree.
+    pub fn collapse_all(&mut self) {
+        self.open.clear();
+        self.rows_dirty = true;
+    }
+
+    /// Opens the ancestors of the given node, and scrolls to it on the next
+    /// [build](Self::build). Ancestors are found with [TreeViewSource::parent].
+    pub fn scroll_to_node<S: TreeViewSource<Id = Id>>(&mut self, source: &mut S, node: Id) {
+        let mut parent = source.parent(node);
+        while let Some(node) = parent {
+            self.set_open(node, true);
+            parent = source.parent(node);
+        }
+        self.scroll_to = Some(node);
+    }
+
+    /// Forgets all children fetched from the source, so they are fetched again.
+    pub fn refresh(&mut self) {
+        self.roots = None;
+        self.children.clear();
+        self.rows_dirty = true;
+    }
+
+    /// Forgets the children of the given node fetched from the source, so they are fetched
+    /// again.
+    pub fn refresh_node(&mut self, node: Id) {
+        self.children.remove(&node);
+        self.rows_dirty = true;
+    }
+
+    /// Returns the visible nodes, in the order they are displayed. This is updated by
+    /// [build](Self::build).
+    pub fn visible_nodes(&self) -> impl Iterator<Item = Id> + '_ {
+        self.rows.iter().map(|row| row.node)
+    }
+
+    /// Draws the tree.
+    pub fn build<S: TreeViewSource<Id = Id>>(
+        &mut self,
+        ui: &Ui,
+        source: &mut S,
+    ) -> TreeViewResponse<Id> {
+        self.build_internal(ui, source, false, |_, _, _| {})
+    }
+
+    /// Draws the tree as the first column of the current table, with one table row per node.
+    ///
+    /// After each node, `row` is called to draw the other columns of its row, by calling
+    /// [table_next_column](Ui::table_next_column). The nodes span all columns, so the whole
+    /// row can be clicked.
+    #[cfg(feature = "tables-api")]
+    pub fn build_table_rows<S, F>(
+        &mut self,
+        ui: &Ui,
+        source: &mut S,
+        row: F,
+    ) -> TreeViewResponse<Id>
+    where
+        S: TreeViewSource<Id = Id>,
+        F: FnMut(&Ui, &mut S, Id),
+    {
+        self.build_internal(ui, source, true, row)
+    }
+
+    fn build_internal<S, F>(
+        &mut self,
+        ui: &Ui,
+        source: &mut S,
+        table: bool,
+        mut row_fn: F,
+    ) -> TreeViewResponse<Id>
+    where
+        S: TreeViewSource<Id = Id>,
+        F: FnMut(&Ui, &mut S, Id),
+    {
+        let mut response = TreeViewResponse::default();
+        if self.rows_dirty {
+            self.rebuild_rows(source);
+        }
+
+        let indent_spacing = unsafe { ui.style() }.indent_spacing;
+        let mut clipper = ListClipper::new(self.rows.len() as i32).begin(ui);
+        if let Some(node) = self.scroll_to.take() {
+            if let Some(pos) = self.rows.iter().position(|row| row.node == node) {
+                clipper.scroll_to_item(pos as i32, 0.5);
+            }
+        }
+
+        for pos in clipper.iter() {
+            let row = self.rows[pos as usize];
+            if table {
+                #[cfg(feature = "tables-api")]
+                {
+                    ui.table_next_row();
+                    ui.table_next_column();
+                }
+            }
+
+            let mut flags = self.flags | TreeNodeFlags::NO_TREE_PUSH_ON_OPEN;
+            if !row.has_children {
+                flags |= TreeNodeFlags::LEAF;
+            }
+            if source.is_selected(row.node) {
+                flags |= TreeNodeFlags::SELECTED;
+            }
+            if table {
+                flags |= TreeNodeFlags::SPAN_ALL_COLUMNS;
+            }
+
+            let label = match source.icon(row.node) {
+                Some(icon) => format!("{} {}", icon, source.label(row.node)),
+                None => source.label(row.node).into_owned(),
+            };
+            let indent = row.depth as f32 * indent_spacing;
+            // indenting by 0.0 would indent by the default spacing
+            if row.depth > 0 {
+                ui.indent_by(indent);
+            }
+            let was_open = row.has_children && self.open.contains(&row.node);
+            let open = {
+                let _id = ui.push_id_u64(node_hash(&row.node));
+                let (id, label) = ui.scratch_txt_two("##node", label);
+                unsafe {
+                    sys::igSetNextItemOpen(was_open, Condition::Always as i32);
+                    sys::igTreeNodeEx_StrStr(id, flags.bits() as i32, fmt_ptr(), label)
+                }
+            };
+            if row.depth > 0 {
+                ui.unindent_by(indent);
+            }
+
+            if row.has_children && open != was_open {
+                response.toggled = Some((row.node, open));
+            } else if ui.is_item_clicked() {
+                response.clicked = Some(row.node);
+            }
+            if ui.is_item_hovered() {
+                response.hovered = Some(row.node);
+                if ui.is_mouse_double_clicked(MouseButton::Left) {
+                    response.double_clicked = Some(row.node);
+                }
+            }
+
+            row_fn(ui, source, row.node);
+        }
+
+        if let Some((node, open)) = response.toggled {
+            self.set_open(node, open);
+        }
+        response
+    }
+
+    fn rebuild_rows<S: TreeViewSource<Id = Id>>(&mut self, source: &mut S) {
+        let mut stack: Vec<_> = self.roots(source).iter().rev().map(|&n| (n, 0)).collect();
+        self.rows.clear();
+        while let Some((node, depth)) = stack.pop() {
+            let has_children = source.child_count(node) > 0;
+            self.rows.push(Row {
+                node,
+                depth,
+                has_children,
+            });
+            if has_children && self.open.contains(&node) {
+                let children = self.children(source, node);
+                stack.extend(children.iter().rev().map(|&child| (child, depth + 1)));
+            }
+        }
+        self.rows_dirty = false;
+    }
+
+    fn roots<S: TreeViewSource<Id = Id>>(&mut self, source: &mut S) -> &[Id] {
+        self.roots.get_or_insert_with(|| {
+            let mut roots = Vec::new();
+            source.roots(&mut roots);
+            roots
+        })
+    }
+
+    fn children<S: TreeViewSource<Id = Id>>(&mut self, source: &mut S, node: Id) -> &[Id] {
+        self.children.entry(node).or_insert_with(|| {
+            let mut children = Vec::new();
+            source.children(node, &mut children);
+            children
+        })
+    }
+}
+
+/// Nodes are identified to imgui by the hash of their id.
+fn node_hash<Id: Hash>(node: &Id) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Node `n` has the children `10n + 1..=10n + 3`, down to a depth of 4.
+    struct Decimal;
+
+    impl TreeViewSource for Decimal {
+        type Id = u32;
+
+        fn roots(&mut self, out: &mut Vec<u32>) {
+            out.push(0);
+        }
+
+        fn child_count(&mut self, node: u32) -> usize {
+            if node < 1000 {
+                3
+            } else {
+                0
+            }
+        }
+
+        fn children(&mut self, node: u32, out: &mut Vec<u32>) {
+            out.extend((1..=3).map(|i| node * 10 + i));
+        }
+
+        fn label(&self, node: u32) -> Cow<'_, str> {
+            node.to_string().into()
+        }
+
+        fn parent(&mut self, node: u32) -> Option<u32> {
+            (node > 0).then_some(node / 10)
+        }
+    }
+
+    #[test]
+    fn test_tree_view_flattens_open_nodes() {
+        let (_guard, mut ctx) = crate::test::test_ctx_initialized();
+        let mut tree = TreeView::new();
+        let mut build = |tree: &mut TreeView<u32>| {
+            let ui = ctx.frame();
+            ui.window("tree").build(|| {
+                tree.build(ui, &mut Decimal);
+            });
+            let nodes: Vec<_> = tree.visible_nodes().collect();
+            ctx.render();
+            nodes
+        };
+
+        assert_eq!(build(&mut tree), [0]);
+
+        tree.set_open(0, true);
+        tree.set_open(2, true);
+        assert_eq!(build(&mut tree), [0, 1, 2, 21, 22, 23, 3]);
+
+        tree.collapse_all();
+        tree.scroll_to_node(&mut Decimal, 132);
+        assert_eq!(build(&mut tree), [0, 1, 11, 12, 13, 131, 132, 133, 2, 3]);
+
+        tree.expand_all(&mut Decimal);
+        assert_eq!(build(&mut tree).len(), 1 + 3 + 9 + 27 + 81);
+    }
+}
@@ -95,7 +95,7 @@ fn get_definitions(definitions: &Path) -> Result<Vec<String>> {
             let args = func["argsT"].as_array().unwrap();
             if !args
                 .iter()
-                .any(|a| a["type"].as_str().map_or(false, bad_arg_type))
+                .any(|a| a["type"].as_str().is_some_and(bad_arg_type))
             {
                 let name = func["ov_cimguiname"]
                     .as_str()